@@ -1,14 +1,38 @@
 use std::fmt::Write;
 
-use crate::{compiler::llvm::{components::{control_flow::unreachable, r#type::{r#struct, struct_init, typecast}}, ir::ppbind}, parser::Value};
+use crate::{compiler::{Target, llvm::{components::{control_flow::unreachable, r#type::{enum_init, float_constant, r#enum, r#struct, struct_init, typecast}}, ir::ppbind}}, parser::Value};
 
-use super::{components::{control_flow::{r#break, r#continue, r#else, r#if, r#loop}, global::{call, declare, define, ret}, memory::{array, deref, get_var, load_address, modify_pointer, modify_var, new_var, ptrinit, tuple}, temp_op::{expr, unary}}, ir::IR};
+use super::{components::{control_flow::{block, r#break, r#continue, r#else, r#if, r#loop, r#match, valued}, global::{call, declare, define, dyn_cast, dyn_data, r#static, ret, vtable_method}, memory::{array, deref, get_var, load_address, modify_pointer, modify_var, new_var, ptrinit, slice, slice_field, tuple}, temp_op::{expr, unary}}, ir::IR};
+
+/// Bytes of string as LLVM constant, anything besides printable ASCII is written as '\XX'
+fn c_string (x: &str) -> String {
+    let mut bytes = String::new();
+    for b in x.bytes() {
+        if (b' '..=b'~').contains(&b) && b != b'"' && b != b'\\' { bytes.push(b as char); }
+        else { write!(bytes, "\\{b:02X}").unwrap(); }
+    }
+    format!("c\"{bytes}\"")
+}
+
+/// Global constant with bytes of string
+fn string_constant (ir: &mut IR, x: &str) -> String {
+    ir.constant(format!("[{} x i8] {}", x.len(), c_string(x)))
+}
+
+/// `&[u8]` slice over bytes of string, it's constant and may be used in initializers of globals
+pub fn string_slice (ir: &mut IR, x: &str) -> String {
+    let bytes = string_constant(ir, x);
+    format!("{{ ptr {bytes}, i{} {} }}", ir.ptr_bits(), x.len())
+}
 
 pub fn translate<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>) -> Option<String> {
     match value {
         Value::Int(x)  => return Some(x.to_string()),
         Value::SInt(x) => return Some(x.to_string()),
         Value::Bool(x) => return Some((x as u8).to_string()),
+        Value::Char(x) => return Some((x as u32).to_string()),
+        Value::Byte(x) => return Some(x.to_string()),
+        Value::Float(x) => return Some(float_constant(x, ir.get_current_type())),
         Value::Expr(x) => {
             let (lhs, rhs, op) = *x;
             return Some(expr(ir, instructions, lhs, rhs, op))
@@ -36,30 +60,17 @@ pub fn translate<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Val
         Value::Get(name, typ) => return Some(get_var(ir, instructions, name, typ)),
         Value::LoadFromPtr(value, typ) => return Some(deref(ir, instructions, *value, typ)),
         Value::Ptr(to, _) => {
-            dbg!(&to);
             match *to {
                 Value::Get(name, _) => {
                     return Some(format!("%{}", ppbind(ir.get_bind(name).unwrap())))
                 },
-                Value::String(x) => {
-                    let len = x.len();
-                    let x = x.replace("\n", "\\0A");
-                    let s = ir.constant(format!("[{len} x i8] c\"{x}\""));
-                    return Some(format!("{s}"))
-                },
                 to => return Some(ptrinit(ir, instructions, to))
             }
         }
         Value::FunctionPointer(n, _, _) => return Some(format!("@\"{n}\"")),
-        Value::String(x) => {
-            if let Some(t) = ir.get_mark_put_in().cloned() {
-                let len = x.len();
-                let x = x.replace("\n", "\\0A");
-                let s = ir.constant(format!("[{len} x i8] c\"{x}\""));
-                let t = ppbind(&t);
-                writeln!(instructions, "call void @llvm.memcpy.inline.p0.p0.i64(ptr %{t}, ptr {s}, i64 {len}, i1 false)").unwrap();
-            }
-        },
+        Value::Static { name, typ, value, mutable } => r#static(ir, name, typ, *value, mutable),
+        Value::StaticPtr(name, _, _) => return Some(format!("@\"{name}\"")),
+        Value::String(x) => return Some(string_slice(ir, &x)),
         Value::Array(arr) => return array(ir, instructions, arr),
         // Value::LoadAddress(from, i, t) => return Some(load_address(ir, instructions, *from, i, t)),
         Value::LoadAddress(from, index, typ, _) => return Some(load_address(ir, instructions, *from, *index, typ)),
@@ -67,20 +78,34 @@ pub fn translate<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Val
         Value::Tuple(tup) => return tuple(ir, instructions, tup),
         Value::Struct { name, kv, .. } => r#struct(ir, name, kv),
         Value::StructInit(name, kv) => return struct_init(ir, instructions, name, kv),
+        Value::Enum { name, tag, payload, .. } => r#enum(ir, name, tag, payload),
+        Value::EnumInit { name, tag, payload } => return enum_init(ir, instructions, name, tag, payload),
 
-        Value::If { condition, body, else_then } => r#if(ir, instructions, *condition, body, *else_then),
-        Value::Else(body) => r#else(ir, instructions, body),
-        Value::Loop(body, label) => r#loop(ir, instructions, body, label),
-        Value::Break(label) => r#break(ir, instructions, label),
+        Value::If { condition, body, else_then } => r#if(ir, instructions, *condition, body, *else_then, None),
+        Value::Else(body) => r#else(ir, instructions, body, None),
+        Value::Loop(body, label) => r#loop(ir, instructions, body, label, None),
+        Value::Match { value, typ, arms } => r#match(ir, instructions, *value, typ, arms, None),
+        Value::Break(label, value) => r#break(ir, instructions, label, value.map(|x| *x)),
         Value::Continue(label) => r#continue(ir, instructions, label),
         Value::Unreachable => unreachable(instructions),
+        Value::Defer(body) => ir.defer(body),
+        Value::Block(body) => block(ir, instructions, body, None),
+        Value::Valued(value, typ) => return valued(ir, instructions, *value, typ),
 
         Value::Typecast(value, from, to) => return Some(typecast(ir, instructions, *value, from, to)),
+        Value::DynCast(value, vtable, _) => return Some(dyn_cast(ir, instructions, *value, vtable)),
+        Value::VtableMethod(value, index, _) => return Some(vtable_method(ir, instructions, *value, index)),
+        Value::DynData(value, _) => return Some(dyn_data(ir, instructions, *value)),
+        Value::MakeSlice(x, _) => return Some(slice(ir, instructions, x.0, x.1)),
+        Value::SliceData(value) => return Some(slice_field(ir, instructions, *value, 0)),
+        Value::SliceLen(value, _) => return Some(slice_field(ir, instructions, *value, 1)),
 
         // Value::ReservedLocals(a) => ir.count_locals(a),
         Value::SharedValue(x) => return translate(ir, instructions, x.replace(Value::Unreachable)),
-        Value::PromisedFunction { .. } | Value::PromisedStruct { .. } | Value::ReturnMark | Value::Namespace(_) => (),
-        _ => { dbg!(value); todo!() }
+        Value::FnTemplate { instances, .. } => instances.into_iter().for_each(|(_, _, x)| { translate(ir, instructions, Value::SharedValue(x)); }),
+        Value::StructTemplate { instances, .. } => instances.into_iter().for_each(|(_, x)| { translate(ir, instructions, Value::SharedValue(x)); }),
+        Value::PromisedFunction { .. } | Value::PromisedStruct { .. } | Value::PromisedEnum { .. } | Value::ReturnMark | Value::Namespace(_) => (),
+        _ => unreachable!("{value:?} can't be translated")
     }
     None
 }
@@ -91,8 +116,12 @@ pub fn translate_all<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, progra
     }
 }
 
-pub fn emit_llvm (program: Vec<Value>) -> String {
+pub fn emit_llvm (program: Vec<Value>, target: &Target) -> String {
     let mut ir = IR::default();
+    ir.set_ptr_bits(target.ptr_bits);
+    ir.global_write(&format!("target datalayout = \"{}\"", target.datalayout()));
+    ir.global_write(&format!("target triple = \"{}\"", target.triple()));
+    ir.global_write(&format!("%.slice = type {{ ptr, i{} }}", target.ptr_bits));
     let mut w = String::new();
     translate_all(&mut ir, &mut w, program);
     ir.move_global()
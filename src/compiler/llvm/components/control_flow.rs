@@ -1,10 +1,10 @@
 use std::fmt::Write;
 
-use crate::{compiler::llvm::{codegen::{translate, translate_all}, ir::IR}, parser::{r#type::Type, Value}};
+use crate::{compiler::llvm::{codegen::{translate, translate_all}, components::r#type::translate_type, ir::{ppbind, IR}}, parser::{components::pattern::{Arm, Pattern}, r#type::Type, Value}};
 
 fn is_terminator (v: &Value) -> bool {
     match v {
-        Value::Return(_) | Value::ReturnMark | Value::Break(_) | Value::Continue(_) | Value::Unreachable => true,
+        Value::Return(_) | Value::ReturnMark | Value::Break(_, _) | Value::Continue(_) | Value::Unreachable => true,
         _ => false
     }
 }
@@ -18,7 +18,34 @@ fn find_terminator (values: &[Value]) -> bool {
     }
 }
 
-pub fn r#if<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, condition: Value<'a>, body: Vec<Value<'a>>, next: Option<Value<'a>>) {
+/// Translates body, and stores value it ends with in `result` (pointer to it and its type) when it's set
+fn translate_body<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, mut body: Vec<Value<'a>>, result: Option<&(String, Type)>) {
+    let tail = if result.is_some() && !find_terminator(&body) { body.pop() } else { None };
+    translate_all(ir, instructions, body);
+    let (Some(tail), Some((ptr, typ))) = (tail, result) else { return };
+    if let Some(x) = ir.type_context(Some(typ.clone()), |ir| translate(ir, instructions, tail)) {
+        writeln!(instructions, "store {} {x}, ptr {ptr}", translate_type(typ)).unwrap();
+    }
+}
+
+/// `if`, `match`, block or `loop` used as value. Its branches (or `break`) store result in slot, that is read after it
+pub fn valued<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, typ: Type) -> Option<String> {
+    let (slot, put_in) = ir.seek_local(&typ);
+    // values inside of it have their own places
+    ir.unmark_put_in();
+    let result = (format!("%{}", ppbind(&slot)), typ);
+    match value {
+        Value::If { condition, body, else_then } => r#if(ir, instructions, *condition, body, *else_then, Some(&result)),
+        Value::Block(body) => block(ir, instructions, body, Some(&result)),
+        Value::Loop(body, label) => r#loop(ir, instructions, body, label, Some(&result)),
+        Value::Match { value, typ, arms } => r#match(ir, instructions, *value, typ, arms, Some(&result)),
+        _ => unreachable!()
+    }
+    if put_in { return None }
+    Some(load(ir, instructions, &result.1, &result.0))
+}
+
+pub fn r#if<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, condition: Value<'a>, body: Vec<Value<'a>>, next: Option<Value<'a>>, result: Option<&(String, Type)>) {
     // let typ = if let Value::Expr(ref x) = condition { default_type_expr(&x.0, &x.1, &x.2) } else { Type::Bool };
     let typ = Type::Bool;
     let condition = ir.type_context(Some(typ), |ir| translate(ir, instructions, condition)).unwrap();
@@ -26,7 +53,8 @@ pub fn r#if<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, condition: Valu
     let true_label = ir.temp();
     let terminates = find_terminator(&body);
     ir.join();
-    translate_all(ir, &mut bodybuf, body);
+    translate_body(ir, &mut bodybuf, body, result);
+    if !terminates { run_deferred(ir, &mut bodybuf, ir.depth()); }
     ir.leave();
     let false_label = ir.temp();
     let mut end_label = false_label;
@@ -34,7 +62,11 @@ pub fn r#if<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, condition: Valu
 
     let mut next_buf = String::new();
     if let Some(next) = next {
-        translate(ir, &mut next_buf, next);
+        match next {
+            Value::If { condition, body, else_then } => r#if(ir, &mut next_buf, *condition, body, *else_then, result),
+            Value::Else(body) => r#else(ir, &mut next_buf, body, result),
+            next => { translate(ir, &mut next_buf, next); }
+        }
         end_label = ir.last_temp();
     }
 
@@ -45,60 +77,261 @@ pub fn r#if<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, condition: Valu
     }
 }
 
-pub fn r#else<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>) {
+pub fn r#else<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>, result: Option<&(String, Type)>) {
     let terminates = find_terminator(&body);
     ir.join();
-    translate_all(ir, instructions, body);
+    translate_body(ir, instructions, body, result);
+    if !terminates { run_deferred(ir, instructions, ir.depth()); }
     ir.leave();
     let end = ir.temp();
     if !terminates { writeln!(instructions, "br label %{end}").unwrap(); }
 }
 
 
-pub fn r#loop<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>, label: Option<&'a str>) {
-    let (start_label, end_label) = if let Some(label) = label {
-        let l = ir.temp();
-        ir.bind(label, l, false);
-        ir.bind(CURR_LABEL, l, false);
-        (format!("{l}"), format!("end.{l}"))
-    } else {
-        let l = ir.temp();
-        ir.bind(CURR_LABEL, l, false);
-        (format!("{l}"), format!("end.{l}"))
-    };
+pub fn block<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>, result: Option<&(String, Type)>) {
+    let terminates = find_terminator(&body);
+    ir.join();
+    translate_body(ir, instructions, body, result);
+    if !terminates { run_deferred(ir, instructions, ir.depth()); }
+    ir.leave();
+}
+
+/// `result` is where `break` puts value of loop
+pub fn r#loop<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>, label: Option<&'a str>, result: Option<&(String, Type)>) {
+    let l = ir.temp();
+    if let Some(label) = label { ir.bind(label, l, false); }
+    if let Some(result) = result { ir.set_loop_result(l, result.clone()); }
+    ir.bind(CURR_LABEL, l, false);
+    let (start_label, end_label) = (format!("{l}"), format!("end.{l}"));
     writeln!(instructions, "br label %{start_label}").unwrap();
     writeln!(instructions, "{start_label}:").unwrap();
     let terminates = find_terminator(&body);
     let mut bodystr = String::new();
     ir.join();
+    ir.enter_loop(l);
     translate_all(ir, &mut bodystr, body);
+    if !terminates { run_deferred(ir, &mut bodystr, ir.depth()); }
     ir.leave();
     writeln!(instructions, "    {}", bodystr.trim().replace("\n", "\n    ")).unwrap();
     if !terminates { writeln!(instructions, "    br label %{start_label}").unwrap() }
     writeln!(instructions, "{end_label}:").unwrap();
 }
 
-pub fn r#break<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, label: Option<&'a str>) {
-    if let Some(label) = label {
-        let label = ir.get_bind(label).unwrap().0;
-        writeln!(instructions, "br label %end.{label}").unwrap();
-    }
-    else {
-        let label = ir.get_bind(CURR_LABEL).unwrap().0;
-        writeln!(instructions, "br label %end.{label}").unwrap();
+/// Value is stored as result of loop before scopes are left
+pub fn r#break<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, label: Option<&'a str>, value: Option<Value<'a>>) {
+    let label = ir.get_bind(label.unwrap_or(CURR_LABEL)).unwrap().0;
+    if let (Some(value), Some((ptr, typ))) = (value, ir.loop_result(label).cloned())
+        && let Some(x) = ir.type_context(Some(typ.clone()), |ir| translate(ir, instructions, value)) {
+        writeln!(instructions, "store {} {x}, ptr {ptr}", translate_type(&typ)).unwrap();
     }
+    run_deferred(ir, instructions, ir.loop_depth(label));
+    writeln!(instructions, "br label %end.{label}").unwrap();
 }
 pub fn r#continue<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, label: Option<&'a str>) {
     if let Some(label) = label {
         let label = ir.get_bind(label).unwrap().0;
+        run_deferred(ir, instructions, ir.loop_depth(label));
         writeln!(instructions, "br label %{label}").unwrap();
     }
     else {
         let label = ir.get_bind(CURR_LABEL).unwrap().0;
+        run_deferred(ir, instructions, ir.loop_depth(label));
         writeln!(instructions, "br label %{label}").unwrap();
     }
 }
 
+/// Emits code deferred in scopes from innermost one to `depth`, as they're left
+pub fn run_deferred<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, depth: usize) {
+    for (scope, body) in ir.deferred(depth) {
+        ir.at_depth(scope, |ir| {
+            ir.join();
+            translate_all(ir, instructions, body);
+            run_deferred(ir, instructions, ir.depth());
+            ir.leave();
+        });
+    }
+}
+
+/// Pointer to field `index` of value of type `typ` (tuple, struct or payload of enum) behind `ptr`
+fn field_ptr (ir: &mut IR, instructions: &mut impl Write, typ: &str, ptr: &str, index: usize) -> String {
+    let gep = ir.temp();
+    writeln!(instructions, "%{gep} = getelementptr inbounds {typ}, ptr {ptr}, i64 0, i32 {index}").unwrap();
+    format!("%{gep}")
+}
+
+fn load (ir: &mut IR, instructions: &mut impl Write, typ: &Type, ptr: &str) -> String {
+    let l = ir.temp();
+    writeln!(instructions, "%{l} = load {}, ptr {ptr}", translate_type(typ)).unwrap();
+    format!("%{l}")
+}
+
+/// Emits checks of value behind `ptr` against pattern, jumping to `fail` if it doesn't match. \
+/// Names bound by pattern are stored in new locals
+fn test_pattern<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, pattern: Pattern<'a>, typ: &Type, ptr: &str, fail: &str, id: &str) {
+    // condition is in temporary, its number names block
+    let check = |instructions: &mut dyn Write, c: usize| {
+        writeln!(instructions, "br i1 %{c}, label %{id}.ok{c}, label %{fail}").unwrap();
+        writeln!(instructions, "{id}.ok{c}:").unwrap();
+    };
+    match pattern {
+        Pattern::Wild => {}
+        Pattern::Bind(name) => {
+            let value = load(ir, instructions, typ, ptr);
+            let local = ir.reserve_local(typ);
+            writeln!(instructions, "store {} {value}, ptr %{}", translate_type(typ), ppbind(&local)).unwrap();
+            ir.bind(name, local.0, true);
+        }
+        Pattern::Bool(x) => {
+            let value = load(ir, instructions, typ, ptr);
+            let c = ir.temp();
+            writeln!(instructions, "%{c} = icmp eq i1 {value}, {}", x as u8).unwrap();
+            check(instructions, c);
+        }
+        Pattern::Range(a, b) => {
+            let value = load(ir, instructions, typ, ptr);
+            let typstr = translate_type(typ);
+            let c = if a == b {
+                let c = ir.temp();
+                writeln!(instructions, "%{c} = icmp eq {typstr} {value}, {a}").unwrap();
+                c
+            }
+            else {
+                // `value - a <= b - a` checks both bounds at once, as unsigned
+                let d = ir.temp();
+                writeln!(instructions, "%{d} = sub {typstr} {value}, {a}").unwrap();
+                let c = ir.temp();
+                writeln!(instructions, "%{c} = icmp ule {typstr} %{d}, {}", b - a).unwrap();
+                c
+            };
+            check(instructions, c);
+        }
+        Pattern::Fields(fields) => {
+            let typstr = translate_type(typ);
+            for (index, (p, t)) in fields.into_iter().enumerate() {
+                if let Pattern::Wild = p { continue }
+                let f = field_ptr(ir, instructions, &typstr, ptr, index);
+                test_pattern(ir, instructions, p, &t, &f, fail, id);
+            }
+        }
+        Pattern::Variant { index, tag, fields } => {
+            let typstr = translate_type(typ);
+            let tagptr = field_ptr(ir, instructions, &typstr, ptr, 0);
+            let value = load(ir, instructions, &tag, &tagptr);
+            let c = ir.temp();
+            writeln!(instructions, "%{c} = icmp eq {} {value}, {index}", translate_type(&tag)).unwrap();
+            check(instructions, c);
+            variant_fields(ir, instructions, fields, &typstr, ptr, fail, id);
+        }
+    }
+}
+
+fn variant_fields<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, fields: Vec<(Pattern<'a>, Type)>, typstr: &str, ptr: &str, fail: &str, id: &str) {
+    if fields.iter().all(|(p, _)| matches!(p, Pattern::Wild)) { return }
+    let layout = format!("{{{}}}", fields.iter().map(|(_, t)| translate_type(t)).collect::<Vec<_>>().join(", "));
+    let payload = field_ptr(ir, instructions, typstr, ptr, 1);
+    for (index, (p, t)) in fields.into_iter().enumerate() {
+        if let Pattern::Wild = p { continue }
+        let f = field_ptr(ir, instructions, &layout, &payload, index);
+        test_pattern(ir, instructions, p, &t, &f, fail, id);
+    }
+}
+
+/// Arms that test only single value (or tag of enum) against constants are lowered to `switch`, others to chain of checks
+pub fn r#match<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, typ: Type, arms: Vec<Arm<'a>>, result: Option<&(String, Type)>) {
+    let local = ir.reserve_local(&typ);
+    ir.mark_put_in(local.0);
+    if let Some(value) = ir.type_context(Some(typ.clone()), |ir| translate(ir, instructions, value)) {
+        writeln!(instructions, "store {} {value}, ptr %{}", translate_type(&typ), ppbind(&local)).unwrap();
+    }
+    ir.unmark_put_in();
+    let ptr = format!("%{}", ppbind(&local));
+    let id = format!("match{}", local.0);
+    let end = format!("{id}.end");
+    let none = format!("{id}.none");
+    let mut all_terminate = true;
+
+    let by_tag = arms.iter().any(|x| matches!(x.pattern, Pattern::Variant { .. }));
+    let switchable = (by_tag || matches!(typ, Type::I(_) | Type::U(_) | Type::Bool)) && arms.iter().all(|x| x.guard.is_none() && match &x.pattern {
+        Pattern::Wild | Pattern::Bind(_) | Pattern::Bool(_) => true,
+        Pattern::Range(a, b) => a == b,
+        Pattern::Variant { fields, .. } => fields.iter().all(|(p, _)| p.is_irrefutable()),
+        Pattern::Fields(_) => false
+    });
+
+    if switchable {
+        let typstr = translate_type(&typ);
+        let (value, vtyp) = if by_tag {
+            let tag = arms.iter().find_map(|x| if let Pattern::Variant { tag, .. } = &x.pattern { Some(tag.clone()) } else { None }).unwrap();
+            let tagptr = field_ptr(ir, instructions, &typstr, &ptr, 0);
+            (load(ir, instructions, &tag, &tagptr), tag)
+        }
+        else { (load(ir, instructions, &typ, &ptr), typ.clone()) };
+        let vtypstr = translate_type(&vtyp);
+
+        let mut cases: Vec<(i128, usize)> = vec![];
+        let mut default = None;
+        for (i, arm) in arms.iter().enumerate() {
+            let v = match &arm.pattern {
+                Pattern::Range(a, _) => *a,
+                Pattern::Bool(x) => *x as i128,
+                Pattern::Variant { index, .. } => *index as i128,
+                _ => { default = Some(i); break }
+            };
+            // repeated values are unreachable, they're already reported
+            if !cases.iter().any(|(x, _)| *x == v) { cases.push((v, i)); }
+        }
+        let default_label = if let Some(i) = default { format!("{id}.arm{i}") } else { none.clone() };
+        writeln!(instructions, "switch {vtypstr} {value}, label %{default_label} [").unwrap();
+        for (v, i) in &cases { writeln!(instructions, "    {vtypstr} {v}, label %{id}.arm{i}").unwrap(); }
+        writeln!(instructions, "]").unwrap();
+
+        for (i, arm) in arms.into_iter().enumerate() {
+            if default != Some(i) && !cases.iter().any(|(_, x)| *x == i) { continue }
+            writeln!(instructions, "{id}.arm{i}:").unwrap();
+            ir.join();
+            match arm.pattern {
+                Pattern::Variant { fields, .. } => variant_fields(ir, instructions, fields, &typstr, &ptr, &none, &id),
+                x @ Pattern::Bind(_) => test_pattern(ir, instructions, x, &typ, &ptr, &none, &id),
+                _ => {}
+            }
+            all_terminate &= arm_body(ir, instructions, arm.body, &end, result);
+            ir.leave();
+        }
+    }
+    else {
+        writeln!(instructions, "br label %{id}.arm0").unwrap();
+        let count = arms.len();
+        for (i, arm) in arms.into_iter().enumerate() {
+            let next = if i + 1 < count { format!("{id}.arm{}", i + 1) } else { none.clone() };
+            writeln!(instructions, "{id}.arm{i}:").unwrap();
+            ir.join();
+            test_pattern(ir, instructions, arm.pattern, &typ, &ptr, &next, &id);
+            if let Some(guard) = arm.guard {
+                let guard = ir.type_context(Some(Type::Bool), |ir| translate(ir, instructions, guard)).unwrap();
+                writeln!(instructions, "br i1 {guard}, label %{id}.body{i}, label %{next}").unwrap();
+                writeln!(instructions, "{id}.body{i}:").unwrap();
+            }
+            all_terminate &= arm_body(ir, instructions, arm.body, &end, result);
+            ir.leave();
+        }
+    }
+    // match is exhaustive, so nothing gets there
+    writeln!(instructions, "{none}:\nunreachable").unwrap();
+    writeln!(instructions, "{end}:").unwrap();
+    if all_terminate { writeln!(instructions, "unreachable").unwrap(); }
+}
+
+/// Returns whether body terminates by itself
+fn arm_body<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, body: Vec<Value<'a>>, end: &str, result: Option<&(String, Type)>) -> bool {
+    let terminates = find_terminator(&body);
+    translate_body(ir, instructions, body, result);
+    if !terminates {
+        run_deferred(ir, instructions, ir.depth());
+        writeln!(instructions, "br label %{end}").unwrap();
+    }
+    terminates
+}
+
 pub fn unreachable (instructions: &mut impl Write) {
     writeln!(instructions, "unreachable").unwrap();
 }
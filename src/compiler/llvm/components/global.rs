@@ -1,8 +1,8 @@
 use std::{fmt::Write, mem};
 
-use crate::{compiler::llvm::{codegen::{translate, translate_all}, ir::IR}, parser::{components::args::Arg, r#type::{extract_type, Type}, Value}};
+use crate::{compiler::llvm::{codegen::{string_slice, translate, translate_all}, ir::IR}, parser::{components::args::Arg, r#type::{extract_type, Type}, Value}};
 
-use super::{memory::new_arg, r#type::translate_type};
+use super::{control_flow::run_deferred, memory::new_arg, r#type::{float_constant, translate_type}};
 
 fn translate_args (ir: &mut IR, args: &Vec<Arg>) -> Vec<String> {
     args.iter().map(|x| {
@@ -33,10 +33,14 @@ pub fn define<'a, 'b> (ir: &mut IR<'a>, name: String, mut args: Vec<Arg<'a>>, bo
 // pub fn call<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: &'a str, args: Vec<(Type, Value<'a>)>, ret: Type) -> Option<String> {
 pub fn call<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, x: Value<'a>, argv: Vec<Value<'a>>, tail: bool) -> Option<String> {
     let Type::Fn(argt, ret) = extract_type(&x).unwrap() else { unreachable!() };
+    // `tail` promises that callee doesn't access allocas of caller, so pointers may not be passed
+    let tail = tail && !argt.iter().any(|x| matches!(x, Type::Ptr(_, _) | Type::Dyn(_, _) | Type::Slice(_, _)));
     let name = translate(ir, instructions, x).unwrap();
     let typstr = translate_type(&ret);
     let mut args = (argt.into_iter(), argv.into_iter());
     let mut args_llvm = vec![];
+    // arguments are passed by value, they can't be put in destination of call result
+    ir.unmark_put_in();
     while let (Some(typ), Some(value)) = (args.0.next(), args.1.next()) {
         let typstr = translate_type(&typ);
         let value = ir.type_context(Some(typ), |ir| translate(ir, instructions, value)).unwrap();
@@ -64,8 +68,65 @@ pub fn ret<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Option<Va
         });
         if let Some(_) = value { typstr += " " }
         write!(instructions, "{retbody}").unwrap();
+        // returned value is computed before deferred code runs
+        run_deferred(ir, instructions, 0);
         writeln!(instructions, "ret {typstr}{}", value.unwrap_or_default()).unwrap();
         return
     }
+    run_deferred(ir, instructions, 0);
     writeln!(instructions, "ret {}", translate_type(&typ)).unwrap();
+}
+
+/// `&dyn Trait` is pair of data pointer and vtable, which is constant array of methods in order of their declaration in trait
+pub fn dyn_cast<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, vtable: Vec<String>) -> String {
+    ir.unmark_put_in();
+    let typ = extract_type(&value);
+    let data = ir.type_context(typ, |ir| translate(ir, instructions, value)).unwrap();
+    let methods = vtable.iter().map(|x| format!("ptr @\"{x}\"")).collect::<Vec<_>>().join(", ");
+    let vtable = ir.constant(format!("[{} x ptr] [{methods}]", vtable.len()));
+    let a = ir.temp();
+    writeln!(instructions, "%{a} = insertvalue {{ ptr, ptr }} undef, ptr {data}, 0").unwrap();
+    let b = ir.temp();
+    writeln!(instructions, "%{b} = insertvalue {{ ptr, ptr }} %{a}, ptr {vtable}, 1").unwrap();
+    format!("%{b}")
+}
+
+pub fn vtable_method<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, index: usize) -> String {
+    let d = ir.type_context(extract_type(&value), |ir| translate(ir, instructions, value)).unwrap();
+    let vtable = ir.temp();
+    writeln!(instructions, "%{vtable} = extractvalue {{ ptr, ptr }} {d}, 1").unwrap();
+    let p = ir.temp();
+    writeln!(instructions, "%{p} = getelementptr inbounds ptr, ptr %{vtable}, i64 {index}").unwrap();
+    let f = ir.temp();
+    writeln!(instructions, "%{f} = load ptr, ptr %{p}").unwrap();
+    format!("%{f}")
+}
+
+pub fn dyn_data<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>) -> String {
+    let d = ir.type_context(extract_type(&value), |ir| translate(ir, instructions, value)).unwrap();
+    let data = ir.temp();
+    writeln!(instructions, "%{data} = extractvalue {{ ptr, ptr }} {d}, 0").unwrap();
+    format!("%{data}")
+}
+
+/// Constant as it's written in initializer of global
+fn initializer (ir: &mut IR, x: &Value, typ: &Type) -> String {
+    let mut items = |x: &[Value], t: &mut dyn Iterator<Item = &Type>| x.iter().zip(t).map(|(x, t)| format!("{} {}", translate_type(t), initializer(ir, x, t))).collect::<Vec<_>>().join(", ");
+    match (x, typ) {
+        (Value::Typecast(x, _, _), _) => initializer(ir, x, typ),
+        (Value::Int(x), _) => x.to_string(),
+        (Value::SInt(x), _) => x.to_string(),
+        (Value::Bool(x), _) => x.to_string(),
+        (Value::Float(x), _) => float_constant(*x, Some(typ)),
+        (Value::String(x), _) => string_slice(ir, x),
+        (Value::Array(x), Type::Array(t, _)) => format!("[{}]", items(x, &mut std::iter::repeat(&**t))),
+        (Value::Tuple(x), Type::Tuple(t)) => format!("{{{}}}", items(x, &mut t.iter())),
+        _ => unreachable!()
+    }
+}
+
+pub fn r#static<'a> (ir: &mut IR<'a>, name: String, typ: Type, value: Value<'a>, mutable: bool) {
+    let kind = if mutable { "global" } else { "constant" };
+    let init = initializer(ir, &value, &typ);
+    ir.global_write(&format!("@\"{name}\" = {kind} {} {init}", translate_type(&typ)));
 }
\ No newline at end of file
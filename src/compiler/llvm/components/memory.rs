@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use crate::{compiler::llvm::{codegen::translate, ir::{IR, ppbind}}, parser::{Value, r#type::{Type, default_type, penetrate_type}}};
+use crate::{compiler::llvm::{codegen::translate, ir::{IR, ppbind}}, parser::{Value, r#type::{Type, default_type, extract_type, penetrate_type}}};
 
 use super::r#type::translate_type;
 
@@ -28,7 +28,6 @@ pub fn new_var<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: &'a st
     let typstr = translate_type(&typ);
     // let new_t = ir.reserve_local(&typ);
     let new_t = ir.seek_local(&typ).0;
-    dbg!(new_t, &typstr);
     if let Some(value) = value {
         // ir.bind(MARK_PUT_IN, new_t);
         ir.mark_put_in(new_t.0);
@@ -63,7 +62,6 @@ pub fn get_var<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: &'a st
     let typstr = translate_type(&typ);
     let c = ir.temp();
     let from = ir.get_bind(name).unwrap();
-    dbg!(name, from);
     write!(instructions, "%{c} = ").unwrap();
     load!(instructions, typstr, ppbind(from));
     format!("%{c}")
@@ -74,9 +72,11 @@ pub fn modify_var<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: &'a
     let p = *ir.get_bind(name).unwrap();
     // ir.bind(MARK_PUT_IN, p);
     ir.mark_put_in(p.0);
-    let value = ir.type_context(Some(typ), |ir| translate(ir, instructions, value)).unwrap();
+    let value = ir.type_context(Some(typ), |ir| translate(ir, instructions, value));
     // ir.remove_bind(MARK_PUT_IN);
     ir.unmark_put_in();
+    // values like `if` put themselves in place
+    let Some(value) = value else { return };
     // writeln!(instructions, "store {typstr} {value}, ptr %{p}").unwrap();
     writeln!(instructions, "store {typstr} {value}, ptr %{}", ppbind(&p)).unwrap();
 }
@@ -116,7 +116,7 @@ pub fn array<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, arr: Vec<Value
     for i in arr {
         let gep = ir.temp();
         writeln!(instructions, "%{gep} = getelementptr inbounds {typstr}, ptr %{current}, i64 {index}").unwrap();
-        ir.mark_put_in(gep);
+        ir.mark_put_in_temp(gep);
 
         if let Some(item) = ir.ref_type_context(&mut subtyp, |ir| translate(ir, instructions, i)) {
             writeln!(instructions, "store {subtypstr} {item}, ptr %{gep}").unwrap();
@@ -138,10 +138,16 @@ pub fn load_address<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, from: V
     let bits = match &typ {
         Type::Tuple(_) | Type::Struct(_) => 32,
         // Type::Struct(_) => todo!(),
-        _ => 64
+        _ => ir.ptr_bits()
     };
     let f = match &typ { Type::Ptr(_, _) => "", _ => " i64 0," };
-    let from = translate(ir, instructions, if Type::Ptr(None, false).check(&from).is_some() { from } else { Value::Ptr(Box::new(from), false) }).unwrap(); //  || Type::PtrMut(None).check(&from).is_some()
+    let from = match from {
+        // address of dereferenced pointer is the pointer itself
+        Value::LoadFromPtr(x, _) => *x,
+        from if Type::Ptr(None, false).check(&from).is_some() => from,
+        from => Value::Ptr(Box::new(from), false)
+    };
+    let from = translate(ir, instructions, from).unwrap(); //  || Type::PtrMut(None).check(&from).is_some()
     let typstr = translate_type(&typ);
     
     let index = translate(ir, instructions, index).unwrap();
@@ -177,7 +183,7 @@ pub fn tuple<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, tuple: Vec<Val
     let Type::Tuple(mut subtyp) = typ else { panic!() };
     for (index, value) in tuple.into_iter().enumerate() {
         let gep = ir.temp();
-        ir.mark_put_in(gep);
+        ir.mark_put_in_temp(gep);
         writeln!(instructions, "%{gep} = getelementptr inbounds {typstr}, ptr %{init}, i64 0, i32 {index}").unwrap();
         if let Some(x) = ir.ref_type_context(&mut subtyp[index], |ir| translate(ir, instructions, value)) {
             writeln!(instructions, "store {} {x}, ptr %{gep}", translate_type(&subtyp[index])).unwrap();
@@ -190,4 +196,26 @@ pub fn tuple<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, tuple: Vec<Val
         return Some(format!("%{load}"))
     }
     None
+}
+
+/// `&[T]` is pair of pointer to the first element and number of elements
+pub fn slice<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, ptr: Value<'a>, len: Value<'a>) -> String {
+    ir.unmark_put_in();
+    let data = ir.type_context(extract_type(&ptr), |ir| translate(ir, instructions, ptr)).unwrap();
+    let usize = Type::U(ir.ptr_bits() as u32);
+    let lenstr = translate_type(&usize);
+    let len = ir.type_context(Some(usize), |ir| translate(ir, instructions, len)).unwrap();
+    let a = ir.temp();
+    writeln!(instructions, "%{a} = insertvalue %.slice undef, ptr {data}, 0").unwrap();
+    let b = ir.temp();
+    writeln!(instructions, "%{b} = insertvalue %.slice %{a}, {lenstr} {len}, 1").unwrap();
+    format!("%{b}")
+}
+
+/// Pointer to elements (0) or length (1) of slice
+pub fn slice_field<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, index: usize) -> String {
+    let d = ir.type_context(extract_type(&value), |ir| translate(ir, instructions, value)).unwrap();
+    let c = ir.temp();
+    writeln!(instructions, "%{c} = extractvalue %.slice {d}, {index}").unwrap();
+    format!("%{c}")
 }
\ No newline at end of file
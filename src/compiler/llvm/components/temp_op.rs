@@ -11,23 +11,30 @@ fn is_unsigned (t: &Type) -> bool {
     }
 }
 
+fn is_float (t: &Type) -> bool {
+    matches!(t, Type::F(_))
+}
+
 fn cmp<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, lhs: Value<'a>, rhs: Value<'a>, op: Operation) -> String {
     let t = extract_types(&[&lhs, &rhs]).unwrap();
     let typ = translate_type(&t);
+    // operands' type, not the one of comparison result
+    let (float, for_unsigned) = (is_float(&t), is_unsigned(&t));
+    let prefix = if float { "f" } else { "i" };
 
     let (lhs, rhs) = ir.type_context(Some(t), |ir| (translate(ir, instructions, lhs).unwrap(), translate(ir, instructions, rhs).unwrap()));
 
-    let prefix = "i"; // todo
-    let for_unsigned = is_unsigned(ir.get_current_type().unwrap());
-
     let c = ir.temp();
     write!(instructions, "%{c} = {prefix}cmp ").unwrap();
 
+    // ordered comparisons for floats: any of them with NaN is false (except '!=')
     let op = match op {
+        Operation::Eq if float => "oeq",
+        Operation::NE if float => "une",
         Operation::Eq => "eq",
         Operation::NE => "ne",
         op => {
-            write!(instructions, "{}", if for_unsigned { "u" } else { "s" }).unwrap();
+            write!(instructions, "{}", if float { "o" } else if for_unsigned { "u" } else { "s" }).unwrap();
             match op {
                 Operation::GT => "gt",
                 Operation::GE => "ge",
@@ -51,7 +58,8 @@ fn branch_op<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, lhs: Value<'a>
     match op {
         Operation::And => writeln!(instructions, "br i1 {lhs}, label %{next}, label %end.{next}").unwrap(),
         Operation::Or => writeln!(instructions, "br i1 {lhs}, label %end.{next}, label %{next}").unwrap(),
-        _ => todo!()
+        // other operations are never lazy, see `is_branch`
+        _ => unreachable!()
     }
     let rhs = ir.type_context(Some(Type::Bool), |ir| translate(ir, instructions, rhs).unwrap());
     writeln!(instructions, "store i1 {rhs}, ptr %{l}").unwrap();
@@ -76,11 +84,20 @@ pub fn expr<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, lhs: Value<'a>,
     let lhs = translate(ir, instructions, lhs).unwrap();
     let rhs = translate(ir, instructions, rhs).unwrap();
     
-    // TODO: also f flag for float support
-
     let for_unsigned = is_unsigned(ir.get_current_type().unwrap());
     
     let c = ir.temp();
+    if is_float(ir.get_current_type().unwrap()) {
+        writeln!(instructions, "%{c} = {} {optype} {lhs}, {rhs}", match op {
+            Operation::Add => "fadd",
+            Operation::Sub => "fsub",
+            Operation::Mul => "fmul",
+            Operation::Div => "fdiv",
+            Operation::Rem => "frem",
+            _ => unreachable!()
+        }).unwrap();
+        return format!("%{c}")
+    }
     // op
     writeln!(instructions, "%{c} = {} {optype} {lhs}, {rhs}", match op {
         Operation::Add => "add",
@@ -95,8 +112,15 @@ pub fn expr<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, lhs: Value<'a>,
             else { "srem" }
         },
         Operation::BitAnd => "and",
-        Operation::And => todo!(),
-        _ => { dbg!(op); todo!() }
+        Operation::BitOr => "or",
+        Operation::Xor => "xor",
+        Operation::Shl => "shl",
+        // arithmetic shift keeps sign of signed integers
+        Operation::Shr => {
+            if for_unsigned { "lshr" }
+            else { "ashr" }
+        },
+        _ => unreachable!()
     }).unwrap();
 
     return format!("%{c}")
@@ -108,13 +132,17 @@ pub fn unary<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, op: Operation,
     let c = ir.temp();
     write!(instructions, "%{c} = ").unwrap();
     match op {
-        Operation::Not => {
+        Operation::Not | Operation::BitNot => {
             writeln!(instructions, "xor {typstr} {value}, -1").unwrap();
         }
+        Operation::Sub if is_float(ir.get_current_type().unwrap()) => {
+            writeln!(instructions, "fneg {typstr} {value}").unwrap();
+        }
         Operation::Sub => {
             writeln!(instructions, "sub {typstr} 0, {value}").unwrap();
         }
-        _ => todo!()
+        // parser makes unary values of '-', '!' and '~' only
+        _ => unreachable!()
     }
     format!("%{c}")
 }
\ No newline at end of file
@@ -8,22 +8,35 @@ pub fn translate_type (t: &Type) -> String {
     match t {
         Type::Bool => "i1",
         Type::I(x) | Type::U(x) => return format!("i{x}"),
+        Type::F(32) => "float",
+        Type::F(_) => "double",
         Type::Array(t, s) => return format!("[{s} x {}]", translate_type(t)),
+        // opaque types have no definition
+        Type::Ptr(Some(t), _) if matches!(**t, Type::Opaque(_)) => "ptr",
         Type::Ptr(Some(t), _) => return format!("{}*", translate_type(t)),
         Type::Ptr(None, _) | Type::Fn(_, _) => "ptr",
         Type::Void | Type::Noret => "void",
+        Type::Dyn(_, _) => "{ ptr, ptr }",
+        // defined by target, length is 'usize'
+        Type::Slice(_, _) => "%.slice",
         Type::Tuple(v) => return format!("{{{}}}", v.iter().map(|x| translate_type(&x)).collect::<Vec<_>>().join(", ")),
         // Type::Struct(_) => todo!(),
         Type::Struct(name) => return format!("%\"{name}\""),
-        Type::Guess => unreachable!(),
+        Type::Guess | Type::Param(_) | Type::Opaque(_) => unreachable!(),
         // _ => { dbg!(t); todo!() }
     }.into()
 }
 
+/// LLVM takes exact floating point constants as hex of double, even for `float` (which must be representable in it)
+pub fn float_constant (x: f64, typ: Option<&Type>) -> String {
+    let x = if let Some(Type::F(32)) = typ { x as f32 as f64 } else { x };
+    format!("0x{:016X}", x.to_bits())
+}
+
 pub fn typecast<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Value<'a>, mut from: Type, mut to: Type) -> String {
     // todo smth with that if
     // if let (Value::Int(_) | Value::SInt(_), Type::I(_) | Type::U(_)) = (&value, &to) { return translate(ir, instructions, value).unwrap() }
-    let value = translate(ir, instructions, value).unwrap();
+    let value = ir.type_context(Some(from.clone()), |ir| translate(ir, instructions, value).unwrap());
     if from == to { return value }
     if let Type::Bool = from { from = Type::I(1) }
     if let Type::Bool = to { to = Type::I(1) }
@@ -47,7 +60,22 @@ pub fn typecast<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, value: Valu
         }
     }
 
+    macro_rules! typecast_float {
+        ($op:expr) => {
+            let tpt = ir.temp();
+            writeln!(instructions, "%{tpt} = {} {} {value} to {}", $op, translate_type(&from), translate_type(&to)).unwrap();
+            return format!("%{tpt}")
+        };
+    }
+
     match (&from, &to) {
+        (Type::F(x), Type::F(y)) => {
+            typecast_float!(if x < y { "fpext" } else { "fptrunc" });
+        }
+        (Type::I(_), Type::F(_)) => { typecast_float!("sitofp"); }
+        (Type::U(_), Type::F(_)) => { typecast_float!("uitofp"); }
+        (Type::F(_), Type::I(_)) => { typecast_float!("fptosi"); }
+        (Type::F(_), Type::U(_)) => { typecast_float!("fptoui"); }
         (Type::I(x), Type::I(y) | Type::U(y)) => {
             typecast_integer!(x, y, 's');
         }
@@ -76,7 +104,7 @@ pub fn struct_init<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: St
     for (k, t) in kt {
         let Some(v) = kv.remove(k.as_ref()) else { todo!() };
         let gep = ir.temp();
-        ir.mark_put_in(gep);
+        ir.mark_put_in_temp(gep);
         writeln!(instructions, "%{gep} = getelementptr inbounds {typstr}, ptr %{init}, i64 0, i32 {index}").unwrap();
         if let Some(l) = ir.ref_type_context(&mut typ, |ir| translate(ir, instructions, v)) {
             writeln!(instructions, "store {} {l}, ptr %{gep}", translate_type(&t)).unwrap();
@@ -92,4 +120,43 @@ pub fn struct_init<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: St
     None
 }
 
+/// Enum is stored as tag followed by storage for biggest payload, aligned as strictest of them
+pub fn r#enum<'a> (ir: &mut IR<'a>, name: String, tag: Type, (align, size): (u32, usize)) {
+    let tag = translate_type(&tag);
+    if size == 0 { ir.global_write(&format!("%\"{name}\" = type {{ {tag} }}")); }
+    else {
+        let count = size.div_ceil(align as usize);
+        ir.global_write(&format!("%\"{name}\" = type {{ {tag}, [{count} x i{}] }}", align * 8));
+    }
+}
+
+pub fn enum_init<'a> (ir: &mut IR<'a>, instructions: &mut impl Write, name: String, (index, tag): (usize, Type), payload: Vec<(Value<'a>, Type)>) -> Option<String> {
+    let typ = Type::Struct(name);
+    let (init, put_in) = ir.seek_local(&typ);
+    // payload values must not be put into enum itself
+    ir.unmark_put_in();
+    let init = ppbind(&init);
+    let typstr = translate_type(&typ);
+    let gep = ir.temp();
+    writeln!(instructions, "%{gep} = getelementptr inbounds {typstr}, ptr %{init}, i64 0, i32 0").unwrap();
+    writeln!(instructions, "store {} {index}, ptr %{gep}", translate_type(&tag)).unwrap();
+    if !payload.is_empty() {
+        let storage = ir.temp();
+        writeln!(instructions, "%{storage} = getelementptr inbounds {typstr}, ptr %{init}, i64 0, i32 1").unwrap();
+        let layout = format!("{{{}}}", payload.iter().map(|(_, t)| translate_type(t)).collect::<Vec<_>>().join(", "));
+        for (i, (v, t)) in payload.into_iter().enumerate() {
+            let Some(l) = ir.type_context(Some(t.clone()), |ir| translate(ir, instructions, v)) else { continue };
+            let gep = ir.temp();
+            writeln!(instructions, "%{gep} = getelementptr inbounds {layout}, ptr %{storage}, i64 0, i32 {i}").unwrap();
+            writeln!(instructions, "store {} {l}, ptr %{gep}", translate_type(&t)).unwrap();
+        }
+    }
+    if !put_in {
+        let load = ir.temp();
+        writeln!(instructions, "%{load} = load {typstr}, ptr %{init}").unwrap();
+        return Some(format!("%{load}"))
+    }
+    None
+}
+
 // to future self: now do field access I guess
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::{collections::HashMap, fmt::Write, mem};
 
 use insordmap::InsordMap;
 
-use crate::parser::r#type::Type;
+use crate::parser::{r#type::Type, Value};
 
 use super::components::r#type::translate_type;
 
@@ -17,13 +17,20 @@ pub struct IR<'a> {
     anon_counter: usize,
     // binds: Vec<HashMap<&'a str, usize>>,
     binds: Vec<HashMap<&'a str, (usize, bool)>>,
+    /// Code deferred in each scope of current function, in order of appearance
+    defers: Vec<Vec<Vec<Value<'a>>>>,
+    /// Scopes of loop bodies by their labels
+    loops: HashMap<usize, usize>,
+    /// Slots for results of loops used as values, by their labels: (pointer, type)
+    results: HashMap<usize, (String, Type)>,
     types: HashMap<String, InsordMap<String, Type>>,
     current_type: Option<Type>,
     /// (current, max)
     // reserved_locals: (usize, usize),
     reserved_locals: usize,
     prologue: String,
-    temp_counter: usize
+    temp_counter: usize,
+    ptr_bits: u8
 }
 
 impl<'a> IR<'a> {
@@ -36,6 +43,9 @@ impl<'a> IR<'a> {
         self.global
     }
 
+    pub fn set_ptr_bits (&mut self, bits: u8) { self.ptr_bits = bits; }
+    pub fn ptr_bits (&self) -> u8 { self.ptr_bits }
+
     pub fn constant (&mut self, k: String) -> String {
         if let Some(l) = self.constants.0.get(&k) {
             return l.clone()
@@ -50,9 +60,13 @@ impl<'a> IR<'a> {
         }
     }
 
-    pub fn join (&mut self) { self.binds.push(HashMap::new()); }
+    pub fn join (&mut self) {
+        self.binds.push(HashMap::new());
+        self.defers.push(vec![]);
+    }
     pub fn leave (&mut self) {
         self.binds.pop();
+        self.defers.pop();
         if self.binds.is_empty() {
             self.temp_counter = 0;
             // self.reserved_locals = (0, 0);
@@ -63,7 +77,9 @@ impl<'a> IR<'a> {
         let reserved_locals = mem::take(&mut self.reserved_locals);
         let temp_counter = mem::take(&mut self.temp_counter);
         let prologue = mem::take(&mut self.prologue);
+        let defers = mem::take(&mut self.defers);
         f(self);
+        self.defers = defers;
         self.temp_counter = temp_counter;
         self.reserved_locals = reserved_locals;
         self.prologue = prologue;
@@ -152,12 +168,37 @@ impl<'a> IR<'a> {
     pub fn mark_put_in (&mut self, l: usize) {
         self.bind(MARK_PUT_IN, l, true);
     }
+    /// Same as `mark_put_in`, but for pointer in temporary (e.g. result of `getelementptr`) rather than in local
+    pub fn mark_put_in_temp (&mut self, l: usize) {
+        self.bind(MARK_PUT_IN, l, false);
+    }
     pub fn unmark_put_in (&mut self) {
         self.remove_bind(MARK_PUT_IN);
     }
-    pub fn get_mark_put_in (&mut self) -> Option<&(usize, bool)> {
-        self.get_bind(MARK_PUT_IN)
+
+    pub fn defer (&mut self, body: Vec<Value<'a>>) { self.defers.last_mut().unwrap().push(body); }
+    /// Index of current scope in function
+    pub fn depth (&self) -> usize { self.defers.len() - 1 }
+    /// Code deferred in scopes from innermost one to `depth` along with depth of its scope, in order it runs when they're left
+    pub fn deferred (&self, depth: usize) -> Vec<(usize, Vec<Value<'a>>)> {
+        self.defers.iter().enumerate().skip(depth).rev().flat_map(|(i, x)| x.iter().rev().map(move |x| (i, x.clone()))).collect()
+    }
+    /// Runs `f` as if scopes after `depth` were already left, so names refer to what they did there
+    pub fn at_depth<T> (&mut self, depth: usize, f: impl FnOnce(&mut Self) -> T) -> T {
+        // functions inside of functions share binds, but not deferred code
+        let cut = self.binds.len() - self.defers.len() + depth + 1;
+        let binds = self.binds.split_off(cut);
+        let defers = self.defers.split_off(depth + 1);
+        let res = f(self);
+        self.binds.extend(binds);
+        self.defers.extend(defers);
+        res
     }
+    /// Marks current scope as body of loop with `label`
+    pub fn enter_loop (&mut self, label: usize) { self.loops.insert(label, self.depth()); }
+    pub fn loop_depth (&self, label: usize) -> usize { self.loops[&label] }
+    pub fn set_loop_result (&mut self, label: usize, result: (String, Type)) { self.results.insert(label, result); }
+    pub fn loop_result (&self, label: usize) -> Option<&(String, Type)> { self.results.get(&label) }
 
     pub fn bind_type (&mut self, name: String, kv: InsordMap<String, Type>) {
         self.types.insert(name, kv);
@@ -1,72 +1,140 @@
 mod components;
 mod ir;
 pub mod codegen;
-use std::{fs::{create_dir_all, File}, io::Write, path::PathBuf, process::Command};
+use std::{collections::HashSet, env, fs::{create_dir_all, File}, io::Write, path::{Path, PathBuf}, process::{self, Command}, sync::OnceLock};
 
 use codegen::emit_llvm;
 
-use crate::parser::{bindings::Bindings, Value};
+use crate::{compiler::{Target, OS}, parser::{bindings::Bindings, Value}};
 
-pub fn compile (program: Vec<Value>, bindings: Bindings, opt: u8) {
-    let result = emit_llvm(program);
-    print!("--- llvm output ---\n{result}");
-    let mut f = File::create("./temp.ll").expect("Cannot access file system");
-    f.write_all(&result.as_bytes()).expect("Failed writing to file");
+/// What `llc` should produce from IR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType { Asm, Obj }
 
-    // .ll to .o
+/// LLVM before 15 parses typed pointers by default, while everything we emit uses opaque `ptr`
+fn opaque_pointers_flag () -> Option<&'static str> {
+    static LEGACY: OnceLock<bool> = OnceLock::new();
+    let legacy = *LEGACY.get_or_init(|| {
+        let Ok(out) = Command::new("llc").arg("--version").output() else { return false };
+        let out = String::from_utf8_lossy(&out.stdout);
+        out.split("LLVM version ").nth(1)
+        .and_then(|x| x.split('.').next())
+        .and_then(|x| x.trim().parse::<u32>().ok())
+        .is_some_and(|x| x < 15)
+    });
+    if legacy { Some("-opaque-pointers") } else { None }
+}
+
+pub fn write_file (path: &Path, content: &str) -> Result<(), String> {
+    let mut f = File::create(path).map_err(|e| format!("Cannot create '{}': {e}", path.display()))?;
+    f.write_all(content.as_bytes()).map_err(|e| format!("Failed writing to '{}': {e}", path.display()))
+}
+
+pub fn llc (input: &Path, output: &Path, filetype: FileType, opt: u8, target: &Target) -> Result<(), String> {
     let x = Command::new("llc")
-    .arg("--filetype=obj")
-    .arg("temp.ll")
+    .arg(match filetype { FileType::Asm => "--filetype=asm", FileType::Obj => "--filetype=obj" })
+    .arg(input)
+    .arg("-o")
+    .arg(output)
+    .arg(format!("-O{opt}"))
+    .arg(format!("-mtriple={}", target.triple()))
+    // executables are linked as PIE, and constant tables of function pointers (vtables) need relocations
+    .arg("--relocation-model=pic")
+    .args(opaque_pointers_flag())
+    .status().map_err(|_| String::from("Failed to execute 'llc' command. Add LLVM binaries in PATH."))?;
+
+    if !x.success() { return Err(format!("llc failed to compile '{}'", input.display())) }
+    Ok(())
+}
+
+/// Runs optimization passes of level `opt` over IR, as `llc` optimizes only code it generates out of IR
+pub fn optimize (input: &Path, output: &Path, opt: u8, target: &Target) -> Result<(), String> {
+    let x = Command::new("opt")
+    .arg("-S")
+    .arg(input)
     .arg("-o")
-    .arg("temp.o")
+    .arg(output)
     .arg(format!("-O{opt}"))
-    .status().expect("Failed to execute 'llc' command. Add LLVM binaries in PATH.");
+    .arg(format!("-mtriple={}", target.triple()))
+    .args(opaque_pointers_flag())
+    .status().map_err(|_| String::from("Failed to execute 'opt' command. Add LLVM binaries in PATH."))?;
+
+    if !x.success() { return Err(format!("opt failed to optimize '{}'", input.display())) }
+    Ok(())
+}
 
-    if !x.success() { panic!("llc failed to compile IR to object file") }
+fn on_path (program: &str) -> bool {
+    Command::new(program).arg("--version").output().is_ok()
+}
+
+/// Translates `#link` entry into `cc` arguments: bare names become `-l`, paths also get `-L` for their directory
+pub(crate) fn unix_link_args (lib: &str) -> Vec<String> {
+    let path = Path::new(lib);
+    let file = path.file_name().and_then(|x| x.to_str()).unwrap_or(lib);
+    let mut args = vec![];
+    if let Some(dir) = path.parent().filter(|x| !x.as_os_str().is_empty()) {
+        args.push(format!("-L{}", dir.display()));
+    }
+    let stem = file.strip_suffix(".so").or_else(|| file.strip_suffix(".a"));
+    match stem.map(|x| x.strip_prefix("lib")) {
+        // `-l` finds `lib{name}.so` and `lib{name}.a` by itself
+        Some(Some(name)) => args.push(format!("-l{name}")),
+        // unusual names, like `foo.so` or versioned `libfoo.so.1`, are given exactly
+        Some(None) => args.push(format!("-l:{file}")),
+        None if file.contains(".so.") => args.push(format!("-l:{file}")),
+        None => args.push(format!("-l{}", file.strip_prefix("lib").unwrap_or(file)))
+    }
+    args
+}
 
-    let y = Command::new("lld-link")
-    .arg("temp.o")
-    .args(bindings.move_links().iter())
-    .arg("/entry:main")
-    .status().expect("Failed to execute 'lld-link' command");
+pub fn link (objects: &[PathBuf], links: &HashSet<String>, output: &Path, target: &Target) -> Result<(), String> {
+    let y = match target.os {
+        // crt startup objects provide `_start` and call `main`, so let C compiler driver do the job
+        OS::Linux => {
+            // foreign architectures need a driver that knows about them
+            let mut cmd = if target.is_host() { Command::new("cc") } else { Command::new("clang") };
+            if !target.is_host() { cmd.arg(format!("--target={}", target.triple())); }
+            if on_path("ld.lld") { cmd.arg("-fuse-ld=lld"); }
+            cmd.args(objects)
+            .arg("-o")
+            .arg(output)
+            .args(links.iter().flat_map(|x| unix_link_args(x)))
+            .status().map_err(|_| String::from("Failed to execute C compiler driver ('cc', or 'clang' for foreign targets). Install C toolchain to link executables."))?
+        }
+        OS::Windows => {
+            Command::new("lld-link")
+            .args(objects)
+            .args(links)
+            .arg("/entry:main")
+            .arg(format!("/out:{}", output.display()))
+            .status().map_err(|_| String::from("Failed to execute 'lld-link' command"))?
+        }
+    };
 
-    if !y.success() { panic!("Failed linking stage") }
+    if !y.success() { return Err(String::from("Failed linking stage")) }
+    Ok(())
 }
 
 #[allow(dead_code)]
 pub fn llc_test (program: Vec<Value>, bindings: Bindings, opt: u8) {
-    let result = emit_llvm(program);
+    let result = emit_llvm(program, &Target::default());
     let path = format!("./tests/{}.ll", bindings.get_current_file_path().to_str().unwrap());
     create_dir_all(PathBuf::from(&path).parent().unwrap()).unwrap();
-    let mut f = File::create(&path).expect("Cannot access file system");
-    f.write_all(&result.as_bytes()).expect("Failed writing to file");
+    write_file(path.as_ref(), &result).unwrap_or_else(|e| panic!("{e}"));
 
     // .ll to .o
-    let x = Command::new("llc")
-    .arg("--filetype=obj")
-    .arg(&path)
-    .arg("-o")
-    .arg(format!("./tests/{}.o", bindings.get_current_file_path().to_str().unwrap()))
-    .arg(format!("-O{opt}"))
-    .status().expect("Failed to execute 'llc' command. Add LLVM binaries in PATH.");
-
-    if !x.success() { panic!("llc failed to compile IR to object file") }
+    let obj = format!("./tests/{}.o", bindings.get_current_file_path().to_str().unwrap());
+    llc(path.as_ref(), obj.as_ref(), FileType::Obj, opt, &Target::default()).unwrap_or_else(|e| panic!("{e}"));
 }
 
-// #[allow(dead_code)]
-pub fn emit_asm (program: Vec<Value>) {
-    let result = emit_llvm(program);
-    let mut f = File::create("./temp.ll").expect("Cannot access file system");
-    f.write_all(&result.as_bytes()).expect("Failed writing to file");
-
-    // .ll to .s
-    let x = Command::new("llc")
-    .arg("--filetype=asm")
-    .arg("temp.ll")
-    .arg("-o")
-    .arg("temp.s")
-    .arg("-O0")
-    .status().expect("Cannot execute 'llc' command. Add LLVM binaries in PATH.");
-
-    if !x.success() { panic!("llc failed to compile IR to object file") }
+/// Compiles program into executable in temporary directory, so it stays out of tracked test artifacts, and returns path to it
+#[allow(dead_code)]
+pub fn link_test (program: Vec<Value>, bindings: Bindings, opt: u8) -> PathBuf {
+    let file = bindings.get_current_file_path().to_str().unwrap().to_string();
+    let links = bindings.get_links().iter().map(|x| x.to_string()).collect();
+    llc_test(program, bindings, opt);
+    let exe = env::temp_dir().join(format!("nalt-tests-{}", process::id())).join(format!("{file}.out"));
+    create_dir_all(exe.parent().unwrap()).unwrap();
+    link(&[format!("./tests/{file}.o").into()], &links, &exe, &Target::default()).unwrap_or_else(|e| panic!("{e}"));
+    exe
 }
\ No newline at end of file
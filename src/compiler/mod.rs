@@ -1,15 +1,15 @@
 pub mod llvm;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OS {
     Windows, Linux
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Arch {
     X86_64, X86, Arm, Aarch64
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Target {
     pub os: OS,
     pub cpu: Arch,
@@ -28,4 +28,56 @@ impl Default for Target {
         
         Self { os, cpu, ptr_bits }
     }
+}
+
+impl Target {
+    /// Parses target in shape of `arch-os` (e.g. `aarch64-linux`) or full LLVM triple (e.g. `x86_64-pc-windows-msvc`)
+    pub fn parse (s: &str) -> Option<Self> {
+        let mut parts = s.split('-');
+        let cpu = match parts.next()? {
+            "x86_64" | "amd64" => Arch::X86_64,
+            "x86" | "i386" | "i586" | "i686" => Arch::X86,
+            "arm" | "armv7" | "armv7a" | "thumbv7a" => Arch::Arm,
+            "aarch64" | "arm64" => Arch::Aarch64,
+            _ => return None
+        };
+        let os = parts.find_map(|x| match x {
+            "linux" => Some(OS::Linux),
+            "windows" | "win32" => Some(OS::Windows),
+            _ => None
+        })?;
+        let ptr_bits = match cpu { Arch::X86_64 | Arch::Aarch64 => 64, Arch::X86 | Arch::Arm => 32 };
+        Some(Self { os, cpu, ptr_bits })
+    }
+
+    pub fn triple (&self) -> &'static str {
+        match (&self.cpu, &self.os) {
+            (Arch::X86_64, OS::Linux) => "x86_64-pc-linux-gnu",
+            (Arch::X86, OS::Linux) => "i686-pc-linux-gnu",
+            (Arch::Arm, OS::Linux) => "armv7-unknown-linux-gnueabihf",
+            (Arch::Aarch64, OS::Linux) => "aarch64-unknown-linux-gnu",
+            (Arch::X86_64, OS::Windows) => "x86_64-pc-windows-msvc",
+            (Arch::X86, OS::Windows) => "i686-pc-windows-msvc",
+            (Arch::Arm, OS::Windows) => "thumbv7a-pc-windows-msvc",
+            (Arch::Aarch64, OS::Windows) => "aarch64-pc-windows-msvc",
+        }
+    }
+
+    pub fn datalayout (&self) -> &'static str {
+        match (&self.cpu, &self.os) {
+            (Arch::X86_64, OS::Linux) => "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128",
+            (Arch::X86, OS::Linux) => "e-m:e-p:32:32-p270:32:32-p271:32:32-p272:64:64-f64:32:64-f80:32-n8:16:32-S128",
+            (Arch::Arm, OS::Linux) => "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+            (Arch::Aarch64, OS::Linux) => "e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128",
+            (Arch::X86_64, OS::Windows) => "e-m:w-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128",
+            (Arch::X86, OS::Windows) => "e-m:x-p:32:32-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32-a:0:32-S32",
+            (Arch::Arm, OS::Windows) => "e-m:w-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+            (Arch::Aarch64, OS::Windows) => "e-m:w-p:64:64-i32:32-i64:64-i128:128-n32:64-S128",
+        }
+    }
+
+    pub fn is_host (&self) -> bool {
+        let host = Self::default();
+        self.os == host.os && self.cpu == host.cpu
+    }
 }
\ No newline at end of file
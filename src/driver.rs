@@ -0,0 +1,354 @@
+use std::{collections::HashSet, env::{self, consts::EXE_SUFFIX}, fs, io::{stderr, IsTerminal}, panic::{self, AssertUnwindSafe}, path::{absolute, Path, PathBuf}, process};
+
+use crate::{compiler::{llvm::{codegen::emit_llvm, link, llc, optimize, write_file, FileType}, Target}, lexer::lex, parser::{bindings::{Bindings, Sources}, lint::{Level, LintLevels}, message::{render, Diagnostic, ErrorFormat}, parse_program}};
+
+pub const USAGE: &str = "\
+Usage: nalt [options] <file>...
+
+Options:
+    -o <path>          Write output to <path>
+    --emit=<kind>      One of: llvm-ir, asm, obj, exe (default: exe)
+    -O<n>              Optimization level, 0 to 3 (default: 1)
+    --target <target>  Compile for <target>, e.g. x86_64-linux, aarch64-linux, i686-windows (default: host)
+    --keep-temps       Do not remove intermediate files
+    --error-format=<f> How to print diagnostics: human or json (default: human)
+    --color=<when>     Colorize diagnostics: auto, always or never (default: auto)
+    -W <lint>          Warn about <lint> (default for every lint)
+    -A <lint>          Allow <lint>, i.e. don't report it
+    -D <lint>          Deny <lint>, i.e. report it as error
+                       Lints: unused-variables, unused-functions, unreachable-code,
+                       unused-imports, duplicate-fields; 'warnings' stands for all of them
+    -h, --help         Print this message
+
+Files other than '.alt' are passed to the linker as is.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit { LlvmIr, Asm, Obj, Exe }
+
+impl Emit {
+    fn extension (&self) -> &'static str {
+        match self {
+            Emit::LlvmIr => "ll",
+            Emit::Asm => "s",
+            Emit::Obj => "o",
+            Emit::Exe => EXE_SUFFIX.trim_start_matches('.')
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice { Auto, Always, Never }
+
+impl ColorChoice {
+    /// Diagnostics go to stderr, so 'auto' enables colors only when it's a terminal
+    pub fn enabled (&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => stderr().is_terminal()
+        }
+    }
+}
+
+/// What command line asks for
+#[derive(Debug)]
+pub enum Command { Compile(Options), Help }
+
+#[derive(Debug)]
+pub struct Options {
+    pub inputs: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub emit: Emit,
+    pub optimization: u8,
+    pub keep_temps: bool,
+    pub target: Target,
+    pub json: bool,
+    pub color: ColorChoice,
+    pub lints: LintLevels
+}
+
+impl Options {
+    pub fn parse (mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut s = Self { inputs: vec![], output: None, emit: Emit::Exe, optimization: 1, keep_temps: false, target: Target::default(), json: false, color: ColorChoice::Auto, lints: LintLevels::default() };
+        while let Some(arg) = args.next() {
+            if let Some(x) = arg.strip_prefix("-O") {
+                let Ok(x) = x.parse::<u8>() else { return Err(format!("Failed to parse argument {arg}")) };
+                if x > 3 { return Err(format!("Optimization level of {x} is invalid (maximum is 3)")) }
+                s.optimization = x;
+                continue
+            }
+            if let Some((level, x)) = lint_flag(&arg) {
+                let x = if x.is_empty() { args.next().ok_or_else(|| format!("'{arg}' requires lint name next to it"))? } else { x.into() };
+                s.lints.set(&x, level)?;
+                continue
+            }
+            if let Some(x) = arg.strip_prefix("--emit=") {
+                s.emit = match x {
+                    "llvm-ir" => Emit::LlvmIr,
+                    "asm" => Emit::Asm,
+                    "obj" => Emit::Obj,
+                    "exe" => Emit::Exe,
+                    _ => return Err(format!("Unknown emit kind '{x}' (expected llvm-ir, asm, obj or exe)"))
+                };
+                continue
+            }
+            if let Some(x) = arg.strip_prefix("--error-format=") {
+                s.json = match x {
+                    "human" => false,
+                    "json" => true,
+                    _ => return Err(format!("Unknown error format '{x}' (expected human or json)"))
+                };
+                continue
+            }
+            if let Some(x) = arg.strip_prefix("--color=") {
+                s.color = match x {
+                    "auto" => ColorChoice::Auto,
+                    "always" => ColorChoice::Always,
+                    "never" => ColorChoice::Never,
+                    _ => return Err(format!("Unknown color choice '{x}' (expected auto, always or never)"))
+                };
+                continue
+            }
+            if let Some(x) = arg.strip_prefix("--target=") {
+                s.target = Target::parse(x).ok_or_else(|| format!("Unsupported target '{x}'"))?;
+                continue
+            }
+            match arg.as_str() {
+                "--target" => {
+                    let Some(x) = args.next() else { return Err(String::from("'--target' requires target next to it")) };
+                    s.target = Target::parse(&x).ok_or_else(|| format!("Unsupported target '{x}'"))?;
+                }
+                "-o" => {
+                    let Some(x) = args.next() else { return Err(String::from("'-o' requires path next to it")) };
+                    s.output = Some(x.into());
+                }
+                "--emit-asm" => s.emit = Emit::Asm,
+                "--keep-temps" => s.keep_temps = true,
+                "-h" | "--help" => return Ok(Command::Help),
+                x if x.starts_with('-') && x.len() > 1 => return Err(format!("Unknown argument {x}\n\n{USAGE}")),
+                _ => s.inputs.push(arg.into())
+            }
+        }
+
+        if !s.inputs.iter().any(|x| is_source(x)) { return Err(format!("No input files\n\n{USAGE}")) }
+        if s.emit != Emit::Exe && s.output.is_some() && s.inputs.iter().filter(|x| is_source(x)).count() > 1 {
+            return Err(String::from("Cannot use '-o' with multiple input files unless linking an executable"))
+        }
+        Ok(Command::Compile(s))
+    }
+
+    pub fn error_format (&self) -> ErrorFormat {
+        if self.json { return ErrorFormat::Json }
+        ErrorFormat::Human { color: self.color.enabled() }
+    }
+}
+
+/// Splits `-W`, `-A` or `-D` flag into level and lint name attached to it (may be empty)
+fn lint_flag (arg: &str) -> Option<(Level, &str)> {
+    if let Some(x) = arg.strip_prefix("-W") { return Some((Level::Warn, x)) }
+    if let Some(x) = arg.strip_prefix("-A") { return Some((Level::Allow, x)) }
+    if let Some(x) = arg.strip_prefix("-D") { return Some((Level::Deny, x)) }
+    None
+}
+
+/// Prints error that isn't tied to any source file
+pub fn report (d: &Diagnostic, format: ErrorFormat) {
+    eprint!("{}", render(d, &[], format));
+}
+
+/// Error of driver, diagnostic is boxed as it's large
+pub type DResult<T> = Result<T, Box<Diagnostic>>;
+
+/// Turns message of failed stage into error with given code, so tools can tell stages apart
+fn fail (code: &'static str) -> impl Fn(String) -> Box<Diagnostic> {
+    move |message| Box::new(Diagnostic::error(&message).code(code))
+}
+
+/// Runs stage of compilation, turning its panic into error. Panic is a bug of compiler, not of code being compiled
+fn internal<T> (stage: impl FnOnce() -> T) -> DResult<T> {
+    // panic of stage is reported as diagnostic in chosen format, other panics still print as usual
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(stage));
+    panic::set_hook(hook);
+    result.map_err(|e| {
+        let message = e.downcast_ref::<&str>().map(|x| x.to_string()).or_else(|| e.downcast_ref::<String>().cloned()).unwrap_or_default();
+        Box::new(Diagnostic::error("Internal compiler error").note(&message).help("this is a bug of compiler, please report it").code("internal-error"))
+    })
+}
+
+fn is_source (path: &Path) -> bool {
+    path.extension().is_some_and(|x| x == "alt")
+}
+
+/// Scratch directory for intermediate files, removed on drop unless asked to keep it
+struct Temps { dir: PathBuf, keep: bool }
+
+impl Temps {
+    fn new (keep: bool) -> DResult<Self> {
+        let dir = env::temp_dir().join(format!("nalt-{}", process::id()));
+        fs::create_dir_all(&dir).map_err(|e| fail("io-error")(format!("Cannot create temporary directory '{}': {e}", dir.display())))?;
+        Ok(Self { dir, keep })
+    }
+    /// `index` of source among inputs keeps files with the same name from different directories apart
+    fn file (&self, index: usize, source: &Path, ext: &str) -> PathBuf {
+        self.dir.join(format!("{index}-{}", source.file_stem().unwrap().to_string_lossy())).with_extension(ext)
+    }
+}
+
+impl Drop for Temps {
+    fn drop (&mut self) {
+        if self.keep { eprintln!("Temporary files are kept in '{}'", self.dir.display()); }
+        else { let _ = fs::remove_dir_all(&self.dir); }
+    }
+}
+
+fn output_path (options: &Options, source: &Path) -> PathBuf {
+    if let Some(x) = &options.output { return x.clone() }
+    PathBuf::from(source.file_stem().unwrap()).with_extension(options.emit.extension())
+}
+
+/// Lexes, parses and lowers one source file into LLVM IR, collecting libraries it asks to link with
+fn compile_source (path: &Path, options: &Options, links: &mut HashSet<String>) -> DResult<String> {
+    let code = fs::read(path).map_err(|e| fail("io-error")(format!("Cannot read '{}': {e}", path.display())))?;
+    let code = String::from_utf8(code).map_err(|_| fail("invalid-utf8")(format!("'{}' is not valid UTF-8", path.display())))?;
+    let tokens = lex(&code);
+
+    let sources = Sources::default();
+    let mut bindings = Bindings::new(&code, absolute(path).unwrap_or(path.into()), Some(options.target.clone()), &sources);
+    bindings.set_lints(options.lints.clone());
+    let program = internal(|| parse_program(&tokens, &mut bindings));
+    // diagnostics reported before panic are still of use
+    eprint!("{}", bindings.render_diagnostics(options.error_format()));
+
+    let (Ok(program), true) = (program?, bindings.is_compileable()) else { return Err(fail("compilation-failed")(format!("Could not compile '{}' due to previous errors", path.display()))) };
+    links.extend(bindings.move_links().into_iter().map(String::from));
+    internal(|| emit_llvm(program, &options.target))
+}
+
+pub fn run (options: Options) -> DResult<()> {
+    let temps = Temps::new(options.keep_temps)?;
+    let target = &options.target;
+    let mut objects = vec![];
+    let mut links = HashSet::new();
+
+    for (i, input) in options.inputs.iter().enumerate() {
+        if !is_source(input) {
+            objects.push(input.clone());
+            continue
+        }
+        let ir = compile_source(input, &options, &mut links)?;
+
+        let ll = temps.file(i, input, "ll");
+        write_file(&ll, &ir).map_err(fail("io-error"))?;
+        match options.emit {
+            Emit::LlvmIr => optimize(&ll, &output_path(&options, input), options.optimization, target).map_err(fail("codegen-failed"))?,
+            Emit::Asm => llc(&ll, &output_path(&options, input), FileType::Asm, options.optimization, target).map_err(fail("codegen-failed"))?,
+            Emit::Obj => llc(&ll, &output_path(&options, input), FileType::Obj, options.optimization, target).map_err(fail("codegen-failed"))?,
+            Emit::Exe => {
+                let obj = temps.file(i, input, "o");
+                llc(&ll, &obj, FileType::Obj, options.optimization, target).map_err(fail("codegen-failed"))?;
+                objects.push(obj);
+            }
+        }
+    }
+
+    if options.emit == Emit::Exe {
+        let first = options.inputs.iter().find(|x| is_source(x)).unwrap();
+        link(&objects, &links, &output_path(&options, first), target).map_err(fail("link-failed"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{Arch, OS}, parser::lint::Lint};
+    use super::*;
+
+    fn parse (args: &str) -> Result<Options, String> {
+        match Options::parse(args.split_whitespace().map(String::from))? {
+            Command::Compile(x) => Ok(x),
+            Command::Help => Err(String::from("help"))
+        }
+    }
+
+    #[test]
+    fn args () {
+        let o = parse("main.alt -o out.s --emit=asm -O3 --keep-temps").unwrap();
+        assert_eq!(o.inputs, vec![PathBuf::from("main.alt")]);
+        assert_eq!(o.output, Some(PathBuf::from("out.s")));
+        assert_eq!((o.emit, o.optimization, o.keep_temps), (Emit::Asm, 3, true));
+    }
+
+    #[test]
+    fn error_format () {
+        assert_eq!(parse("main.alt --error-format=json --color=always").unwrap().error_format(), ErrorFormat::Json);
+        assert_eq!(parse("main.alt --color=never").unwrap().error_format(), ErrorFormat::Human { color: false });
+    }
+
+    #[test]
+    fn target () {
+        let o = parse("main.alt --target aarch64-linux").unwrap();
+        assert_eq!((o.target.cpu, o.target.os, o.target.ptr_bits), (Arch::Aarch64, OS::Linux, 64));
+        let o = parse("main.alt --target=i686-pc-windows-msvc").unwrap();
+        assert_eq!((o.target.cpu, o.target.os, o.target.ptr_bits), (Arch::X86, OS::Windows, 32));
+    }
+
+    #[test]
+    fn lints () {
+        let o = parse("main.alt -D warnings -A unused-variables -Wunused_imports").unwrap();
+        assert_eq!(o.lints.get(Lint::UnusedVariables), Level::Allow);
+        assert_eq!(o.lints.get(Lint::UnusedImports), Level::Warn);
+        assert_eq!(o.lints.get(Lint::UnreachableCode), Level::Deny);
+        assert_eq!(parse("main.alt").unwrap().lints.get(Lint::DuplicateFields), Level::Warn);
+    }
+
+    #[test]
+    fn help () {
+        assert!(matches!(Options::parse(["main.alt", "--help"].map(String::from).into_iter()), Ok(Command::Help)));
+        assert!(matches!(Options::parse(["-h"].map(String::from).into_iter()), Ok(Command::Help)));
+    }
+
+    #[test]
+    fn driver_errors () {
+        let e = run(parse("missing.alt --emit=llvm-ir").unwrap()).unwrap_err();
+        assert_eq!(e.code.as_deref(), Some("io-error"));
+        let json = render(&fail("link-failed")(String::from("Failed linking stage")), &[], ErrorFormat::Json);
+        assert!(json.contains("\"code\":\"link-failed\",\"message\":\"Failed linking stage\""));
+    }
+
+    #[test]
+    fn optimized_ir () {
+        let emit = |opt: u8| {
+            let out = env::temp_dir().join(format!("nalt-{}-O{opt}.ll", process::id()));
+            run(parse(&format!("src/tests/expressions/expressions.alt --emit=llvm-ir -O{opt} -o {}", out.display())).unwrap()).unwrap();
+            let ir = fs::read_to_string(&out).unwrap();
+            let _ = fs::remove_file(&out);
+            ir
+        };
+        assert!(emit(0).contains("alloca"));
+        // locals are promoted to registers by optimizations
+        assert!(!emit(2).contains("alloca"));
+    }
+
+    #[test]
+    fn internal_error () {
+        let e = internal(|| -> i32 { panic!("{} has no type", "x") }).unwrap_err();
+        assert_eq!((e.code.as_deref(), e.notes.as_slice()), (Some("internal-error"), &[String::from("x has no type")][..]));
+        assert_eq!(internal(|| 4), Ok(4));
+    }
+
+    #[test]
+    fn args_errors () {
+        assert!(parse("").is_err());
+        assert!(parse("main.alt -O4").is_err());
+        assert!(parse("main.alt --emit=wasm").is_err());
+        assert!(parse("a.alt b.alt --emit=obj -o x.o").is_err());
+        assert!(parse("a.alt b.alt lib.o -o app").is_ok());
+        assert!(parse("main.alt --target riscv64-linux").is_err());
+        assert!(parse("main.alt --error-format=xml").is_err());
+        assert!(parse("main.alt --color=sometimes").is_err());
+        assert!(parse("main.alt -W unused-everything").is_err());
+        assert!(parse("main.alt -D").is_err());
+    }
+}
\ No newline at end of file
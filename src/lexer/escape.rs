@@ -0,0 +1,67 @@
+/// Malformed escape sequence: offset of its backslash and its length, both in chars of literal contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeError { pub at: usize, pub len: usize, pub message: String }
+
+fn fail<T> (at: usize, len: usize, message: &str) -> Result<T, EscapeError> {
+    Err(EscapeError { at, len, message: message.into() })
+}
+
+/// Decodes contents of string or char literal (without quotes) into bytes. \
+/// In byte literals `\x` goes up to 0xFF, while in others only up to 0x7F so result is valid UTF-8
+pub fn unescape (body: &str, byte: bool) -> Result<Vec<u8>, EscapeError> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut bytes = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c != '\\' {
+            if byte && !c.is_ascii() { return fail(i, 1, "Non-ASCII character in byte literal") }
+            let mut buf = [0; 4];
+            bytes.extend(c.encode_utf8(&mut buf).as_bytes());
+            i += 1;
+            continue
+        }
+        let Some(&e) = chars.get(i+1) else { return fail(i, 1, "Unterminated escape sequence") };
+        match e {
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            '0' => bytes.push(0),
+            '\\' | '\'' | '\"' => bytes.push(e as u8),
+            'x' => {
+                let digits: String = chars.iter().skip(i+2).take(2).collect();
+                let Some(x) = (digits.len() == 2).then(|| u8::from_str_radix(&digits, 16).ok()).flatten() else {
+                    return fail(i, 2 + digits.len(), "Escape '\\x' must be followed by two hex digits")
+                };
+                if !byte && x > 0x7F { return fail(i, 4, "Escape '\\x' in string can't go above '\\x7F', use '\\u{..}' instead") }
+                bytes.push(x);
+                i += 4;
+                continue
+            }
+            'u' => {
+                if byte { return fail(i, 2, "Unicode escapes aren't allowed in byte literals") }
+                let Some(close) = chars.get(i+2).filter(|x| **x == '{').and(chars[i+2..].iter().position(|x| *x == '}')) else {
+                    return fail(i, 2, "Escape '\\u' must be followed by '{', hex code and '}'")
+                };
+                let len = close + 3;
+                let digits: String = chars[i+3..i+2+close].iter().filter(|x| **x != '_').collect();
+                let Some(c) = (1..=6).contains(&digits.len()).then(|| u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)).flatten() else {
+                    return fail(i, len, "Invalid unicode escape, expected code of character in 1 to 6 hex digits")
+                };
+                let mut buf = [0; 4];
+                bytes.extend(c.encode_utf8(&mut buf).as_bytes());
+                i += len;
+                continue
+            }
+            // line continuation, skips line break and leading whitespace of next line
+            '\n' => {
+                i += 2;
+                while chars.get(i).is_some_and(|x| x.is_whitespace()) { i += 1; }
+                continue
+            }
+            _ => return fail(i, 2, &format!("Unknown escape sequence '\\{e}'"))
+        }
+        i += 2;
+    }
+    Ok(bytes)
+}
\ No newline at end of file
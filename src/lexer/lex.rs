@@ -2,7 +2,7 @@ use super::rules::rules;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenKind {
-    String, Int, Float, Arithmetic, Logical, Bitwise, Brackets, Word,
+    String, Char, Int, Float, Arithmetic, Logical, Bitwise, Brackets, Word,
     Assign, LabelSymbol, Punctuation, Special, In,
     Meta
 }
@@ -11,7 +11,9 @@ pub enum TokenKind {
 pub struct Token {
     pub typ: TokenKind,
     pub value: String,
-    pub col: usize, pub line: usize
+    pub col: usize, pub line: usize,
+    /// Index of source file in compilation, 0 is the main one
+    pub file: usize
 }
 
 pub trait CollectChars { fn collect_chars (&self) -> Vec<char>; }
@@ -31,6 +33,10 @@ macro_rules! stdimplchars {
 stdimplchars!(&str, &String, String);
 
 pub fn lex (code: impl CollectChars) -> Vec<Token> {
+    lex_file(code, 0)
+}
+
+pub fn lex_file (code: impl CollectChars, file: usize) -> Vec<Token> {
     let rules = rules();
     let code = code.collect_chars();
     
@@ -44,8 +50,13 @@ pub fn lex (code: impl CollectChars) -> Vec<Token> {
             for i in &rules {
                 let val_jmp = i(&code[index..]);
                 if let (Some((value, typ)), off) = val_jmp {
-                    tokens.push(Token { typ, value, col, line });
-                    index += off; col += off;
+                    // strings may span several lines
+                    let (l, c) = match value.rfind('\n') {
+                        Some(x) => (line + value.matches('\n').count(), value[x..].chars().count()),
+                        None => (line, col + off)
+                    };
+                    tokens.push(Token { typ, value, col, line, file });
+                    index += off; (line, col) = (l, c);
                     continue 'x
                 }
                 else if val_jmp.1 > 0 {
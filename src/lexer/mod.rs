@@ -1,4 +1,6 @@
 mod lex;
 mod rules;
+mod escape;
 
-pub use lex::*;
\ No newline at end of file
+pub use lex::*;
+pub use escape::*;
\ No newline at end of file
@@ -4,13 +4,14 @@ use super::TokenKind;
 pub type RuleOutput = (Option<(String, TokenKind)>, usize);
 pub type Rule = fn (&[char]) -> RuleOutput;
 
+/// `"a"` or `c"a"`, contents are decoded by parser
 fn string (code: &[char]) -> RuleOutput {
-    if code[0] != '\"' { return (None, 0) }
+    let start = if code[0] == 'c' && code.get(1) == Some(&'\"') { 1 } else if code[0] == '\"' { 0 } else { return (None, 0) };
     let mut esc = false;
-    for (index, i) in code.iter().skip(1).enumerate() {
+    for (index, i) in code.iter().enumerate().skip(start + 1) {
         if *i == '\"' && !esc {
-            let l = code[0..=index+1].iter().collect::<String>();
-            return (Some((l, TokenKind::String)), index+2)
+            let l = code[0..=index].iter().collect::<String>();
+            return (Some((l, TokenKind::String)), index+1)
         }
         else if *i == '\\' { esc = true; }
         else if esc { esc = false; }
@@ -18,6 +19,21 @@ fn string (code: &[char]) -> RuleOutput {
     (None, 0)
 }
 
+/// `'a'` or `b'a'`, contents are decoded by parser
+fn char (code: &[char]) -> RuleOutput {
+    let start = if code[0] == 'b' && code.get(1) == Some(&'\'') { 1 } else if code[0] == '\'' { 0 } else { return (None, 0) };
+    let mut index = start + 1;
+    while let Some(c) = code.get(index) {
+        match c {
+            '\'' => return (Some((code[..=index].iter().collect(), TokenKind::Char)), index + 1),
+            '\n' => break,
+            '\\' => index += 2,
+            _ => index += 1
+        }
+    }
+    (None, 0)
+}
+
 fn number (code: &[char]) -> RuleOutput {
     let mut typ = TokenKind::Int;
     // && !(code[0] == '-' && code.get(1).is_some_and(|x| x.is_digit(10)))
@@ -30,7 +46,8 @@ fn number (code: &[char]) -> RuleOutput {
         if code[index] == '.' && typ != TokenKind::Float && code.get(index+1).is_some_and(|x| x.is_digit(10)) {
             typ = TokenKind::Float;
         }
-        else if !code[index].is_numeric() { break }
+        // digits of other bases, separators and type suffix are checked by parser
+        else if !code[index].is_alphanumeric() && code[index] != '_' { break }
         index += 1;
     }
 
@@ -58,14 +75,20 @@ fn word (code: &[char]) -> RuleOutput {
 }
 
 fn symbol (code: &[char]) -> RuleOutput {
+    if code.len() > 2 && code[0..=2] == ['.', '.', '='] {
+        return (Some(("..=".into(), TokenKind::Special)), 3)
+    }
+    if code.len() > 2 && matches!(code[0..=2], ['<', '<', '='] | ['>', '>', '=']) {
+        return (Some((code[0..=2].iter().collect(), TokenKind::Assign)), 3)
+    }
     if code.len() > 1 {
         let x = code[0..=1].iter().collect::<String>();
         match x.as_str() {
             "&&" | "||" | ">=" | "<=" | "!=" | "==" => return (Some((x, TokenKind::Logical)), 2),
             "//" => { return (None, code.iter().position(|x| *x == '\n').unwrap_or(code.len())) },
             ">>" | "<<" => return (Some((x, TokenKind::Bitwise)), 2),
-            "+=" | "-=" | "*=" | "/=" | "%=" => return (Some((x, TokenKind::Assign)), 2),
-            ".*" | "::" => return (Some((x, TokenKind::Special)), 2),
+            "+=" | "-=" | "*=" | "/=" | "%=" | "&=" | "|=" | "^=" => return (Some((x, TokenKind::Assign)), 2),
+            ".*" | "::" | "=>" | ".." => return (Some((x, TokenKind::Special)), 2),
             _ => {}
         }
     }
@@ -75,8 +98,7 @@ fn symbol (code: &[char]) -> RuleOutput {
         '+' | '-' | '*' | '/' | '%' => return (Some((code[0].to_string(), TokenKind::Arithmetic)), 1),
         '(' | ')' | '[' | ']' | '{' | '}' => return (Some((code[0].to_string(), TokenKind::Brackets)), 1),
         '>' | '<' | '!' => return (Some((code[0].to_string(), TokenKind::Logical)), 1),
-        '&' | '^' | '|' => return (Some((code[0].to_string(), TokenKind::Bitwise)), 1),
-        '~' => return (Some((code[0].to_string(), TokenKind::Special)), 1), // TODO REMOVE
+        '&' | '^' | '|' | '~' => return (Some((code[0].to_string(), TokenKind::Bitwise)), 1),
         '@' => return (Some((code[0].to_string(), TokenKind::LabelSymbol)), 1),
         ',' | ';' => return (Some((code[0].to_string(), TokenKind::Punctuation)), 1),
         '.' => return (Some((code[0].to_string(), TokenKind::In)), 1),
@@ -86,9 +108,10 @@ fn symbol (code: &[char]) -> RuleOutput {
     (None, 0)
 }
 
-pub fn rules () -> [Rule; 4] {
+pub fn rules () -> [Rule; 5] {
     [
         string,
+        char,
         number,
         word,
         symbol
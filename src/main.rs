@@ -1,40 +1,29 @@
-use std::{env, fs, path::absolute};
+use std::{env, process};
 
-// use compiler::codegen;
-use compiler::{
-    // ir::{translate_all, x86_display::display_instructions, IRb},
-    llvm::{compile, emit_asm},
-};
-use lexer::lex;
-use parser::{bindings::Bindings, parse_program};
+use driver::{report, ColorChoice, Command, Options, USAGE};
+use parser::message::{Diagnostic, ErrorFormat};
 
 mod lexer;
 mod parser;
 mod compiler;
+mod driver;
 mod tests;
 
 fn main () {
-    let args: Vec<String> = env::args().collect();
-    let mut asm = false;
-    let mut optimization = 1;
-    for arg in args {
-        if arg.starts_with("-O") {
-            let Ok(x) = arg.as_str()[2..].parse::<u8>() else { panic!("Failed to parse argument {arg}") };
-            if x > 3 { panic!("Optimization level of {x} is invalid (maximum is 3)") }
-            optimization = x;
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Command::Compile(x)) => x,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            process::exit(0)
         }
-        match arg.as_str() {
-            "--emit-asm" => asm = true,
-            _ => {}
+        Err(e) => {
+            // format is asked for by arguments that failed to parse, so it's looked up among them as is
+            let format = if env::args().any(|x| x == "--error-format=json") { ErrorFormat::Json } else { ErrorFormat::Human { color: ColorChoice::Auto.enabled() } };
+            report(&Diagnostic::error(&e).code("invalid-argument"), format);
+            process::exit(1)
         }
-    }
-    let path = absolute("./bober.alt").unwrap();
-    let f = String::from_utf8(fs::read(&path).unwrap()).unwrap();
-    let tokens = lex(&f);
-
-    let mut bindings = Bindings::new(&f, path, None);
-    let program = parse_program(&tokens, &mut bindings);
-    // println!("{program:#?}");
+    };
+    let format = options.error_format();
 
     // [DONE]: return handling
     // [DONE]: if-else and logical expressions
@@ -47,12 +36,26 @@ fn main () {
     // [DONE]: partial parsing of functions, even with namespaces
     // (TODOOOOO): tests for all shit that I made so far (control flow instructions, mutability, namespaces, functions and function pointers, pointers, type casting)
     // (todo): structs
-    // (todo): 'defer' and new variable handling for this if needed
-    // (todo): floating point operations support
-    // (todo): templates
+    // [DONE]: 'defer' and new variable handling for this if needed
+    // [DONE]: floating point operations support
+    // [DONE]: enums with payloads (tag + payload storage)
+    // [DONE]: 'match' with exhaustiveness checking
+    // [DONE]: templates (generic functions and structs, monomorphized)
+    // [DONE]: traits with static dispatch and bounds on generic parameters
+    // [DONE]: trait objects ('&dyn Trait') with vtables
+    // [DONE]: methods with 'self' receivers, 'Self' and 'Type::function()' paths
+    // [DONE]: 'while' and 'for' over ranges and arrays
+    // [DONE]: shifts, xor, bitwise not and compound assignments for them
+    // [DONE]: escape sequences in strings, char and byte literals
+    // [DONE]: hex, octal and binary integers, '_' separators, type suffixes and range checks of literals
+    // [DONE]: global constants and static variables
+    // [DONE]: slices '&[T]' with length, sub-slicing and iteration
+    // [DONE]: string literals as '&[u8]', c"..." strings for C
+    // [DONE]: if/else, blocks and loop as expressions
+    // [DONE]: opaque types for C handles
 
-    if bindings.is_compileable() {
-        if asm { emit_asm(program) }
-        else { compile(program, bindings, optimization) }
+    if let Err(e) = driver::run(options) {
+        report(&e, format);
+        process::exit(1)
     }
-}
+}
\ No newline at end of file
@@ -1,11 +1,12 @@
-use std::{collections::{HashMap, HashSet}, mem, path::PathBuf};
+use std::{cell::{Cell, OnceCell}, collections::{HashMap, HashSet}, fs, mem, path::{Path, PathBuf}};
 
-use crate::{compiler::Target, lexer::Token, parser::{message::{parsing_error_message, point, point_range}, SharedValue}};
+use crate::{compiler::Target, lexer::{lex_file, Token}, parser::{lint::{Lint, LintLevels}, message::{render, Diagnostic, Diagnostics, ErrorFormat, FatalError, Span}, SharedValue, Value}};
 
 use super::r#type::Type;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Context { Fn(Type), If, Loop, Array }
+/// `Loop` has label and type of its result: none for 'while' and 'for', 'Guess' until first 'break' of 'loop'
+pub enum Context { Fn(Type), If, Loop(Option<String>, Option<Type>), Array }
 
 #[derive(Debug, Clone)]
 pub enum Bind<'a> {
@@ -21,6 +22,15 @@ pub enum Bind<'a> {
     Namespace(HashMap<String, Bind<'a>>),
     Type(Option<SharedValue<'a>>),
     Alias(String),
+    /** type argument bound to parameter of generic declaration */
+    Generic(Type),
+    /** generic function */
+    Template(SharedValue<'a>),
+    Trait(SharedValue<'a>),
+    /** value of constant, evaluated to literal */
+    Const(Value<'a>),
+    /** (type, mutable) of static, named by its global name */
+    Static(Type, bool),
     // Public(Box<Bind<'a>>),
     // Value(Value<'a>)
 }
@@ -79,28 +89,83 @@ macro_rules! gen_bindings_promised_operations {
     };
 }
 
+/// Variable declared in some scope, kept to report it if it's never used
+#[derive(Debug)]
+struct Local<'a> { token: &'a Token, depth: usize, used: bool }
+
+/// Names brought by one `use` path (several for `*`)
+#[derive(Debug)]
+struct Import<'a> { names: Vec<String>, path: String, token: &'a Token, used: Cell<bool> }
+
+/// Source file taking part in compilation. `namespace` is global name of namespace it's imported as (none for the main file)
+#[derive(Debug)]
+struct SourceFile<'a> { path: PathBuf, canonical: Option<PathBuf>, code: &'a str, namespace: Option<String> }
+
+/// Arena for code and tokens of imported files, as values refer to them up to the end of compilation.
+/// It's owned by driver, so it outlives bindings
+#[derive(Debug, Default)]
+pub struct Sources { file: OnceCell<(String, Vec<Token>)>, next: OnceCell<Box<Sources>> }
+
+impl Sources {
+    /// Keeps `code` of file along with its tokens, lexed with index `file`
+    fn add (&self, code: String, file: usize) -> (&str, &[Token]) {
+        if self.file.get().is_some() { return self.next.get_or_init(Box::default).add(code, file) }
+        let tokens = lex_file(code.as_str(), file);
+        let (code, tokens) = self.file.get_or_init(|| (code, tokens));
+        (code, tokens)
+    }
+}
+
+/// Implementation of trait for type: methods mapped to global names of functions implementing them
+#[derive(Debug)]
+struct TraitImpl { typ: Type, r#trait: String, methods: HashMap<String, String> }
+
 #[derive(Debug, Default)]
 pub struct Bindings<'a> {
     content: Vec<HashMap<String, Bind<'a>>>,
     /// Scope context
     context: Vec<Context>,
     global_prefix: Vec<String>,
-    current_file: PathBuf,
-    initial_code: &'a str,
+    /// Source map: tokens refer to files by their index here
+    files: Vec<SourceFile<'a>>,
+    sources: Option<&'a Sources>,
+    /// Files being parsed right now, innermost import last
+    importing: Vec<usize>,
     // function_pointers: Vec<SharedValue<'a>>,
     promised_values: PromisedValues<'a>,
     link: HashSet<&'a str>,
     target: Target,
-    compileable: bool,
+    diagnostics: Diagnostics,
+    lints: LintLevels,
+    locals: Vec<Local<'a>>,
+    /// Unused variables of scopes already left, reported in order of declaration once there are no variables in scope
+    unused: Vec<&'a Token>,
+    /// Global constants and statics yet to be declared: (global name, name token, what it is)
+    ahead: Vec<(String, &'a Token, &'static str)>,
+    /// Fatal error is reported, so scopes left from now on are unwound by it, not parsed to the end
+    aborted: Cell<bool>,
+    imports: Vec<Import<'a>>,
+    /// Instances of generic structs by their mangled name: (template, type arguments)
+    generic_instances: HashMap<String, (String, Vec<Type>)>,
+    /// Instances of generic functions by their mangled name: how many instances deep they were requested
+    fn_instances: HashMap<String, usize>,
+    /// Depth of instance whose body is being parsed, 0 outside of generic functions
+    instance_depth: usize,
+    /// `>>` closed two type argument lists, outer one is yet to see it
+    split_close: bool,
+    impls: Vec<TraitImpl>,
+    /// Traits by global name, regardless of visibility, to build vtables of `&dyn Trait`
+    traits: HashMap<String, SharedValue<'a>>,
+    /// Structs and enums by global name, regardless of visibility, as values of private types may still be reached through public ones
+    types: HashMap<String, SharedValue<'a>>,
     // type_pointers: Vec<usize>
 }
 impl<'a> Bindings<'a> {
-    pub fn new (initial_code: &'a str, path: PathBuf, target: Option<Target>) -> Self {
-        let mut s = Self::default();
+    pub fn new (initial_code: &'a str, path: PathBuf, target: Option<Target>, sources: &'a Sources) -> Self {
+        let mut s = Self { sources: Some(sources), ..Self::default() };
         s.content.push(HashMap::new());
-        s.initial_code = initial_code;
-        s.current_file = path;
-        s.compileable = true;
+        s.files.push(SourceFile { canonical: fs::canonicalize(&path).ok(), path, code: initial_code, namespace: None });
+        s.importing.push(0);
         if let Some(target) = target { s.target = target; }
         s
     }
@@ -124,6 +189,11 @@ impl<'a> Bindings<'a> {
     // }
 
     pub fn get (&self, name: &str) -> Option<&Bind<'a>> {
+        let x = self.get_noimport(name);
+        if let (Some(Bind::Alias(_)), Some(i)) = (x, self.imports.iter().rev().find(|i| i.names.iter().any(|x| x == name))) { i.used.set(true); }
+        x
+    }
+    fn get_noimport (&self, name: &str) -> Option<&Bind<'a>> {
         bget!(self, name, false)
     }
     pub fn get_mut (&mut self, name: &str) -> Option<&mut Bind<'a>> {
@@ -139,9 +209,59 @@ impl<'a> Bindings<'a> {
 
     pub fn leave_scope (&mut self) {
         self.content.pop();
+        self.drop_locals();
     }
     pub fn pop_scope (&mut self) -> HashMap<String, Bind<'a>> {
-        self.content.pop().unwrap()
+        let x = self.content.pop().unwrap();
+        self.drop_locals();
+        x
+    }
+
+    /// Declares variable named by `token` in current scope
+    pub fn insert_local (&mut self, token: &'a Token, typ: Type, mutable: bool) {
+        self.insert(&token.value, Bind::Let(typ, mutable));
+        self.locals.push(Local { token, depth: self.content.len(), used: false });
+    }
+    /// Marks innermost variable with this name as read
+    pub fn use_local (&mut self, name: &str) {
+        if let Some(x) = self.locals.iter_mut().rev().find(|x| x.token.value == name) { x.used = true; }
+    }
+    fn drop_locals (&mut self) {
+        let at = self.locals.iter().rposition(|x| x.depth <= self.content.len()).map_or(0, |x| x + 1);
+        let dropped = self.locals.split_off(at);
+        // uses of variables may be in code that was never parsed
+        if !self.aborted.get() {
+            self.unused.extend(dropped.into_iter().filter(|x| !x.used && !x.token.value.starts_with('_')).map(|x| x.token));
+        }
+        if !self.locals.is_empty() { return }
+        let mut unused = mem::take(&mut self.unused);
+        unused.sort_by_key(|x| (x.file, x.line, x.col));
+        for token in unused {
+            self.lint(Lint::UnusedVariables,
+                Diagnostic::warning(&format!("Unused variable '{}'", token.value))
+                .span(Span::token(token))
+                .help(&format!("if this is intentional, prefix it with an underscore: '_{}'", token.value))
+            );
+        }
+    }
+
+    /// Remembers names brought into scope by one `use` path, `path` is how it's written in code
+    pub fn import (&mut self, names: Vec<String>, path: String, token: &'a Token) {
+        self.imports.push(Import { names, path, token, used: Cell::new(false) });
+    }
+    pub fn report_unused_imports (&mut self) {
+        for Import { path, token, used, .. } in mem::take(&mut self.imports) {
+            if used.get() { continue }
+            self.lint(Lint::UnusedImports, Diagnostic::warning(&format!("Unused import '{path}'")).span(Span::token(token)));
+        }
+    }
+
+    /// Remembers global declared further in code, as its initializer is evaluated only once it's reached
+    pub fn declare_ahead (&mut self, name: String, token: &'a Token, what: &'static str) { self.ahead.push((name, token, what)); }
+    /// Global named `name` that is declared after `at`
+    pub fn declared_after (&self, name: &str, at: &Token) -> Option<(&'a Token, &'static str)> {
+        let name = self.global_name(name);
+        self.ahead.iter().find(|(x, t, _)| *x == name && t.file == at.file && (t.line, t.col) > (at.line, at.col)).map(|(_, t, w)| (*t, *w))
     }
 
     // pub fn insert (&mut self, name: &'a str, value: Bind<'a>) { self.content.last_mut().unwrap().insert(name, value); }
@@ -165,22 +285,30 @@ impl<'a> Bindings<'a> {
             |y| std::mem::discriminant(*y) == std::mem::discriminant(x)
         )
     }
+    /// Type of result of innermost loop, or loop with `label`
+    pub fn loop_result (&mut self, label: Option<&str>) -> Option<&mut Option<Type>> {
+        self.context.iter_mut().rev().find_map(|x| match x {
+            Context::Loop(l, t) if label.is_none() || l.as_deref() == label => Some(t),
+            _ => None
+        })
+    }
     // pub fn get_last_context (&self) -> Option<&Context> { self.context.last() }
     pub fn pop_context (&mut self) -> Option<Context> { self.context.pop() }
     pub fn move_context (&mut self) -> Vec<Context> { mem::take(&mut self.context) }
     pub fn switch_context (&mut self, x: Vec<Context>) { let _ = mem::replace(&mut self.context, x); }
 
-    pub fn context_scope (&mut self, context: Context, scope: impl FnOnce(&mut Self) -> ()) {
+    pub fn context_scope<R> (&mut self, context: Context, scope: impl FnOnce(&mut Self) -> R) -> R {
         self.join_scope();
         let upper_context = if let Context::Fn(_) = context { Some(self.move_context()) } else { None };
         self.push_context(context);
         // self.insert(MARK_BARRIER, Bind::Mark);
 
-        scope(self);
+        let result = scope(self);
 
         self.pop_context();
         if let Some(x) = upper_context { self.switch_context(x); }
         self.leave_scope();
+        result
     }
 
     pub fn push_global_prefix (&mut self, prefix: String) {
@@ -205,29 +333,94 @@ impl<'a> Bindings<'a> {
     gen_bindings_promised_operations!(push_function, get_functions, move_functions, functions);
     gen_bindings_promised_operations!(push_type, get_types, move_types, types);
 
+    pub fn register_instance (&mut self, name: String, template: String, types: Vec<Type>) { self.generic_instances.insert(name, (template, types)); }
+    pub fn get_instance (&self, name: &str) -> Option<&(String, Vec<Type>)> { self.generic_instances.get(name) }
+    /// Remembers instance of generic function requested from body being parsed and returns how deep it is
+    pub fn register_fn_instance (&mut self, name: String) -> usize {
+        let depth = self.instance_depth + 1;
+        self.fn_instances.insert(name, depth);
+        depth
+    }
+    /// Runs `f` over body of function `name`, so instances requested there are one deeper than it
+    pub fn instance_scope<T> (&mut self, name: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let depth = mem::replace(&mut self.instance_depth, self.fn_instances.get(name).copied().unwrap_or(0));
+        let x = f(self);
+        self.instance_depth = depth;
+        x
+    }
+    pub fn set_split_close (&mut self) { self.split_close = true; }
+    pub fn take_split_close (&mut self) -> bool { mem::take(&mut self.split_close) }
+
+    pub fn declare_trait (&mut self, name: String, l: SharedValue<'a>) { self.traits.insert(name, l); }
+    pub fn get_trait (&self, name: &str) -> Option<&SharedValue<'a>> { self.traits.get(name) }
+    pub fn declare_type (&mut self, name: String, l: SharedValue<'a>) { self.types.insert(name, l); }
+    pub fn get_type (&self, name: &str) -> Option<&SharedValue<'a>> { self.types.get(name) }
+    pub fn add_impl (&mut self, typ: Type, r#trait: String, methods: HashMap<String, String>) { self.impls.push(TraitImpl { typ, r#trait, methods }); }
+    pub fn implements (&self, typ: &Type, r#trait: &str) -> bool { self.impls.iter().any(|x| x.typ == *typ && x.r#trait == r#trait) }
+    pub fn impl_methods (&self, typ: &Type, r#trait: &str) -> Option<&HashMap<String, String>> {
+        self.impls.iter().find(|x| x.typ == *typ && x.r#trait == r#trait).map(|x| &x.methods)
+    }
+    /// Functions implementing method for type, along with traits they come from
+    pub fn find_methods (&self, typ: &Type, method: &str) -> Vec<(&str, &str)> {
+        self.impls.iter().filter(|x| x.typ == *typ).filter_map(|x| Some((x.r#trait.as_str(), x.methods.get(method)?.as_str()))).collect()
+    }
+
     pub fn link (&mut self, k: &'a str) { self.link.insert(k); }
     pub fn move_links (self) -> HashSet<&'a str> { self.link }
+    pub fn get_links (&self) -> &HashSet<&'a str> { &self.link }
 
     pub fn target_ptr_bits (&self) -> u8 { self.target.ptr_bits }
 
-    pub fn gentle_error (&mut self, token: &Token, message: &str) {
-        let Token { col, line, value, .. } = token;
-        parsing_error_message(self, message, *line, *col);
-        let lines: Vec<&str> = self.get_initial_code().lines().collect();
-        point(lines, *line, *col, value.chars().count(), "[91m");
-        if self.compileable { self.compileable = false; }
+    pub fn set_lints (&mut self, lints: LintLevels) { self.lints = lints; }
+    /// Reports diagnostic of `lint` with severity requested for it
+    pub fn lint (&self, lint: Lint, d: Diagnostic) {
+        if let Some(d) = self.lints.apply(lint, d) { self.diagnose(d); }
+    }
+
+    /// Reports diagnostic without interrupting parsing
+    pub fn diagnose (&self, d: Diagnostic) { self.diagnostics.push(d); }
+    /// Reports diagnostic, after which parsing of the whole program is aborted with returned error
+    pub fn fatal (&self, d: Diagnostic) -> FatalError {
+        self.aborted.set(true);
+        self.diagnose(d);
+        FatalError
+    }
+    pub fn gentle_error (&mut self, token: &Token, message: &str, code: &str) {
+        self.diagnose(Diagnostic::error(message).span(Span::token(token)).code(code));
+    }
+    pub fn gentle_error_range (&mut self, tokens: &[Token], message: &str, code: &str) {
+        self.diagnose(Diagnostic::error(message).span(Span::range(tokens)).code(code));
     }
-    pub fn gentle_error_range (&mut self, tokens: &[Token], message: &str) {
-        let f = &tokens[0];
-        parsing_error_message(self, message, f.line, f.col);
-        let lines: Vec<&str> = self.get_initial_code().lines().collect();
-        point_range(tokens, lines, "[91m");
-        if self.compileable { self.compileable = false; }
+    /// Renders all reported diagnostics in order of reporting, draining them
+    pub fn render_diagnostics (&self, format: ErrorFormat) -> String {
+        let files = self.files.iter().map(|x| (x.path.as_path(), x.code)).collect::<Vec<_>>();
+        self.diagnostics.take().iter().map(|x| render(x, &files, format)).collect()
     }
 
-    pub fn is_compileable (&self) -> bool { self.compileable }
-    pub fn get_initial_code (&self) -> &'a str { self.initial_code }
-    pub fn get_current_file_path (&self) -> &PathBuf { &self.current_file }
+    pub fn is_compileable (&self) -> bool { !self.diagnostics.has_errors() }
+    pub fn error_count (&self) -> usize { self.diagnostics.error_count() }
+    pub fn get_current_file_path (&self) -> &PathBuf { &self.files[0].path }
+
+    // source map
+
+    pub fn get_file_path (&self, file: usize) -> &Path { &self.files[file].path }
+    /// Finds already known file, comparing canonical paths
+    pub fn find_file (&self, canonical: &Path) -> Option<usize> {
+        self.files.iter().position(|x| x.canonical.as_deref() == Some(canonical))
+    }
+    /// Registers file, returning its index and tokens
+    pub fn add_file (&mut self, path: PathBuf, canonical: PathBuf, code: String) -> (usize, &'a [Token]) {
+        let file = self.files.len();
+        let (code, tokens) = self.sources.unwrap().add(code, file);
+        self.files.push(SourceFile { path, canonical: Some(canonical), code, namespace: None });
+        (file, tokens)
+    }
+    pub fn get_file_namespace (&self, file: usize) -> Option<&String> { self.files[file].namespace.as_ref() }
+    pub fn set_file_namespace (&mut self, file: usize, namespace: String) { self.files[file].namespace = Some(namespace); }
+    /// Chain of files being imported right now, from the main one
+    pub fn get_importing (&self) -> &[usize] { &self.importing }
+    pub fn enter_file (&mut self, file: usize) { self.importing.push(file); }
+    pub fn leave_file (&mut self) { self.importing.pop(); }
 }
 
 pub const MARK_BARRIER: &str = "@barrier";
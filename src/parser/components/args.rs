@@ -1,22 +1,42 @@
-use crate::{lexer::{Token, TokenKind}, parser::{bindings::Bindings, simpler::{ignore_separator, next_mutable_flag, next_token, next_type}, r#type::Type}};
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::{Bind, Bindings}, message::{error, PResult}, simpler::{ignore_separator, next_mutable_flag, next_token, next_type}, r#type::Type}};
 
 #[derive(Debug, Clone)]
-pub struct Arg<'a> { pub name: &'a str, pub typ: Type, pub mutable: bool }
+pub struct Arg<'a> { pub name: &'a str, pub typ: Type, pub mutable: bool, pub token: &'a Token }
 
 // MAYBE TODO: automatic type filling (exmaple: a b i32 = a i32 b i32) by special Guess type
 
-pub fn parse_args<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> (Vec<Arg<'a>>, usize) {
+pub fn parse_args<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<(Vec<Arg<'a>>, usize)> {
     let mut args: Vec<Arg> = vec![];
     let mut last = 0;
+    if let Some(arg) = receiver(&mut last, tokens, bindings)? {
+        args.push(arg);
+        ignore_separator(&mut last, tokens);
+    }
     loop {
         let mutable = next_mutable_flag(&mut last, tokens);
         if let Some(i) = next_token(&mut last, tokens, None, Some(TokenKind::Word)) {
-            let Some(typ) = next_type(&mut last, tokens, bindings) else { return (args, last) };
-            args.push(Arg { name: &i.value, typ, mutable });
+            let Some(typ) = next_type(&mut last, tokens, bindings)? else { return Ok((args, last)) };
+            args.push(Arg { name: &i.value, typ, mutable, token: i });
             ignore_separator(&mut last, tokens);            
         }
         else { break }
     }
     
-    (args, last)
+    Ok((args, last))
+}
+
+/// `self`, `mut self`, `&self` or `&mut self` as first argument of method, where `Self` is type of `impl`
+fn receiver<'a> (last: &mut usize, tokens: &'a [Token], bindings: &Bindings<'a>) -> PResult<Option<Arg<'a>>> {
+    let mut off = *last;
+    let ptr = next_token(&mut off, tokens, Some("&"), None).is_some();
+    let mutable = next_mutable_flag(&mut off, tokens);
+    let Some(token) = next_token(&mut off, tokens, Some("self"), Some(TokenKind::Word)) else { return Ok(None) };
+    // explicitly typed `self` is an ordinary argument
+    if tokens.get(off).is_some_and(|x| x.value != ",") { return Ok(None) }
+    let Some(Bind::Generic(typ)) = bindings.get("Self") else { return Err(error(token, bindings, "'self' is only allowed in methods inside 'impl'", "misplaced-item")) };
+    *last = off;
+    Ok(Some(match ptr {
+        true => Arg { name: &token.value, typ: Type::Ptr(Some(Box::new(typ.clone())), mutable), mutable: false, token },
+        false => Arg { name: &token.value, typ: typ.clone(), mutable, token }
+    }))
 }
\ No newline at end of file
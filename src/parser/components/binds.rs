@@ -1,24 +1,37 @@
-use std::mem;
+use std::{fs, mem, path::Path};
 
-use crate::{lexer::{Token, TokenKind}, nvalue, parser::{Operation, Value, bindings::{Bind, Bindings}, components::{r#fn::r#fn, types::struct_init}, message::{assert, assert_range, error, error_range}, parse_inplace, parse_np, simpler::{ignore_separator, next_access_type_member, next_body, next_body_optional, next_call, next_deref, next_load_address, next_mutable_flag, next_name, next_token, next_type, next_uniform_call, next_value}, r#type::{Type, default_type, extract_type, penetrate_type, pure_type}}};
+use crate::{lexer::{Token, TokenKind}, nvalue, parser::{Operation, Value, bindings::{Bind, Bindings, Context}, components::{generics::generic_call, globals::{r#const, r#static}, slices::{elements_array, range_dots, subslice}, traits::{coerce, dyn_method, find_method, impl_trait, r#trait}, r#fn::r#fn, types::{enum_init, r#enum, r#struct, struct_init}}, message::{assert, assert_range, error, error_range, token_at, Diagnostic, PResult, Span}, numbers::check_literals, parse_inplace, parse_np, simpler::{ignore_separator, next_access_type_member, next_body, next_body_optional, next_call, next_deref, next_load_address, next_mutable_flag, next_name, next_token, next_type, next_uniform_call, next_value}, r#type::{Type, default_type, parse_type, extract_type, penetrate_type, pure_type}}};
 
 pub const ASSIGN_NOT_EXPECTED: &str = "Assign operation not expected here";
 
-fn variable_look <'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) {
+fn variable_look <'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<()> {
     // fix here
-    next_load_address(off, tokens, instructions, bindings, mutable);
-    next_access_type_member(off, tokens, instructions, bindings, mutable);
-    next_call(off, tokens, instructions, bindings);
-    if let Some(_) = next_deref(off, tokens, instructions, bindings) {}
-    else if mutable { modify(off, tokens, instructions, bindings); }
+    next_load_address(off, tokens, instructions, bindings, mutable)?;
+    next_access_type_member(off, tokens, instructions, bindings, mutable)?;
+    next_call(off, tokens, instructions, bindings)?;
+    if next_deref(off, tokens, instructions, bindings)?.is_some() {}
+    else if mutable { modify(off, tokens, instructions, bindings)?; }
+    Ok(())
 }
 
-pub fn external_word<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn external_word<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut name = tokens[0].value.clone();
     let mut off = 1;
 
     loop {
-    let Some(x) = bindings.get(&name) else { bindings.gentle_error(&tokens[off-1], "Unknown keyword"); return off };
+    let Some(x) = bindings.get(&name) else {
+        if let Some((decl, what)) = bindings.declared_after(&name, &tokens[off-1]) {
+            return Err(bindings.fatal(
+                Diagnostic::error(&format!("{what} '{name}' is used before its declaration"))
+                .span(Span::token(&tokens[off-1]))
+                .label(Span::token(decl), "declared here")
+                .help("global values are evaluated in order of declaration, move it above")
+                .code("used-before-declaration")
+            ))
+        }
+        bindings.gentle_error(&tokens[off-1], "Unknown keyword", "unknown-name");
+        return Ok(off)
+    };
     match x {
         Bind::Function(argt, typ, l) => {
             let (typ, argt) = (typ.clone(), argt.clone());
@@ -26,17 +39,28 @@ pub fn external_word<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>
             if let Some(l) = l {
                 bindings.push_function(l.clone());
             }
-            next_call(&mut off, tokens, instructions, bindings);
+            next_call(&mut off, tokens, instructions, bindings)?;
         }
         Bind::Let(typ, mutable) => {
-            instructions.push(Value::Get(&tokens[0].value, typ.clone()));
-            variable_look(&mut off, tokens, instructions, bindings, *mutable);
+            let (typ, mutable) = (typ.clone(), *mutable);
+            // assignment only writes to variable
+            if tokens.get(1).is_none_or(|x| x.typ != TokenKind::Assign) { bindings.use_local(&tokens[0].value); }
+            instructions.push(Value::Get(&tokens[0].value, typ));
+            variable_look(&mut off, tokens, instructions, bindings, mutable)?;
+        }
+        Bind::Const(x) => {
+            instructions.push(x.clone());
+            variable_look(&mut off, tokens, instructions, bindings, false)?;
+        }
+        Bind::Static(typ, mutable) => {
+            let (typ, mutable) = (typ.clone(), *mutable);
+            instructions.push(Value::LoadFromPtr(Box::new(Value::StaticPtr(name, typ.clone(), mutable)), typ));
+            variable_look(&mut off, tokens, instructions, bindings, mutable)?;
         }
-        // Bind::Value(x) => { instructions.push(x.clone()); }
         Bind::Namespace(_) => {
             if let Some(_) = next_token(&mut off, tokens, Some("::"), Some(TokenKind::Special)) {
                 let Some(b) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else {
-                    error(&tokens[off-1], bindings, "Invalid path")
+                    return Err(error(&tokens[off-1], bindings, "Invalid path", "unknown-name"))
                 };
                 name += "::";
                 name += &b.value;
@@ -48,20 +72,54 @@ pub fn external_word<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>
             name = n.into();
             continue
         }
+        Bind::Template(l) => {
+            let l = l.clone();
+            generic_call(&mut off, tokens, l, instructions, bindings)?;
+        }
         // Bind::Type(_) => todo!("types in code blocks"),
         Bind::Type(Some(l)) => {
-            let body = next_body(&mut off, tokens, bindings, ("{", "}"));
-            struct_init(l.clone(), body, instructions, bindings);
+            let l = l.clone();
+            if matches!(&*l.borrow(), Value::StructTemplate { .. }) {
+                // instance is named after its type arguments: `Pair<i32, u8> { .. }`
+                let Some((Type::Struct(x), j)) = parse_type(tokens, bindings)? else { unreachable!() };
+                name = x;
+                off = j;
+                continue
+            }
+            if next_token(&mut off, tokens, Some("::"), Some(TokenKind::Special)).is_some() {
+                // associated function, like `Point::new`, otherwise it's enum variant
+                if let Some(Token { value: member, .. }) = tokens.get(off) {
+                    let path = format!("{name}::{member}");
+                    if bindings.get(&path).is_some() {
+                        name = path;
+                        off += 1;
+                        continue
+                    }
+                }
+                enum_init(&mut off, tokens, l, instructions, bindings)?;
+            }
+            else {
+                let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+                struct_init(l, body, instructions, bindings)?;
+            }
         },
-        Bind::Type(None) => todo!("opaque types"),
+        Bind::Type(None) => bindings.gentle_error(&tokens[0], "Opaque type can't be used as value", "not-a-value"),
+        // `Self { .. }` or `Self::new()` inside `impl`
+        Bind::Generic(Type::Struct(x)) => {
+            name = x.clone();
+            continue
+        }
+        Bind::Generic(_) => bindings.gentle_error(&tokens[0], "Type parameter can't be used as value", "not-a-value"),
+        Bind::Trait(_) => bindings.gentle_error(&tokens[0], "Trait can't be used as value", "not-a-value"),
         // Bind::Public(x) => { return  }
-        Bind::Label => bindings.gentle_error(&tokens[0], "You should use label names for 'break' and 'continue'"),
-        Bind::Mark => panic!("Unexpected behaviour in front-end")
+        Bind::Label => bindings.gentle_error(&tokens[0], "You should use label names for 'break' and 'continue'", "not-a-value"),
+        // barrier between scopes of functions, it has no value
+        Bind::Mark => bindings.gentle_error(&tokens[0], "Unknown keyword", "unknown-name")
     }
     break
     }
 
-    return off
+    Ok(off)
 }
 
 // pub fn reserve_local<'a> (bindings: &mut Bindings<'a>) {
@@ -69,31 +127,38 @@ pub fn external_word<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>
 //     *x += 1;
 // }
 
-pub fn var<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn var<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
     let off_name = off;
     let mutable = next_mutable_flag(&mut off, tokens);
-    let name = &next_token(&mut off, tokens, None, Some(TokenKind::Word)).unwrap_or_else(|| error(&tokens[0], bindings, "Expected name of variable")).value;
-    let mut typ = next_type(&mut off, tokens, bindings).unwrap_or(Type::Guess);
+    let name_token = next_token(&mut off, tokens, None, Some(TokenKind::Word)).ok_or_else(|| error(&tokens[0], bindings, "Expected name of variable", "expected-name"))?;
+    let name = &name_token.value;
+    let mut typ = next_type(&mut off, tokens, bindings)?.unwrap_or(Type::Guess);
     // reserve_local(bindings);
     if let Some(_) = next_token(&mut off, tokens, Some("="), Some(TokenKind::Assign)) {
-        let val = nvalue!(&mut off, tokens, instructions, bindings, "Expected value of variable", &tokens[off-1], off);
+        let mut val = nvalue!(&mut off, tokens, instructions, bindings, "Expected value of variable", &tokens[off-1], off);
         
-        if Type::Guess == typ { typ = default_type(&val); }
-        else { typ.check_strict(&val, &tokens[off_name..off], bindings); }
-        assert_range(typ != Type::Void, &tokens[1..off], bindings, "Variable with void-type");
+        if Type::Guess == typ {
+            typ = default_type(&val);
+            check_literals(&typ, &val, &tokens[off_name..off], bindings);
+        }
+        else {
+            val = coerce(&typ, val, &tokens[off_name..off], bindings)?;
+            typ.check_strict(&val, &tokens[off_name..off], bindings);
+        }
+        assert_range(typ != Type::Void, &tokens[1..off], bindings, "Variable with void-type", "void-variable")?;
 
         let val = Box::new(val);
-        bindings.insert(name, Bind::Let(typ.clone(), mutable));
+        bindings.insert_local(name_token, typ.clone(), mutable);
         // dbg!(typ.alignment(bindings), typ.sizeof(bindings));
         instructions.push(Value::InitVar(&name, typ, Some(val)));
-        return off
+        return Ok(off)
     }
-    assert(typ != Type::Guess, &tokens[off_name], bindings, "Must provide type at this point");
-    assert_range(typ != Type::Void, &tokens[1..off], bindings, "Variable with void-type");
-    bindings.insert(name, Bind::Let(typ.clone(), mutable));
+    assert(typ != Type::Guess, &tokens[off_name], bindings, "Must provide type at this point", "type-annotations-needed")?;
+    assert_range(typ != Type::Void, &tokens[1..off], bindings, "Variable with void-type", "void-variable")?;
+    bindings.insert_local(name_token, typ.clone(), mutable);
     instructions.push(Value::InitVar(&name, typ, None));
-    return off
+    Ok(off)
 }
 
 fn assign<'a> (d: Value<'a>, value: Value<'a>, symbol: &'a str) -> Value<'a> {
@@ -109,58 +174,104 @@ fn assign<'a> (d: Value<'a>, value: Value<'a>, symbol: &'a str) -> Value<'a> {
         "*=" => expr!(Operation::Mul),
         "/=" => expr!(Operation::Div),
         "%=" => expr!(Operation::Rem),
-        _ => todo!()
+        "&=" => expr!(Operation::BitAnd),
+        "|=" => expr!(Operation::BitOr),
+        "^=" => expr!(Operation::Xor),
+        "<<=" => expr!(Operation::Shl),
+        ">>=" => expr!(Operation::Shr),
+        _ => unreachable!()
     }
 }
 
-pub fn modify<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> bool {
+pub fn modify<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<bool> {
     let s = *off;
     if let Some(Token { value: ass, .. }) = next_token(off, tokens, None, Some(TokenKind::Assign)) {
         let val = instructions.pop().unwrap();
-        dbg!(&val);
         if let Value::Get(name, typ) = val {
+            if let Some(Bind::Let(_, false)) = bindings.get(name) {
+                bindings.diagnose(
+                    Diagnostic::error(&format!("Cannot assign twice to immutable variable '{name}'"))
+                    .span(Span::token(&tokens[s]))
+                    .label(Span::range(&tokens[..s]), "this variable is immutable")
+                    .help(&format!("make it mutable: 'let mut {name}'"))
+                    .code("immutable-assign")
+                );
+            }
             let v = assign(Value::Get(name, typ.clone()), nvalue!(off, tokens, instructions, bindings, "Expected value", false), ass);
+            let v = coerce(&typ, v, &tokens[..*off], bindings)?;
             typ.check_strict(&v, &tokens[..*off], bindings);
             instructions.push(Value::ModifyVar(name, typ, Box::new(v)));
-            return true
+            return Ok(true)
         }
         else if let Value::LoadFromPtr(x, typ) = val {
             let v = assign(Value::LoadFromPtr(x.clone(), typ.clone()), nvalue!(off, tokens, instructions, bindings, "Expected value", false), ass);
+            let v = coerce(&typ, v, &tokens[..*off], bindings)?;
             typ.check_strict(&v, &tokens[..*off], bindings);
             match *x {
-                Value::Get(_, Type::Ptr(_, true)) | Value::LoadAddress(_, _, Type::Ptr(_, true), _) | Value::LoadAddress(_, _, _, true) => {
+                Value::StaticPtr(name, _, false) => {
+                    bindings.diagnose(
+                        Diagnostic::error(&format!("Cannot assign to immutable static '{name}'"))
+                        .span(Span::token(&tokens[s]))
+                        .help(&format!("make it mutable: 'static mut {name}'"))
+                        .code("immutable-assign")
+                    );
+                    return Ok(true)
+                }
+                Value::Get(_, Type::Ptr(_, true)) | Value::LoadAddress(_, _, Type::Ptr(_, true), _) | Value::LoadAddress(_, _, _, true) | Value::StaticPtr(_, _, true) => {
                     instructions.push(Value::ModifyByPointer(Box::new((*x, typ, v))));
-                    return true
+                    return Ok(true)
+                }
+                Value::LoadAddress(ref data, _, _, false) if matches!(**data, Value::SliceData(_)) => {
+                    let Value::SliceData(slice) = &**data else { unreachable!() };
+                    let slice_typ = extract_type(slice).unwrap_or(Type::Slice(Box::new(typ), false));
+                    let d = Diagnostic::error(&format!("Cannot assign to element of immutable slice '{}'", slice_typ.display()))
+                        .span(Span::token(&tokens[s]))
+                        .label(Span::range(&tokens[..s]), "element is read-only");
+                    bindings.diagnose(match **slice {
+                        Value::String(_) => d.note("string literals are read-only"),
+                        _ => d.help("take mutable slice: '&mut'")
+                    }.code("immutable-assign"));
+                    return Ok(true)
                 }
                 _ => {}
             }
         }
-        bindings.gentle_error(&tokens[s], ASSIGN_NOT_EXPECTED);
+        bindings.gentle_error(&tokens[s], ASSIGN_NOT_EXPECTED, "unexpected-assign");
     }
-    false
+    Ok(false)
 }
 
-pub fn load_address<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> usize {
+pub fn load_address<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<usize> {
     let mut from = instructions.pop().unwrap();
     let mut off = 0;
-    let body = next_body(&mut off, tokens, bindings, ("[", "]"));
-    let Some(index) = next_value(&mut 0, body, instructions, bindings) else { bindings.gentle_error_range(&tokens[0..off], "Expected offset"); return off };
+    let body = next_body(&mut off, tokens, bindings, ("[", "]"))?;
+    if let Some(dots) = range_dots(body) {
+        let slice = subslice(from, &tokens[..off], body, dots, instructions, bindings, mutable)?;
+        instructions.push(slice);
+        return Ok(off)
+    }
+    let Some(index) = next_value(&mut 0, body, instructions, bindings)? else { bindings.gentle_error_range(&tokens[0..off], "Expected offset", "expected-value"); return Ok(off) };
     Type::U(bindings.target_ptr_bits() as u32).check_strict(&index, body, bindings);
-    let typ = extract_type(&from).unwrap_or_else(|| error(&tokens[0], bindings, "Unindexable value"));
+    let typ = extract_type(&from).ok_or_else(|| error(&tokens[0], bindings, "Unindexable value", "invalid-access"))?;
     let mut u = 0;
     match typ {
         Type::Array(_, _) => {}
+        Type::Slice(el, mutable) => {
+            let elements = Value::LoadAddress(Box::new(Value::SliceData(Box::new(from))), Box::new(index), elements_array(el.clone()), mutable);
+            instructions.push(Value::LoadFromPtr(Box::new(elements), *el));
+            return Ok(off)
+        }
         Type::Ptr(Some(to), mutable) => {
             instructions.push(Value::LoadFromPtr(Box::new(from), *to.clone()));
             return load_address(tokens, instructions, bindings, mutable)
         }
         Type::Tuple(_) => {
             if let Value::Int(x) = index {
-                u = x
+                u = x as usize
             }
-            else { bindings.gentle_error_range(&body, "To load address of structure's field, you need to provide clear number"); return off }
+            else { bindings.gentle_error_range(&body, "To load address of structure's field, you need to provide clear number", "non-constant-index"); return Ok(off) }
         },
-        _ => error(&tokens[0], bindings, "Unindexable value")
+        _ => return Err(error(&tokens[0], bindings, "Unindexable value", "invalid-access"))
         // _ => { bindings.gentle_error(&tokens[0], "Unindexable value"); return off },
     }
     let subtyp = penetrate_type(extract_type(&from).unwrap(), u);
@@ -169,19 +280,22 @@ pub fn load_address<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>,
     instructions.push(Value::LoadFromPtr(Box::new(Value::LoadAddress(Box::new(from), Box::new(index), typ, mutable)), subtyp));
     // dbg!(instructions.last());
 
-    off
+    Ok(off)
 }
 
-pub fn access_type_member<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> usize {
+pub fn access_type_member<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<usize> {
     let mut off = 1;
     let from = instructions.pop().unwrap();
     // dbg!(&from);
     // todo
-    let typ = extract_type(&from).unwrap_or_else(|| error(&tokens[0], bindings, "No valid value for this operation"));
-    let Some(Token { value: field, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { error(&tokens[0], bindings, "Don't you think that this dot here kinda passive-aggressive?") };
+    let typ = extract_type(&from).ok_or_else(|| error(&tokens[0], bindings, "No valid value for this operation", "invalid-access"))?;
+    let Some(Token { value: field, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[0], bindings, "Don't you think that this dot here kinda passive-aggressive?", "syntax")) };
 
-    // TODO UNIFORM FUNCTION CALL SYNTAX SOMEHOW
     if let Some(_) = next_body_optional(&mut 0, &tokens[off..], ("(", ")")) {
+        if let Type::Dyn(_, _) = typ {
+            dyn_method(&mut off, from, tokens, instructions, bindings)?;
+            return Ok(off)
+        }
         // dbg!("bebra");
         if let Type::Struct(name) = pure_type(&typ) {
             let name = format!("{name}::{field}");
@@ -190,63 +304,75 @@ pub fn access_type_member<'a> (tokens: &'a [Token], instructions: &mut Vec<Value
                 instructions.push(Value::FunctionPointer(name, ret.clone(), args.clone()));
                 // TODO ORGANIZE
                 if let Some(l) = l { bindings.push_function(l.clone()); }
-                next_uniform_call(&mut off, from, tokens, instructions, bindings);
-                return off
+                next_uniform_call(&mut off, from, tokens, instructions, bindings)?;
+                return Ok(off)
                 // next_call(&mut off, tokens, instructions, bindings);
             }
         }
+        if let Some(name) = find_method(pure_type(&typ), field, &tokens[off-1], bindings)? {
+            let Some(Bind::Function(args, ret, l)) = bindings.get(&name) else { unreachable!() };
+            instructions.push(Value::FunctionPointer(name, ret.clone(), args.clone()));
+            if let Some(l) = l { bindings.push_function(l.clone()); }
+            next_uniform_call(&mut off, from, tokens, instructions, bindings)?;
+            return Ok(off)
+        }
         if let Some(Bind::Function(args, ret, l)) = bindings.get(field) {
             instructions.push(Value::FunctionPointer(field.clone(), ret.clone(), args.clone()));
             // TODO ORGANIZE
             if let Some(l) = l { bindings.push_function(l.clone()); }
-            next_uniform_call(&mut off, from, tokens, instructions, bindings);
-            return off
+            next_uniform_call(&mut off, from, tokens, instructions, bindings)?;
+            return Ok(off)
             // next_call(&mut off, tokens, instructions, bindings);
         }
     }
     match &typ {
         Type::Struct(name) => {
-            if let Some(Bind::Type(Some(l))) = bindings.get(&name) {
-                let Value::Struct { kv, .. } = &*l.borrow() else { panic!() };
-                let Some((subtyp, index)) = kv.get_w_p(field) else { error(&tokens[off-1], bindings, &format!("There is no '{field}' in type '{}'", typ.display())) };
+            if let Some(l) = bindings.get_type(name) {
+                let Value::Struct { kv, .. } = &*l.borrow() else {
+                    return Err(error(&tokens[off-1], bindings, &format!("There is no '{field}' in type '{}'", typ.display()), "unknown-field"))
+                };
+                let Some((subtyp, index)) = kv.get_w_p(field) else { return Err(error(&tokens[off-1], bindings, &format!("There is no '{field}' in type '{}'", typ.display()), "unknown-field")) };
                 // dbg!(mutable);
-                instructions.push(Value::LoadFromPtr(Box::new(Value::LoadAddress(Box::new(from), Box::new(Value::Int(index)), typ, mutable)), subtyp.clone()));
+                instructions.push(Value::LoadFromPtr(Box::new(Value::LoadAddress(Box::new(from), Box::new(Value::Int(index as u128)), typ, mutable)), subtyp.clone()));
             }
-            else { error(&tokens[0], bindings, "Unindexable value") }
+            else { return Err(error(&tokens[0], bindings, "Unindexable value", "invalid-access")) }
         }
         Type::Ptr(Some(to), mutable) => {
             instructions.push(Value::LoadFromPtr(Box::new(from), *to.clone()));
             return access_type_member(tokens, instructions, bindings, *mutable)
         }
-        _ => error(&tokens[0], bindings, "Unindexable value")
+        Type::Slice(_, _) if field == "len" => instructions.push(Value::SliceLen(Box::new(from), Type::U(bindings.target_ptr_bits() as u32))),
+        Type::Slice(_, _) => return Err(error(&tokens[off-1], bindings, &format!("There is no '{field}' in type '{}', only 'len'", typ.display()), "unknown-field")),
+        _ => return Err(error(&tokens[0], bindings, "Unindexable value", "invalid-access"))
     }
-    off
+    Ok(off)
 }
 
-pub fn deref<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn deref<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
-    assert(Type::Ptr(None, false).check(instructions.last().unwrap()).is_some(), &tokens[0], bindings, "Expected pointer to dereference");
+    assert(Type::Ptr(None, false).check(instructions.last().unwrap()).is_some(), &tokens[0], bindings, "Expected pointer to dereference", "mismatched-types")?;
     let val = instructions.pop().unwrap();
     
     let typ = if let Some(x) = extract_type(&val) { penetrate_type(x, 0) }
-    else { error_range(&tokens[..off], bindings, "Cannot get value behind address") };
+    else { return Err(error_range(&tokens[..off], bindings, "Cannot get value behind address", "invalid-access")) };
+    if let Type::Opaque(x) = &typ { return Err(error_range(&tokens[..off], bindings, &format!("Cannot get value of opaque type '{x}' behind address"), "invalid-access")) }
     
     instructions.push(Value::LoadFromPtr(Box::new(val), typ));
-    modify(&mut off, tokens, instructions, bindings);
+    modify(&mut off, tokens, instructions, bindings)?;
     
-    off
+    Ok(off)
 }
 
 
-pub fn namespace<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn namespace<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
 
-    let Some(Token { value: name, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { error(&tokens[0], bindings, "Namespace must have name, duh") };
-    let body = next_body(&mut off, tokens, bindings, ("{", "}"));
+    let Some(Token { value: name, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[0], bindings, "Namespace must have name, duh", "expected-name")) };
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
 
     bindings.join_scope();
     bindings.push_global_prefix(name.to_string());
-    parse_np(body, instructions, bindings);
+    parse_np(body, instructions, bindings)?;
     // let scope = bindings.pop_scope().into_keys();
     // let namespace= Bind::Namespace(scope.filter(|x| {
     //     bindings.get(&bindings.global_name(x)).is_some()
@@ -256,7 +382,66 @@ pub fn namespace<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bi
     bindings.pop_global_prefix();
     bindings.global_insert(name, namespace);
 
-    off
+    Ok(off)
+}
+
+/// Parses another source file into namespace named after it (or after name given by `as`): `import "path/file.alt"`.
+/// Path is relative to directory of file with this instruction
+pub fn import<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 1;
+    if bindings.get_context_noval(&Context::Fn(Type::Void)).is_some() {
+        return Err(error(&tokens[0], bindings, "'import' is not allowed in functions", "misplaced-item"))
+    }
+    let Some(path_token) = next_token(&mut off, tokens, None, Some(TokenKind::String)) else { return Err(error(&tokens[0], bindings, "'import' requires path (string) next to it", "syntax")) };
+    let path = bindings.get_file_path(path_token.file).parent().unwrap_or(Path::new("")).join(&path_token.value[1..path_token.value.len()-1]);
+
+    let name = if next_token(&mut off, tokens, Some("as"), Some(TokenKind::Word)).is_some() {
+        next_token(&mut off, tokens, None, Some(TokenKind::Word)).ok_or_else(|| error(&tokens[off-1], bindings, "Expected name of namespace", "expected-name"))?.value.clone()
+    }
+    else {
+        let stem = path.file_stem().and_then(|x| x.to_str()).unwrap_or_default();
+        if stem.is_empty() || stem.starts_with(|x: char| x.is_ascii_digit()) || !stem.chars().all(|x| x.is_alphanumeric() || x == '_') {
+            return Err(bindings.fatal(
+                Diagnostic::error(&format!("File name '{stem}' can't be used as namespace name"))
+                .span(Span::token(path_token))
+                .help("give it another name: 'import \"...\" as name'")
+                .code("invalid-namespace-name")
+            ))
+        }
+        stem.to_string()
+    };
+
+    let canonical = fs::canonicalize(&path).map_err(|e| error(path_token, bindings, &format!("Cannot import '{}': {e}", path.display()), "import-failed"))?;
+    if let Some(file) = bindings.find_file(&canonical) {
+        let importing = bindings.get_importing();
+        if let Some(at) = importing.iter().position(|x| *x == file) {
+            let chain = importing[at..].iter().chain([&file]).map(|x| bindings.get_file_path(*x).display().to_string()).collect::<Vec<_>>().join(" -> ");
+            return Err(bindings.fatal(
+                Diagnostic::error("Import cycle detected")
+                .span(Span::token(path_token))
+                .note(&format!("cycle: {chain}"))
+                .code("import-cycle")
+            ))
+        }
+        // already imported somewhere else, so just refer to it (global names are visible from anywhere)
+        if let Some(namespace) = bindings.get_file_namespace(file).filter(|x| **x != name).cloned() { bindings.insert(&name, Bind::Alias(namespace)); }
+        return Ok(off)
+    }
+
+    let code = fs::read_to_string(&path).map_err(|e| error(path_token, bindings, &format!("Cannot import '{}': {e}", path.display()), "import-failed"))?;
+    let (file, file_tokens) = bindings.add_file(path, canonical, code);
+
+    bindings.enter_file(file);
+    bindings.join_scope();
+    bindings.push_global_prefix(name.clone());
+    parse_np(file_tokens, instructions, bindings)?;
+    let scope = bindings.pop_scope();
+    bindings.pop_global_prefix();
+    bindings.leave_file();
+    bindings.set_file_namespace(file, bindings.global_name(&name));
+    bindings.global_insert(&name, Bind::Namespace(scope));
+
+    Ok(off)
 }
 
 fn resolve_alias (mut n: String, bindings: &Bindings) -> String {
@@ -267,22 +452,22 @@ fn resolve_alias (mut n: String, bindings: &Bindings) -> String {
 }
 
 #[derive(Debug)]
-enum NSPath { Group(Vec<Vec<NSPath>>), Fragment(String), All }
+enum NSPath<'a> { Group(Vec<Vec<NSPath<'a>>>), Fragment(&'a Token), All(&'a Token) }
 
-fn nspath<'a> (off: &mut usize, tokens: &'a [Token], bindings: &Bindings<'a>) -> Option<Vec<NSPath>> {
-    let mut path = vec![NSPath::Fragment(next_token(off, tokens, None, Some(TokenKind::Word))?.value.to_string())];
+fn nspath<'a> (off: &mut usize, tokens: &'a [Token]) -> Option<Vec<NSPath<'a>>> {
+    let mut path = vec![NSPath::Fragment(next_token(off, tokens, None, Some(TokenKind::Word))?)];
     while let Some(_) = next_token(off, tokens, Some("::"), Some(TokenKind::Special)) {
         if let Some(sub) = next_token(off, tokens, None, Some(TokenKind::Word)) {
-            path.push(NSPath::Fragment(sub.value.to_string()));
+            path.push(NSPath::Fragment(sub));
         }
-        else if let Some(_) = next_token(off, tokens, Some("*"), Some(TokenKind::Arithmetic)) {
-            path.push(NSPath::All);
+        else if let Some(all) = next_token(off, tokens, Some("*"), Some(TokenKind::Arithmetic)) {
+            path.push(NSPath::All(all));
             break
         }
         else if let Some(body) = next_body_optional(off, tokens, ("{", "}")) {
             let mut soff = 0;
             let mut v = vec![];
-            while let Some(b) = nspath(&mut soff, body, bindings) {
+            while let Some(b) = nspath(&mut soff, body) {
                 v.push(b);
                 ignore_separator(&mut soff, body);
             }
@@ -293,56 +478,70 @@ fn nspath<'a> (off: &mut usize, tokens: &'a [Token], bindings: &Bindings<'a>) ->
     Some(path)
 }
 
-fn sign_path (bindings: &mut Bindings, mut path: Vec<NSPath>, mut root: String) {
+/// `written` is path as it's written in code, for diagnostics
+fn sign_path<'a> (bindings: &mut Bindings<'a>, mut path: Vec<NSPath<'a>>, mut root: String, mut written: String) -> PResult<()> {
     let access = path.pop().unwrap();
     let mut path = path.into_iter();
-    while let Some(NSPath::Fragment(x)) = path.next() {
+    while let Some(NSPath::Fragment(Token { value: x, .. })) = path.next() {
         // may be problem in future. there might be problems with public "use"
-        if root.is_empty() { root += &resolve_alias(x, bindings) }
-        else { root += &x; }
+        if root.is_empty() { root += &resolve_alias(x.clone(), bindings) }
+        else { root += x; }
         root += "::";
+        written += x;
+        written += "::";
     }
     match access {
-        NSPath::Fragment(x) => {
+        NSPath::Fragment(token) => {
+            let x = &token.value;
             // dbg!(&x, format!("{root}{x}"));
-            bindings.insert(&x, Bind::Alias(format!("{root}{x}")));
+            bindings.insert(x, Bind::Alias(format!("{root}{x}")));
+            bindings.import(vec![x.clone()], format!("{written}{x}"), token);
         },
         NSPath::Group(g) => {
             for i in g {
-                sign_path(bindings, i, root.clone());
+                sign_path(bindings, i, root.clone(), written.clone())?;
             }
         }
-        NSPath::All => {
-            let Some(Bind::Namespace(n)) = bindings.get(&root[..root.len()-2]) else { todo!() };
+        NSPath::All(token) => {
+            let Some(Bind::Namespace(n)) = bindings.get(&root[..root.len()-2]) else {
+                return Err(error(token, bindings, &format!("'{}' is not a namespace", &written[..written.len()-2]), "unknown-name"))
+            };
             // let n = n.keys().cloned().filter(|x| bindings.get(&bindings.global_name(x)).is_some()).collect::<Vec<String>>();
             // dbg!(&root);
             let n = n.keys().cloned().filter(|x| bindings.get(&format!("{root}{x}")).is_some()).collect::<Vec<String>>();
-            for i in n {
-                bindings.insert(&i, Bind::Alias(format!("{root}{i}")));
+            for i in &n {
+                bindings.insert(i, Bind::Alias(format!("{root}{i}")));
             }
+            bindings.import(n, format!("{written}*"), token);
         }
     }
+    Ok(())
 }
 
 /// WARN: for now, this is implemented for namespaces only. Need to think about structures, especially cases with generics involved
-pub fn r#use<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> usize {
+pub fn r#use<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
 
-    let Some(path) = nspath(&mut off, tokens, bindings) else { todo!() };
-    sign_path(bindings, path, String::new());
+    let Some(path) = nspath(&mut off, tokens) else { return Err(error(&tokens[0], bindings, "Expected path next to 'use'", "syntax")) };
+    sign_path(bindings, path, String::new(), String::new())?;
 
-    off
+    Ok(off)
 }
 
 
-pub fn r#pub<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn r#pub<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     if let Some(Token { typ: TokenKind::Word, value, .. }) = tokens.get(1) {
         match value.as_str() {
-            "fn" => return r#fn(&tokens[1..], instructions, bindings, true) + 1,
+            "fn" => return Ok(r#fn(&tokens[1..], instructions, bindings, true)? + 1),
+            "struct" => return Ok(r#struct(&tokens[1..], instructions, bindings, true)? + 1),
+            "enum" => return Ok(r#enum(&tokens[1..], instructions, bindings, true)? + 1),
+            "trait" => return Ok(r#trait(&tokens[1..], bindings, true)? + 1),
+            "const" => return Ok(r#const(&tokens[1..], instructions, bindings, true)? + 1),
+            "static" => return Ok(r#static(&tokens[1..], instructions, bindings, true)? + 1),
             _ => {}
         }
     }
-    error(&tokens[0], bindings, "Expected valid instruction after 'pub' (e.g. function declaration)");
+    Err(error(&tokens[0], bindings, "Expected valid instruction after 'pub' (e.g. function or type declaration)", "syntax"))
 }
 
 // somehow fix (idk i fixed it or not, i guess i am because it works fine, but comment is not deleted soooo)
@@ -370,20 +569,30 @@ pub fn join_by_path<'a, T> (name: &str, bindings: &mut Bindings<'a>, f: impl FnO
     else { f(bindings) }
 }
 
-pub fn r#impl<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn r#impl<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
     let name = next_name(&mut off, tokens);
+    if name.is_empty() { return Err(error(token_at(tokens, off), bindings, "Expected type to implement methods for", "expected-type")) }
+    if next_token(&mut off, tokens, Some("for"), Some(TokenKind::Word)).is_some() {
+        impl_trait(&mut off, tokens, &name, instructions, bindings)?;
+        return Ok(off)
+    }
+    let Some((typ, _)) = parse_type(&tokens[1..off], bindings)? else { return Err(error_range(&tokens[1..off], bindings, &format!("'{name}' is not a type"), "unknown-name")) };
     // dbg!(&name);
-    let body = next_body(&mut off, tokens, bindings, ("{", "}"));
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
 
     bindings.join_scope();
+    bindings.insert("Self", Bind::Generic(typ));
     bindings.push_global_prefix(name);
-    parse_inplace(body, instructions, bindings);
+    parse_inplace(body, instructions, bindings)?;
     bindings.pop_global_prefix();
-    bindings.leave_scope();
+    // methods are reachable by path, like `Point::new`, wherever type is
+    for (name, bind) in bindings.pop_scope() {
+        if let Bind::Function(_, _, _) | Bind::Template(_) = bind { bindings.insert(&name, bind); }
+    }
     // dbg!(instructions);
 
     // todo!();
 
-    off
+    Ok(off)
 }
\ No newline at end of file
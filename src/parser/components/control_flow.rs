@@ -1,4 +1,4 @@
-use crate::{lexer::{Token, TokenKind}, nvalue, parser::{bindings::{Bind, Bindings, Context}, message::error, parse, simpler::{next_body, next_token, next_value}, r#type::Type, Value}};
+use crate::{lexer::{Token, TokenKind}, nvalue, parser::{bindings::{Bind, Bindings, Context}, Operation, parse_inplace, components::{pattern::{check_arms, next_pattern, Arm}, slices::elements_array, traits::coerce}, lint::Lint, message::{error, error_range, Diagnostic, PResult, Span}, parse, value_lookaround, simpler::{ignore_separator, next_body, next_body_optional, next_token, next_value}, r#type::{default_type, extract_type, extract_types, Type}, Value}};
 
 pub fn get_return (body: &Vec<Value>) -> bool {
     if let Some(Value::Return(_)) | Some(Value::ReturnMark) | Some(Value::Unreachable) = body.last() {
@@ -9,13 +9,28 @@ pub fn get_return (body: &Vec<Value>) -> bool {
     }
 }
 
-pub fn r#if<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+/// Warns about tokens left in body from `at`, after instruction (pointed by `label`) that never passes control further
+pub fn unreachable_after (tokens: &[Token], at: usize, label: Span, bindings: &Bindings) {
+    let Some(next) = tokens.get(at) else { return };
+    bindings.lint(Lint::UnreachableCode,
+        Diagnostic::warning("Unreachable code")
+        .span(Span::token(next))
+        .label(label, "any code following this is unreachable")
+    );
+}
+
+pub fn r#if<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    if_chain(tokens, instructions, bindings, false)
+}
+
+/// `chained` is set for `if` after `else`, so unreachable code is reported only once, by the first `if`
+fn if_chain<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, chained: bool) -> PResult<usize> {
     let mut off = 1;
     
     let condition = Box::new(nvalue!(&mut off, tokens, instructions, bindings, "Expected expression"));
     Type::Bool.check_strict(&condition, &tokens[1..off], bindings);
     
-    let body = next_body(&mut off, tokens, bindings, ("{", "}"));
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
     let mut else_then = Box::new(None);
 
     let mut it_returns = false;
@@ -27,98 +42,576 @@ pub fn r#if<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, binding
     //     else_then = Box::new(instructions.pop());
     // }
     // }
-    if let Some(_) = next_token(&mut off, tokens, Some("else"), Some(TokenKind::Word)) {
-        let (ej, eret) = r#else(&tokens[off-1..], instructions, bindings);
-        off += ej;
+    if next_token(&mut off, tokens, Some("else"), Some(TokenKind::Word)).is_some() {
+        let (ej, eret) = r#else(&tokens[off-1..], instructions, bindings)?;
+        // `ej` counts 'else' itself
+        off += ej - 1;
         it_returns = eret;
         else_then = Box::new(instructions.pop());
     }
 
     bindings.context_scope(Context::If, |bindings| {
-        let body = parse(body, bindings);
+        let body = parse(body, bindings)?;
         it_returns = it_returns && get_return(&body);
         instructions.push(Value::If { condition, body, else_then });
-    });
+        Ok(())
+    })?;
     
-    if it_returns { instructions.push(Value::ReturnMark); return tokens.len() }
-    return off
+    if it_returns {
+        if !chained { unreachable_after(tokens, off, Span::token(&tokens[0]), bindings); }
+        instructions.push(Value::ReturnMark);
+        return Ok(tokens.len())
+    }
+    // branches of chain are in the first `if`
+    if !chained { valued(instructions); }
+    return Ok(off)
 }
 
-pub fn r#else<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> (usize, bool) {
+pub fn r#else<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<(usize, bool)> {
     let mut off = 1;
     if let Some(_) = next_token(&mut off, tokens, Some("if"), Some(TokenKind::Word)) {
-        off += r#if(&tokens[1..], instructions, bindings);
+        // chain counts 'if' itself
+        off = 1 + if_chain(&tokens[1..], instructions, bindings, true)?;
         let last = instructions.last();
         if let Some(Value::If { .. }) = last {
-            return (off, false)
+            return Ok((off, false))
         }
         else if let Some(Value::ReturnMark) = last {
             instructions.pop();
-            return (off, true)
+            return Ok((off, true))
         }
         else { unreachable!() }
     }
     else {
-        let body = next_body(&mut off, tokens, bindings, ("{", "}"));
+        let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
         bindings.join_scope();
-        let body = parse(body, bindings);
+        let body = parse(body, bindings)?;
         let r = (off, get_return(&body));
         instructions.push(Value::Else(body));
         bindings.leave_scope();
-        r
+        Ok(r)
+    }
+}
+
+/// `{ .. }` in its own scope, it results in value it ends with
+pub fn block<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 0;
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+    bindings.join_scope();
+    let body = parse(body, bindings)?;
+    bindings.leave_scope();
+    let it_returns = get_return(&body);
+    instructions.push(Value::Block(body));
+    if it_returns {
+        unreachable_after(tokens, off, Span::token(&tokens[0]), bindings);
+        instructions.push(Value::ReturnMark);
+        return Ok(tokens.len())
+    }
+    valued(instructions);
+    Ok(off)
+}
+
+/// Value that body ends with, if it ends with one
+fn tail<'a, 'b> (body: &'b [Value<'a>]) -> Option<&'b Value<'a>> {
+    let x = body.last()?;
+    match x {
+        Value::InitVar(..) | Value::ModifyVar(..) | Value::ModifyByPointer(..) => None,
+        Value::Int(_) | Value::SInt(_) | Value::Float(_) | Value::Bool(_) | Value::Expr(_) | Value::Unary(_) | Value::Array(_) | Value::Tuple(_) => Some(x),
+        x => extract_type(x).filter(|t| *t != Type::Void).map(|_| x)
+    }
+}
+
+/// Value that branch results in, nothing if it never ends
+fn branch<'a, 'b> (body: &'b Vec<Value<'a>>) -> Option<Vec<&'b Value<'a>>> {
+    if get_return(body) || matches!(body.last(), Some(Value::Break(..) | Value::Continue(_))) { return Some(vec![]) }
+    tail(body).map(|x| vec![x])
+}
+
+/// Values that `if` with all of its branches, `match` with all of its arms or block may result in, none if some branch doesn't end with value
+fn results<'a, 'b> (x: &'b Value<'a>) -> Option<Vec<&'b Value<'a>>> {
+    match x {
+        Value::If { body, else_then, .. } => {
+            let mut results_of = branch(body)?;
+            results_of.extend(results((**else_then).as_ref()?)?);
+            Some(results_of)
+        }
+        Value::Match { arms, .. } => {
+            let mut results_of = vec![];
+            for x in arms { results_of.extend(branch(&x.body)?); }
+            Some(results_of)
+        }
+        Value::Else(body) | Value::Block(body) => branch(body),
+        _ => None
     }
 }
 
-pub fn r#loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+/// Wraps `if`, `match` or block on top of `instructions` in `Value::Valued`, when every branch of it ends with value of the same type
+fn valued (instructions: &mut Vec<Value>) {
+    let Some(results) = instructions.last().and_then(results) else { return };
+    if results.is_empty() { return }
+    // literals are checked against type of the rest once it's known where value goes
+    let Some(typ) = extract_types(&results) else { return };
+    let x = instructions.pop().unwrap();
+    instructions.push(Value::Valued(Box::new(x), typ));
+}
+
+/// Values of `break` in `body` that leave loop with `label`, or the innermost loop around when `depth` is 0
+fn breaks<'a, 'b> (body: &'b [Value<'a>], label: Option<&str>, depth: usize, found: &mut Vec<&'b Value<'a>>) {
+    for x in body {
+        match x {
+            Value::Break(l, Some(v)) if (l.is_none() && depth == 0) || (l.is_some() && *l == label) => found.push(v),
+            Value::Loop(body, _) => breaks(body, label, depth + 1, found),
+            Value::If { body, else_then, .. } => {
+                breaks(body, label, depth, found);
+                if let Some(x) = &**else_then { breaks(std::slice::from_ref(x), label, depth, found); }
+            }
+            Value::Else(body) | Value::Block(body) => breaks(body, label, depth, found),
+            Value::Valued(x, _) | Value::InitVar(_, _, Some(x)) | Value::ModifyVar(_, _, x) => breaks(std::slice::from_ref(x), label, depth, found),
+            Value::Match { arms, .. } => arms.iter().for_each(|x| breaks(&x.body, label, depth, found)),
+            _ => {}
+        }
+    }
+}
+
+/// Values that `if`, `match`, block or `loop` wrapped in `Value::Valued` may result in
+pub fn valued_results<'a, 'b> (x: &'b Value<'a>) -> Option<Vec<&'b Value<'a>>> {
+    match x {
+        Value::Loop(body, label) => {
+            let mut found = vec![];
+            breaks(body, *label, 0, &mut found);
+            Some(found)
+        }
+        x => results(x)
+    }
+}
+
+/// `if`, `match`, block or `loop` used as value takes type that is expected from it, when it results only in literals of that type
+pub fn retype<'a> (typ: &Type, value: Value<'a>) -> Value<'a> {
+    let Value::Valued(x, t) = value else { return value };
+    let literals = valued_results(&x).is_some_and(|x| x.iter().all(|x| extract_type(x).is_none() && typ.check(x).is_some()));
+    Value::Valued(x, if literals { typ.clone() } else { t })
+}
+
+/// Distinct types of `results`, quoted and joined for message
+fn result_types (results: &[&Value]) -> String {
+    let mut types: Vec<String> = vec![];
+    for t in results.iter().map(|x| format!("'{}'", extract_type(x).unwrap_or_else(|| default_type(x)).display())) {
+        if !types.contains(&t) { types.push(t); }
+    }
+    types.join(", ")
+}
+
+/// Reports `if`, `match`, block or loop used as value, when it doesn't result in one
+pub fn expect_value (x: &Value, tokens: &[Token], bindings: &Bindings) -> PResult<()> {
+    let mut code = "no-value";
+    let message = match x {
+        Value::If { else_then, .. } if else_then.is_none() => String::from("'if' without 'else' has no value"),
+        Value::If { .. } => match results(x) {
+            Some(results) => {
+                code = "mismatched-types";
+                format!("Branches of 'if' end with values of different types: {}", result_types(&results))
+            }
+            None => String::from("'if' has no value, as not all of its branches end with one")
+        },
+        Value::Match { .. } => match results(x) {
+            Some(results) => {
+                code = "mismatched-types";
+                format!("Arms of 'match' end with values of different types: {}", result_types(&results))
+            }
+            None => String::from("'match' has no value, as not all of its arms end with one")
+        },
+        Value::Block(_) => String::from("Block has no value, as it doesn't end with one"),
+        Value::Loop(..) => String::from("Loop has no value, as it isn't left by 'break' with value"),
+        _ => return Ok(())
+    };
+    Err(bindings.fatal(Diagnostic::error(&message).span(Span::range(tokens)).code(code)))
+}
+
+/// Position of first `value` in `tokens` from `at`, that is not nested in brackets
+fn find_outside_brackets (tokens: &[Token], at: usize, value: &str) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(at) {
+        match token.value.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            x if x == value && depth == 0 => return i,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+pub fn r#match<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
-    let body = next_body(&mut off, tokens, bindings, ("{", "}"));
-    let label = if let Some(_) = next_token(&mut off, tokens, None, Some(TokenKind::LabelSymbol)) {
-        let Some(Token { value: label, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { error(&tokens[off], bindings, "Expected name of label") };
-        bindings.insert(label, Bind::Label);
-        Some(label.as_str())
-    } else { None };
+
+    let value = Box::new(nvalue!(&mut off, tokens, instructions, bindings, "Expected value to match"));
+    let typ = extract_type(&value).unwrap_or_else(|| default_type(&value));
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+
+    let mut arms = vec![];
+    let mut patterns = vec![];
+    let mut boff = 0;
+    while boff < body.len() {
+        let start = boff;
+        let mut binds = vec![];
+        let errors = bindings.error_count();
+        let pattern = next_pattern(&mut boff, body, &typ, &mut binds, bindings)?;
+        // invalid pattern is replaced by wildcard, it must not affect checks of other arms
+        let valid = errors == bindings.error_count();
+        let pattern_tokens = &body[start..boff];
+
+        bindings.join_scope();
+        for (token, typ) in binds { bindings.insert_local(token, typ, false); }
+        let guard = if next_token(&mut boff, body, Some("if"), Some(TokenKind::Word)).is_some() {
+            let end = find_outside_brackets(body, boff, "=>");
+            let mut goff = boff;
+            let guard = nvalue!(&mut goff, &body[..end], instructions, bindings, "Expected condition after 'if'");
+            Type::Bool.check_strict(&guard, &body[boff..end], bindings);
+            boff = end;
+            Some(guard)
+        } else { None };
+        if next_token(&mut boff, body, Some("=>"), Some(TokenKind::Special)).is_none() {
+            return Err(error(&body[boff.min(body.len()-1)], bindings, "Expected '=>' after pattern", "syntax"))
+        }
+        // arm is either block or single instruction up to ','
+        let arm_body = next_body_optional(&mut boff, body, ("{", "}")).unwrap_or_else(|| {
+            let end = find_outside_brackets(body, boff, ",");
+            let x = &body[boff..end];
+            boff = end;
+            x
+        });
+        let arm_body = parse(arm_body, bindings)?;
+        bindings.leave_scope();
+        ignore_separator(&mut boff, body);
+
+        if valid { patterns.push((pattern.clone(), guard.is_some(), arms.len())); }
+        arms.push((Arm { pattern, guard, body: arm_body }, pattern_tokens));
+    }
+
+    let (unreachable, missing) = check_arms(&patterns.iter().map(|(p, g, _)| (p.clone(), *g)).collect::<Vec<_>>(), &typ, bindings)?;
+    for i in unreachable.into_iter().map(|i| patterns[i].2) {
+        bindings.lint(Lint::UnreachableCode,
+            Diagnostic::warning("Unreachable pattern")
+            .span(Span::range(arms[i].1))
+            .note("values matched by it are already matched by previous arms")
+        );
+    }
+    if let Some(missing) = missing.as_ref().filter(|_| patterns.len() == arms.len()) {
+        bindings.diagnose(Diagnostic::error("Match is not exhaustive")
+            .span(Span::token(&tokens[0]))
+            .note(&format!("pattern '{missing}' is not covered"))
+            .help("add arm for it, or wildcard '_' to match anything else")
+            .code("non-exhaustive"));
+    }
+
+    let it_returns = missing.is_none() && !arms.is_empty() && arms.iter().all(|(x, _)| get_return(&x.body));
+    instructions.push(Value::Match { value, typ, arms: arms.into_iter().map(|(x, _)| x).collect() });
+    if it_returns {
+        unreachable_after(tokens, off, Span::token(&tokens[0]), bindings);
+        instructions.push(Value::ReturnMark);
+        return Ok(tokens.len())
+    }
+    valued(instructions);
+    Ok(off)
+}
+
+/// `@label` after body of loop
+fn next_label<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<&'a str>> {
+    if next_token(off, tokens, None, Some(TokenKind::LabelSymbol)).is_none() { return Ok(None) }
+    let Some(Token { value: label, .. }) = next_token(off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[*off-1], bindings, "Expected name of label", "expected-name")) };
+    bindings.insert(label, Bind::Label);
+    Ok(Some(label.as_str()))
+}
+
+pub fn r#loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 1;
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+    let label = next_label(&mut off, tokens, bindings)?;
     
-    bindings.context_scope(Context::Loop, |bindings| {
-        let body = parse(body, bindings);
+    let mut result = None;
+    bindings.context_scope(Context::Loop(label.map(String::from), Some(Type::Guess)), |bindings| {
+        let body = parse(body, bindings)?;
+        result = bindings.loop_result(None).cloned().flatten();
         instructions.push(Value::Loop(body, label));
-    });
+        Ok(())
+    })?;
+    // loop is left only by `break`, so it has value when each of them has one
+    if let Some(typ) = result.filter(|x| !matches!(x, Type::Guess | Type::Void)) {
+        let x = instructions.pop().unwrap();
+        instructions.push(Value::Valued(Box::new(x), typ));
+    }
     
-    return off
+    return Ok(off)
 }
 
+/// `while condition { .. }` is `loop { if !condition { break } .. }`
+pub fn r#while<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 1;
+    let condition = nvalue!(&mut off, tokens, instructions, bindings, "Expected condition");
+    Type::Bool.check_strict(&condition, &tokens[1..off], bindings);
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+    let label = next_label(&mut off, tokens, bindings)?;
 
-pub fn r#break<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
-    if let Some(Token { typ: TokenKind::Word, value: name, .. }) = tokens.get(1) {
-        if let Some(Bind::Label) = bindings.get(name) {
-            instructions.push(Value::Break(Some(name)));
-        }
-        else {
-            error(&tokens[1], bindings, "There's no label with this name")
+    bindings.context_scope(Context::Loop(label.map(String::from), None), |bindings| {
+        let mut instructions_loop = vec![exit_unless(condition)];
+        parse_inplace(body, &mut instructions_loop, bindings)?;
+        instructions.push(Value::Loop(instructions_loop, label));
+        Ok(())
+    })?;
+    Ok(off)
+}
+
+fn exit_unless (condition: Value) -> Value {
+    Value::If { condition: Box::new(Value::Unary(Box::new((Operation::Not, condition)))), body: vec![Value::Break(None, None)], else_then: Box::new(None) }
+}
+
+fn binary<'a> (lhs: Value<'a>, rhs: Value<'a>, op: Operation) -> Value<'a> { Value::Expr(Box::new((lhs, rhs, op))) }
+
+// counters of `for` live in its own scope, so nested loops don't clash
+const FOR_START: &str = "@for.start";
+const FOR_END: &str = "@for.end";
+const FOR_STEP: &str = "@for.step";
+const FOR_COUNT: &str = "@for.count";
+const FOR_INDEX: &str = "@for.index";
+const FOR_ITER: &str = "@for.iter";
+
+/// `for i in a..b { .. }` over integers, with `..=` for inclusive range and optional `step n` and `rev` after it. \
+/// `for x in array { .. }` or `for (i, x) in array { .. }` over arrays and pointers to them. \
+/// Both are lowered to `loop` over hidden index, that is advanced by deferred code, so `continue` works as well
+pub fn r#for<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 1;
+    let pair = next_token(&mut off, tokens, Some("("), Some(TokenKind::Brackets)).is_some();
+    let Some(first) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[off.min(tokens.len()-1)], bindings, "Expected name of loop variable", "expected-name")) };
+    let second = if pair {
+        ignore_separator(&mut off, tokens);
+        let Some(x) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[off-1], bindings, "Expected '(index, element)'", "syntax")) };
+        if next_token(&mut off, tokens, Some(")"), Some(TokenKind::Brackets)).is_none() { return Err(error(&tokens[off-1], bindings, "Expected '(index, element)'", "syntax")) }
+        Some(x)
+    } else { None };
+    if next_token(&mut off, tokens, Some("in"), Some(TokenKind::Word)).is_none() { return Err(error(&tokens[off-1], bindings, "Expected 'in' after loop variable", "syntax")) }
+
+    let start = off;
+    let from = nvalue!(&mut off, tokens, instructions, bindings, "Expected range or array to iterate over");
+    let (prelude, counter, binds) = if let Some(Token { value: dots, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Special)).filter(|x| x.value == ".." || x.value == "..=") {
+        if pair { bindings.gentle_error_range(&tokens[1..start-1], "Range yields only numbers, there's no index", "mismatched-types"); }
+        let to = nvalue!(&mut off, tokens, instructions, bindings, "Expected end of range");
+        let typ = extract_types(&[&from, &to]).unwrap_or_else(|| default_type(&from));
+        if !matches!(typ, Type::I(_) | Type::U(_)) { bindings.gentle_error_range(&tokens[start..off], &format!("Range must be of integers, not '{}'", typ.display()), "mismatched-types"); }
+        typ.check_strict(&from, &tokens[start..off], bindings);
+        typ.check_strict(&to, &tokens[start..off], bindings);
+
+        let (mut step, mut rev) = (None, false);
+        loop {
+            if next_token(&mut off, tokens, Some("rev"), Some(TokenKind::Word)).is_some() { rev = true; }
+            else if let Some(t) = next_token(&mut off, tokens, Some("step"), Some(TokenKind::Word)) {
+                let s = off;
+                let value = nvalue!(&mut off, tokens, instructions, bindings, "Expected step of range", t, off);
+                typ.check_strict(&value, &tokens[s..off], bindings);
+                match literal(&value) {
+                    Some(0) => bindings.gentle_error_range(&tokens[s..off], "Step of range must be positive, not zero", "invalid-operand"),
+                    Some(x) if x < 0 => bindings.gentle_error_range(&tokens[s..off], "Step of range must be positive, not negative", "invalid-operand"),
+                    _ => {}
+                }
+                step = Some(value);
+            }
+            else { break }
         }
-    }
-    else if let Some(_) = bindings.get_context_noval(&Context::Loop) {
-        instructions.push(Value::Break(None));
+        let (prelude, value, counter) = range(from, to, dots == "..=", step, rev, typ.clone());
+        (prelude, counter, vec![(first, typ, value)])
     }
     else {
-        error(&tokens[0], bindings, "Using 'break' is allowed only in loops")
+        let typ = extract_type(&from).unwrap_or_else(|| default_type(&from));
+        let (iter, iter_typ, arr) = match typ {
+            Type::Array(_, _) => (Value::Ptr(Box::new(from), false), Type::Ptr(Some(Box::new(typ.clone())), false), typ),
+            Type::Ptr(Some(ref x), _) if matches!(**x, Type::Array(_, _)) => (from, typ.clone(), *x.clone()),
+            Type::Slice(ref el, _) => (from, typ.clone(), elements_array(el.clone())),
+            _ => return Err(error_range(&tokens[start..off], bindings, &format!("Can't iterate over '{}', expected range, array, slice or pointer to array", typ.display()), "mismatched-types"))
+        };
+        let Type::Array(ref el, len) = arr else { unreachable!() };
+        let usize = Type::U(bindings.target_ptr_bits() as u32);
+        let (count, elements) = match iter_typ {
+            Type::Slice(_, _) => (Value::SliceLen(Box::new(Value::Get(FOR_ITER, iter_typ.clone())), usize.clone()), Value::SliceData(Box::new(Value::Get(FOR_ITER, iter_typ.clone())))),
+            _ => (Value::Int(len as u128), Value::Get(FOR_ITER, iter_typ.clone()))
+        };
+        let prelude = vec![
+            Value::InitVar(FOR_ITER, iter_typ, Some(Box::new(iter))),
+            Value::InitVar(FOR_COUNT, usize.clone(), Some(Box::new(count))),
+            Value::InitVar(FOR_INDEX, usize.clone(), Some(Box::new(Value::Int(0))))
+        ];
+        let element = Value::LoadFromPtr(Box::new(Value::LoadAddress(Box::new(elements), Box::new(Value::Get(FOR_INDEX, usize.clone())), arr.clone(), false)), *el.clone());
+        let binds = match second {
+            Some(x) => vec![(first, usize.clone(), Value::Get(FOR_INDEX, usize.clone())), (x, *el.clone(), element)],
+            None => vec![(first, *el.clone(), element)]
+        };
+        (prelude, usize, binds)
+    };
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+    let label = next_label(&mut off, tokens, bindings)?;
+
+    let index = Value::Get(FOR_INDEX, counter.clone());
+    let mut head = vec![
+        exit_unless(binary(index.clone(), Value::Get(FOR_COUNT, counter.clone()), Operation::NE)),
+        Value::Defer(vec![Value::ModifyVar(FOR_INDEX, counter, Box::new(binary(index, Value::Int(1), Operation::Add)))])
+    ];
+
+    bindings.context_scope(Context::Loop(label.map(String::from), None), |bindings| {
+        for (token, typ, value) in binds {
+            head.push(Value::InitVar(&token.value, typ.clone(), Some(Box::new(value))));
+            bindings.insert_local(token, typ, false);
+        }
+        parse_inplace(body, &mut head, bindings)
+    })?;
+    let mut block = prelude;
+    block.push(Value::Loop(head, label));
+    instructions.push(Value::Block(block));
+    Ok(off)
+}
+
+/// Value of integer literal, also negated or cast by suffix
+pub fn literal (x: &Value) -> Option<i128> {
+    match x {
+        Value::Int(x) => i128::try_from(*x).ok(),
+        Value::SInt(x) => Some(*x),
+        Value::Typecast(x, _, _) => literal(x),
+        Value::Unary(x) if matches!(x.0, Operation::Sub) => literal(&x.1)?.checked_neg(),
+        _ => None
     }
-    return tokens.len()
 }
 
-pub fn r#continue<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+/// Counters of range, value of loop variable and type of counters. Number of iterations is computed beforehand
+/// in unsigned type wider than range, so bounds at limits of type don't overflow it, except for inclusive range
+/// over all values of 128-bit integer. Range with step that isn't positive at runtime is left empty
+fn range<'a> (from: Value<'a>, to: Value<'a>, inclusive: bool, step: Option<Value<'a>>, rev: bool, typ: Type) -> (Vec<Value<'a>>, Value<'a>, Type) {
+    // range of other types is already reported, and is never compiled
+    let bits = match typ { Type::I(x) | Type::U(x) => x, _ => 64 };
+    let counter = Type::U(if bits < 64 { 64 } else { 128 });
+    let get = |name| Value::Get(name, typ.clone());
+    let count_get = |name| Value::Get(name, counter.clone());
+    let init = |name, x| Value::InitVar(name, typ.clone(), Some(Box::new(x)));
+    let count_init = |name, x| Value::InitVar(name, counter.clone(), Some(Box::new(x)));
+    // difference wraps in type of range, but as unsigned it's exact
+    let widen = |x| Value::Typecast(Box::new(x), Type::U(bits), counter.clone());
+
+    let mut prelude = vec![init(FOR_START, from), init(FOR_END, to)];
+    let diff = widen(binary(get(FOR_END), get(FOR_START), Operation::Sub));
+    let count = match (&step, inclusive) {
+        (None, false) => diff,
+        (None, true) => binary(diff, Value::Int(1), Operation::Add),
+        // range isn't empty, so difference is at least 1
+        (Some(_), false) => binary(binary(binary(diff, Value::Int(1), Operation::Sub), widen(get(FOR_STEP)), Operation::Div), Value::Int(1), Operation::Add),
+        (Some(_), true) => binary(binary(diff, widen(get(FOR_STEP)), Operation::Div), Value::Int(1), Operation::Add)
+    };
+    let stepped = step.is_some();
+    if let Some(step) = step { prelude.push(init(FOR_STEP, step)); }
+    let mut nonempty = binary(get(FOR_START), get(FOR_END), if inclusive { Operation::LE } else { Operation::LT });
+    // zero step would divide by zero
+    if stepped { nonempty = binary(nonempty, binary(get(FOR_STEP), Value::Int(0), Operation::GT), Operation::And); }
+    prelude.push(count_init(FOR_COUNT, Value::Int(0)));
+    prelude.push(Value::If { condition: Box::new(nonempty), body: vec![Value::ModifyVar(FOR_COUNT, counter.clone(), Box::new(count))], else_then: Box::new(None) });
+    prelude.push(count_init(FOR_INDEX, Value::Int(0)));
+
+    // `rev` walks the same numbers backwards
+    let nth = if rev { binary(binary(count_get(FOR_COUNT), Value::Int(1), Operation::Sub), count_get(FOR_INDEX), Operation::Sub) } else { count_get(FOR_INDEX) };
+    let nth = Value::Typecast(Box::new(nth), counter.clone(), typ.clone());
+    let offset = if prelude.iter().any(|x| matches!(x, Value::InitVar(FOR_STEP, _, _))) { binary(nth, get(FOR_STEP), Operation::Mul) } else { nth };
+    (prelude, binary(get(FOR_START), offset, Operation::Add), counter)
+}
+
+
+/// `break`, optionally with label of loop and value, that becomes result of `loop`
+pub fn r#break<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut off = 1;
+    let label = match tokens.get(1) {
+        Some(Token { typ: TokenKind::Word, value: name, .. }) if matches!(bindings.get(name), Some(Bind::Label)) => { off += 1; Some(name.as_str()) },
+        _ => None
+    };
+    let Some(result) = bindings.loop_result(label).cloned() else {
+        if label.is_some() { return Err(error(&tokens[1], bindings, "Label doesn't belong to any loop around", "unknown-label")) }
+        return Err(error(&tokens[0], bindings, "Using 'break' is allowed only in loops", "misplaced-item"))
+    };
+    let start = off;
+    // value must start on the same line, as code after plain `break` is a statement of its own
+    let same_line = tokens.get(off).is_some_and(|x| x.line == tokens[off-1].line);
+    let mut value = if same_line { next_value(&mut off, tokens, instructions, bindings)? } else { None };
+    match (result, &value) {
+        (None, Some(_)) => bindings.gentle_error_range(&tokens[start..off], "Only 'loop' can be left with value, not 'while' or 'for'", "break-value"),
+        (None, None) => {}
+        (Some(Type::Guess), _) => *bindings.loop_result(label).unwrap() = Some(value.as_ref().map_or(Type::Void, |x| extract_type(x).unwrap_or_else(|| default_type(x)))),
+        (Some(t), Some(v)) => {
+            let v = coerce(&t, v.clone(), &tokens[start..off], bindings)?;
+            t.check_strict(&v, &tokens[start..off], bindings);
+            value = Some(v);
+        }
+        (Some(Type::Void), None) => {}
+        (Some(t), None) => bindings.gentle_error(&tokens[0], &format!("Expected '{}', got nothing", t.display()), "syntax")
+    }
+    instructions.push(Value::Break(label, value.map(Box::new)));
+    unreachable_after(tokens, off, Span::range(&tokens[..off]), bindings);
+    return Ok(tokens.len())
+}
+
+pub fn r#continue<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     if let Some(Token { typ: TokenKind::Word, value: name, .. }) = tokens.get(1) {
         if let Some(Bind::Label) = bindings.get(name) {
             instructions.push(Value::Continue(Some(name)));
+            unreachable_after(tokens, 2, Span::range(&tokens[..2]), bindings);
         }
         else {
-            error(&tokens[1], bindings, "There's no label with this name")
+            return Err(error(&tokens[1], bindings, "There's no label with this name", "unknown-label"))
         }
     }
-    else if let Some(_) = bindings.get_context_noval(&Context::Loop) {
+    else if let Some(_) = bindings.get_context_noval(&Context::Loop(None, None)) {
         instructions.push(Value::Continue(None));
+        unreachable_after(tokens, 1, Span::token(&tokens[0]), bindings);
+    }
+    else {
+        return Err(error(&tokens[0], bindings, "Using 'continue' is allowed only in loops", "misplaced-item"))
+    }
+    return Ok(tokens.len())
+}
+
+/// Whether deferred code has instruction that leaves it early. `labels` are of loops inside of it
+fn escapes<'a> (body: &[Value<'a>], labels: &mut Vec<&'a str>, in_loop: bool) -> bool {
+    body.iter().any(|x| match x {
+        Value::Return(_) => true,
+        Value::Break(None, _) | Value::Continue(None) => !in_loop,
+        Value::Break(Some(l), _) | Value::Continue(Some(l)) => !labels.contains(l),
+        Value::Loop(body, label) => {
+            labels.extend(label);
+            escapes(body, labels, true)
+        }
+        Value::If { body, else_then, .. } => escapes(body, labels, in_loop) || (**else_then).as_ref().is_some_and(|x| escapes(std::slice::from_ref(x), labels, in_loop)),
+        Value::Else(body) | Value::Defer(body) | Value::Block(body) => escapes(body, labels, in_loop),
+        Value::Valued(x, _) => escapes(std::slice::from_ref(x), labels, in_loop),
+        Value::Match { arms, .. } => arms.iter().any(|x| escapes(&x.body, labels, in_loop)),
+        _ => false
+    })
+}
+
+/// `defer <instruction>` or `defer { .. }`, runs when enclosing scope is left, in reverse order of appearance
+pub fn defer<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    if bindings.get_context_noval(&Context::Fn(Type::Void)).is_none() {
+        return Err(error(&tokens[0], bindings, "Using 'defer' is allowed only in functions", "misplaced-item"))
     }
+    let mut off = 1;
+    if tokens.len() == 1 { return Err(error(&tokens[0], bindings, "Expected code to defer", "expected-value")) }
+
+    bindings.join_scope();
+    let body = if let Some(body) = next_body_optional(&mut off, tokens, ("{", "}")) { parse(body, bindings)? }
     else {
-        error(&tokens[0], bindings, "Using 'continue' is allowed only in loops")
+        let mut body = vec![];
+        off += value_lookaround(&tokens[1..], &mut body, bindings)?;
+        body
+    };
+    bindings.leave_scope();
+    if escapes(&body, &mut vec![], false) {
+        bindings.diagnose(
+            Diagnostic::error("Deferred code can't leave its scope")
+            .span(Span::range(&tokens[..off]))
+            .note("'return', and 'break' or 'continue' of loops outside of it aren't allowed in 'defer'")
+            .code("defer-escape")
+        );
     }
-    return tokens.len()
+    instructions.push(Value::Defer(body));
+    Ok(off)
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::{lexer::{Token, TokenKind}, parser::{bindings::Bindings, message::{assert, error}, r#type::{default_type, extract_types_move, Type}, value, value_loop, Operation, Value}};
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::Bindings, message::{assert, error, PResult}, r#type::{extract_types, Type}, value, value_loop, Operation, Value}};
 
 // use super::binds::reserve_local;
 
@@ -17,17 +17,26 @@ pub fn get_op (token: &Token) -> Option<Operation> {
         "&&" => Some(Operation::And), "||" => Some(Operation::Or),
         "!" => Some(Operation::Not),
         "&" => Some(Operation::BitAnd), "|" => Some(Operation::BitOr),
-        ">>" | "<<" | "^" => todo!(),
+        "^" => Some(Operation::Xor), "~" => Some(Operation::BitNot),
+        "<<" => Some(Operation::Shl), ">>" => Some(Operation::Shr),
         _ => None
     }
 }
 
-pub fn precedence (op: &Operation) -> u8 {
+/// Binding power of binary operator, the higher the tighter it binds. \
+/// None for operators which are only prefix
+pub fn precedence (op: &Operation) -> Option<u8> {
     match op {
-        Operation::Div | Operation::Mul | Operation::Rem | Operation::BitAnd | Operation::BitOr | Operation::Not => 0,
-        Operation::Add | Operation::Sub => 1,
-        Operation::Eq | Operation::NE | Operation::GE | Operation::GT | Operation::LE | Operation::LT => 2,
-        Operation::And | Operation::Or => 3
+        Operation::Div | Operation::Mul | Operation::Rem => Some(10),
+        Operation::Add | Operation::Sub => Some(9),
+        Operation::Shl | Operation::Shr => Some(8),
+        Operation::BitAnd => Some(7),
+        Operation::Xor => Some(6),
+        Operation::BitOr => Some(5),
+        Operation::Eq | Operation::NE | Operation::GE | Operation::GT | Operation::LE | Operation::LT => Some(4),
+        Operation::And => Some(3),
+        Operation::Or => Some(2),
+        Operation::Not | Operation::BitNot => None
     }
 }
 
@@ -35,11 +44,16 @@ pub fn is_cmp (op: &Operation) -> bool {
     match op {
         Operation::Add | Operation::Sub | Operation::Div | Operation::Mul |
         Operation::Rem | Operation::And | Operation::Or |
-        Operation::BitAnd | Operation::BitOr | Operation::Not => false,
+        Operation::BitAnd | Operation::BitOr | Operation::Xor | Operation::Shl | Operation::Shr |
+        Operation::Not | Operation::BitNot => false,
         Operation::Eq | Operation::NE | Operation::GE | Operation::GT | Operation::LE | Operation::LT => true
     }
 }
 
+pub fn is_bitwise (op: &Operation) -> bool {
+    matches!(op, Operation::BitAnd | Operation::BitOr | Operation::Xor | Operation::Shl | Operation::Shr | Operation::BitNot)
+}
+
 pub fn is_branch (op: &Operation) -> bool {
     match op {
         Operation::And | Operation::Or => true,
@@ -47,54 +61,66 @@ pub fn is_branch (op: &Operation) -> bool {
     }
 }
 
-/// Algorithm for expression parsing \
-/// TODO: maybe optimize by excluding excess loops through 'promised' vector and treating 'values' and 'promised' as hashmaps (position, value)
-pub fn expr<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
-    let mut values = vec![instructions.pop().unwrap()];
-    let mut promised: Vec<Operation> = vec![];
-    
-    // assert(Type::Ptr(None, false).check(&values[0]).is_none(), &tokens[0], bindings, "Clear pointer arithmetic is currently unavailable");
-    if let Type::U(_) | Type::I(_) = default_type(&values[0]) {}
-    else { bindings.gentle_error(&tokens[0], "Unsupported type"); }
-
-    let mut i = 0;
-    while i < tokens.len() {
-        let tokens = &tokens[i..];
-        let Some(op) = get_op(&tokens[0]) else { break };
-        let a = value(&tokens[1..], &mut values, bindings);
-        let b = value_loop(&tokens[1..], &mut values, bindings, a, &[(Some(TokenKind::Arithmetic), None), (Some(TokenKind::Bitwise), None), (Some(TokenKind::Logical), None)]);
-        let len = b + 1;
-        i += len;
-        promised.push(op);
-    }
-
-    extract_types_move(&values).unwrap_or_else(|| error(&tokens[0], bindings, "Heterogeneous types"));
-    assert(values.len() == promised.len() + 1, &tokens[i-1], bindings, "Failed parsing binary expression");
-    
-    let mut current_prec = 0;
-    let mut j = 0;
-    loop {
-        if promised.is_empty() { break }
-        if j >= promised.len() { j = 0; current_prec += 1; }
-        
-        // idea: optimize remove: do not use remove, take value behind index and replace it with NoOp, so there's no reallocation, just little memory overhead
-        if precedence(&promised[j]) == current_prec {
-            let lhs = values.remove(j); let rhs = values.remove(j);
-            let op = promised.remove(j);
-            assert(!is_branch(&op) || (Type::Bool.check(&lhs).is_some() && Type::Bool.check(&rhs).is_some()), &tokens[0], bindings, "'&&' and '||' accepts only boolean-typed values");
-            // if is_branch(&op) { reserve_local(bindings); }
-            values.insert(j, Value::Expr(Box::new((lhs, rhs, op))));
-            continue
-        }
+const OPERATORS: [(Option<TokenKind>, Option<&str>); 3] = [(Some(TokenKind::Arithmetic), None), (Some(TokenKind::Bitwise), None), (Some(TokenKind::Logical), None)];
+
+/// Value with everything that binds tighter than binary operators: calls, fields, indexing and casts
+fn operand<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let a = value(tokens, instructions, bindings)?;
+    value_loop(tokens, instructions, bindings, a, &OPERATORS)
+}
+
+/// Operand of prefix operator, it binds tighter than 'as', but not than calls, fields and indexing
+pub fn prefix_operand<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<Option<Value<'a>>> {
+    let Some(tokens) = tokens.get(*off..) else { return Ok(None) };
+    let ls = instructions.len();
+    let a = value(tokens, instructions, bindings)?;
+    if instructions.len() == ls { *off += a; return Ok(None) }
+    let ignore = [OPERATORS[0], OPERATORS[1], OPERATORS[2], (Some(TokenKind::Word), Some("as"))];
+    *off += value_loop(tokens, instructions, bindings, a, &ignore)?;
+    Ok(instructions.pop())
+}
 
-        j += 1;
+/// Types of operands must match and be supported by operator
+fn check_operands<'a> (lhs: &Value<'a>, rhs: &Value<'a>, op: Operation, token: &Token, bindings: &mut Bindings<'a>) -> PResult<()> {
+    if is_branch(&op) {
+        return assert(Type::Bool.check(lhs).is_some() && Type::Bool.check(rhs).is_some(), token, bindings, "'&&' and '||' accepts only boolean-typed values", "mismatched-types")
+    }
+    let typ = extract_types(&[lhs, rhs]).ok_or_else(|| error(token, bindings, "Heterogeneous types", "mismatched-types"))?;
+    match typ {
+        Type::F(_) if is_bitwise(&op) => bindings.gentle_error(token, &format!("Operator '{}' is only defined for integers", token.value), "invalid-operand"),
+        Type::U(_) | Type::I(_) | Type::F(_) => {}
+        Type::Bool if matches!(op, Operation::Eq | Operation::NE | Operation::BitAnd | Operation::BitOr | Operation::Xor) => {}
+        _ => bindings.gentle_error(token, "Unsupported type", "mismatched-types")
     }
+    Ok(())
+}
 
+/// Binary operators after `lhs` which bind tighter than `min`, precedence climbing
+fn climb<'a> (off: &mut usize, tokens: &'a [Token], mut lhs: Value<'a>, min: u8, bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    while let Some(op) = tokens.get(*off).and_then(get_op) {
+        let token = &tokens[*off];
+        let Some(power) = precedence(&op) else { break };
+        if power < min { break }
+        *off += 1;
 
-    // instructions.extend(values);
-    instructions.push(values.pop().unwrap());
+        let mut values = vec![];
+        *off += match tokens.get(*off..) { Some(x) => operand(x, &mut values, bindings)?, None => 0 };
+        let Some(rhs) = values.pop() else { return Err(error(token, bindings, "Expected value after operator", "expected-value")) };
+        // all operators are left-associative
+        let rhs = climb(off, tokens, rhs, power + 1, bindings)?;
+        check_operands(&lhs, &rhs, op, token, bindings)?;
+        lhs = Value::Expr(Box::new((lhs, rhs, op)));
+    }
+    Ok(lhs)
+}
 
-    i
+/// Binary expression, where left-hand side is the last instruction and `tokens` start with operator
+pub fn expr<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let lhs = instructions.pop().unwrap();
+    let mut off = 0;
+    let value = climb(&mut off, tokens, lhs, 0, bindings)?;
+    instructions.push(value);
+    Ok(off)
 }
 
 #[allow(dead_code)]
@@ -121,14 +147,15 @@ fn print_expr (x: &Value) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::*;
+    use crate::{lexer::lex, parser::{bindings::{Bindings, Sources}, parse_program}};
     use super::*;
 
     macro_rules! test_case {
         ($code:expr, $expect:expr) => {
             let code = $code;
             let tokens = lex(code);
-            let mut result = parse_program(&tokens, &mut Bindings::new(code, "".into(), None));
+            let sources = Sources::default();
+            let mut result = parse_program(&tokens, &mut Bindings::new(code, "".into(), None, &sources)).unwrap();
             // dbg!(&result);
             assert_eq!(eval_expr(result.remove(0)), $expect);
         };
@@ -186,6 +213,43 @@ mod tests {
         test_case!("4 / 2 * 2 + 2 - 2 / 2 < 4 / 2 * 2 + 2 + 2 / 2", 1.0);
     }
 
+    #[test]
+    fn bitwise () {
+        test_case!("12 & 10 | 1 ^ 4", 13.0);
+    }
+
+    #[test]
+    fn shifts () {
+        test_case!("1 << 2 + 1", 8.0);
+        test_case!("64 >> 1 * 2", 16.0);
+    }
+
+    #[test]
+    fn left_assoc () {
+        test_case!("20 - 3 - 4", 13.0);
+        test_case!("64 / 4 / 2 % 5", 3.0);
+    }
+
+    #[test]
+    fn bitwise_levels () {
+        test_case!("1 | 2 ^ 3 & 1", 3.0);
+        test_case!("6 & 3 == 2", 1.0);
+        test_case!("1 + 2 * 3 << 1", 14.0);
+    }
+
+    #[test]
+    fn logical_levels () {
+        test_case!("1 < 2 || 2 < 1 && 1 < 0", 1.0);
+        test_case!("1 + 1 == 2 && 3 != 4 * 1", 1.0);
+    }
+
+    #[test]
+    fn unary_inside () {
+        test_case!("2 * -3 + 1", -5.0);
+        test_case!("2 * -(1 + 2) - ~0", -5.0);
+        test_case!("-2 * 3 - -1", -5.0);
+    }
+
     fn eval_expr (x: Value) -> f64 {
         match x {
             Value::Expr(x) => {
@@ -195,11 +259,28 @@ mod tests {
                     Operation::Mul => eval_expr(lhs) * eval_expr(rhs),
                     Operation::Sub => eval_expr(lhs) - eval_expr(rhs),
                     Operation::Div => eval_expr(lhs) / eval_expr(rhs),
+                    Operation::Rem => eval_expr(lhs) % eval_expr(rhs),
                     Operation::LT => if eval_expr(lhs) < eval_expr(rhs) { 1.0 } else { 0.0 },
+                    Operation::GT => f64::from(eval_expr(lhs) > eval_expr(rhs)),
+                    Operation::Eq => f64::from(eval_expr(lhs) == eval_expr(rhs)),
+                    Operation::NE => f64::from(eval_expr(lhs) != eval_expr(rhs)),
+                    Operation::And => f64::from(eval_expr(lhs) != 0.0 && eval_expr(rhs) != 0.0),
+                    Operation::Or => f64::from(eval_expr(lhs) != 0.0 || eval_expr(rhs) != 0.0),
+                    Operation::BitAnd => (eval_expr(lhs) as i64 & eval_expr(rhs) as i64) as f64,
+                    Operation::BitOr => (eval_expr(lhs) as i64 | eval_expr(rhs) as i64) as f64,
+                    Operation::Xor => (eval_expr(lhs) as i64 ^ eval_expr(rhs) as i64) as f64,
+                    Operation::Shl => ((eval_expr(lhs) as i64) << eval_expr(rhs) as i64) as f64,
+                    Operation::Shr => (eval_expr(lhs) as i64 >> eval_expr(rhs) as i64) as f64,
                     _ => 0.0
                 }
             }
+            Value::Unary(x) => match *x {
+                (Operation::Sub, x) => -eval_expr(x),
+                (Operation::BitNot, x) => !(eval_expr(x) as i64) as f64,
+                _ => 0.0
+            }
             Value::Int(x) => x as f64,
+            Value::SInt(x) => x as f64,
             Value::Float(x) => x,
             _ => {
                 0.0
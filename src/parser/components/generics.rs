@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::{Bind, Bindings}, components::{binds::join_by_path, traits::resolve_trait}, message::{error, error_range, Diagnostic, PResult, Span, token_at}, simpler::{construct_call, ignore_separator, next_args, next_body, next_body_optional, next_call, next_token, next_type, next_value}, r#type::{default_type, extract_type, Type}, SharedValue, Value}};
+
+/// Names of type parameters and tokens of their bounds
+pub type GenericParams<'a> = (Vec<&'a str>, Vec<&'a [Token]>);
+/// Type parameters bound to type arguments, arguments and return type of instantiated function
+type Header<'a> = (Vec<(&'a str, Type)>, Vec<super::args::Arg<'a>>, Type);
+
+/// Parses `<T, U Trait + Other>` after name of generic declaration: names of parameters and their bounds.
+/// Bounds are resolved on instantiation, as traits may be declared later
+pub fn next_generic_params<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<GenericParams<'a>>> {
+    let start = *off;
+    if next_token(off, tokens, Some("<"), Some(TokenKind::Logical)).is_none() { return Ok(None) }
+    let (mut params, mut bounds) = (vec![], vec![]);
+    while next_token(off, tokens, Some(">"), Some(TokenKind::Logical)).is_none() {
+        let Some(t) = next_token(off, tokens, None, Some(TokenKind::Word)) else {
+            return Err(error(token_at(tokens, *off), bindings, "Expected name of type parameter", "expected-name"))
+        };
+        if params.contains(&t.value.as_str()) { bindings.gentle_error(t, &format!("Type parameter '{}' is declared twice", t.value), "duplicate-name"); }
+        params.push(t.value.as_str());
+        let b = *off;
+        while tokens.get(*off).is_some_and(|x| x.value != "," && x.value != ">") { *off += 1; }
+        bounds.push(&tokens[b..*off]);
+        ignore_separator(off, tokens);
+    }
+    if params.is_empty() { bindings.gentle_error_range(&tokens[start..*off], "Generic declaration must have at least one type parameter", "syntax"); }
+    Ok(Some((params, bounds)))
+}
+
+/// Checks that type arguments implement traits their parameters are bound by. `tokens` are where instance is requested
+fn check_bounds<'a> (template: &str, params: &[&str], bounds: &[&'a [Token]], types: &[Type], tokens: &[Token], bindings: &mut Bindings<'a>) -> PResult<()> {
+    for ((param, bound), typ) in params.iter().zip(bounds).zip(types) {
+        if bound.is_empty() { continue }
+        for path in bound.split(|x| x.value == "+") {
+            let Some(Token { value: first, .. }) = path.first() else { return Err(error(&bound[0], bindings, "Expected trait", "expected-type")) };
+            let name = path.iter().map(|x| x.value.as_str()).collect::<String>();
+            let Some(r#trait) = join_by_path(template, bindings, |bindings| resolve_trait(&name, bindings)) else {
+                return Err(error_range(path, bindings, &format!("'{first}' is not a trait"), "unknown-name"))
+            };
+            let Value::Trait { name: trait_name, .. } = &*r#trait.borrow() else { unreachable!() };
+            if !bindings.implements(typ, trait_name) {
+                return Err(bindings.fatal(
+                    Diagnostic::error(&format!("Type '{}' doesn't implement trait '{trait_name}'", typ.display()))
+                    .span(Span::range(tokens))
+                    .note(&format!("required by bound '{param} {name}' of '{template}'"))
+                    .code("unsatisfied-bound")
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses `<i32, Pair<u8, bool>>`. Closing `>>` is split between two nested lists
+pub fn next_type_args<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<Vec<Type>>> {
+    if next_token(off, tokens, Some("<"), Some(TokenKind::Logical)).is_none() { return Ok(None) }
+    let mut types = vec![];
+    loop {
+        if bindings.take_split_close() || next_token(off, tokens, Some(">"), Some(TokenKind::Logical)).is_some() { break }
+        if next_token(off, tokens, Some(">>"), Some(TokenKind::Bitwise)).is_some() { bindings.set_split_close(); break }
+        let Some(t) = next_type(off, tokens, bindings)? else {
+            return Err(error(token_at(tokens, *off), bindings, "Expected type argument", "expected-type"))
+        };
+        types.push(t);
+        ignore_separator(off, tokens);
+    }
+    Ok(Some(types))
+}
+
+pub fn generic_scope<'a> (generics: &[(&'a str, Type)]) -> HashMap<String, Bind<'a>> {
+    generics.iter().map(|(k, v)| (k.to_string(), Bind::Generic(v.clone()))).collect()
+}
+
+/// Name of template instance, e.g. `Pair<i32, bool>`
+fn mangle (name: &str, types: &[Type]) -> String {
+    format!("{name}<{}>", types.iter().map(Type::display).collect::<Vec<_>>().join(", "))
+}
+
+pub fn has_param (t: &Type, bindings: &Bindings) -> bool {
+    match t {
+        Type::Param(_) => true,
+        Type::Ptr(Some(t), _) | Type::Array(t, _) | Type::Slice(t, _) => has_param(t, bindings),
+        Type::Tuple(v) => v.iter().any(|x| has_param(x, bindings)),
+        Type::Fn(args, ret) => args.iter().any(|x| has_param(x, bindings)) || has_param(ret, bindings),
+        Type::Struct(name) => bindings.get_instance(name).is_some_and(|(_, v)| v.iter().any(|x| has_param(x, bindings))),
+        _ => false
+    }
+}
+
+pub fn fn_template<'a> (off: &mut usize, tokens: &'a [Token], name: String, (params, bounds): GenericParams<'a>, bindings: &mut Bindings<'a>) -> PResult<SharedValue<'a>> {
+    let start = *off;
+    // types of arguments and return type can't contain braces
+    let Some(j) = tokens[start..].iter().position(|x| x.value == "{") else {
+        return Err(error(&tokens[*off-1], bindings, "Expected function body", "syntax"))
+    };
+    *off += j;
+    let body = next_body(off, tokens, bindings, ("{", "}"))?;
+    Ok(SharedValue::new(Value::FnTemplate { name, params, bounds, header: &tokens[start..start+j], body, token: &tokens[0], instances: vec![] }.into()))
+}
+
+/// Parses argument list and return type of generic function with given type arguments
+fn header<'a> (l: &SharedValue<'a>, types: &[Type], bindings: &mut Bindings<'a>) -> PResult<Header<'a>> {
+    let Value::FnTemplate { name, params, header, .. } = &*l.borrow() else { unreachable!() };
+    let generics = params.iter().copied().zip(types.iter().cloned()).collect::<Vec<_>>();
+    let (args, ret) = join_by_path(name, bindings, |bindings| {
+        bindings.push_scope(generic_scope(&generics));
+        let mut off = 0;
+        let args = next_args(&mut off, header, bindings)?;
+        let ret = next_type(&mut off, header, bindings)?.unwrap_or(Type::Void);
+        bindings.pop_scope();
+        Ok((args, ret))
+    })?;
+    Ok((generics, args, ret))
+}
+
+/// How deep generic functions may request instances of each other, as recursion with growing type arguments never ends
+const RECURSION_LIMIT: usize = 128;
+
+/// Returns pointer to instance of generic function, promising it on first use. `tokens` are where it's requested
+pub fn instantiate_fn<'a> (l: &SharedValue<'a>, types: Vec<Type>, tokens: &[Token], bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    let cached = match &*l.borrow() {
+        Value::FnTemplate { instances, .. } => instances.iter().find(|(t, _, _)| *t == types).map(|x| x.1.clone()),
+        _ => unreachable!()
+    };
+    if let Some(x) = cached { return Ok(x) }
+    let (template, params, bounds) = match &*l.borrow() { Value::FnTemplate { name, params, bounds, .. } => (name.clone(), params.clone(), bounds.clone()), _ => unreachable!() };
+    check_bounds(&template, &params, &bounds, &types, tokens, bindings)?;
+    let (generics, args, ret) = header(l, &types, bindings)?;
+    let (name, body, token) = match &*l.borrow() { Value::FnTemplate { name, body, token, .. } => (mangle(name, &types), *body, *token), _ => unreachable!() };
+    if bindings.register_fn_instance(name.clone()) > RECURSION_LIMIT {
+        return Err(bindings.fatal(
+            Diagnostic::error(&format!("Reached recursion limit while instantiating '{template}'"))
+            .span(Span::range(tokens))
+            .note(&format!("instances of generic functions can't be nested deeper than {RECURSION_LIMIT}"))
+            .help("function which calls itself with ever growing type arguments needs infinitely many instances")
+            .code("recursion-limit")
+        ))
+    }
+    let pointer = Value::FunctionPointer(name.clone(), ret.clone(), args.iter().map(|x| x.typ.clone()).collect());
+    let sv = SharedValue::new(Value::PromisedFunction { name, args, body, ret, token, generics }.into());
+    bindings.push_function(sv.clone());
+    let Value::FnTemplate { instances, .. } = &mut *l.borrow_mut() else { unreachable!() };
+    instances.push((types, pointer.clone(), sv));
+    Ok(pointer)
+}
+
+/// Name of instance of generic struct, declaring it on first use
+pub fn instantiate_struct<'a> (l: &SharedValue<'a>, types: Vec<Type>, tokens: &[Token], bindings: &mut Bindings<'a>) -> PResult<String> {
+    let (template, params, bounds, body) = match &*l.borrow() {
+        Value::StructTemplate { name, params, bounds, body, .. } => (name.clone(), params.clone(), bounds.clone(), *body),
+        _ => unreachable!()
+    };
+    if params.len() != types.len() {
+        return Err(error_range(tokens, bindings, &format!("Struct '{template}' takes {} type arguments, got {}", params.len(), types.len()), "type-argument-count"))
+    }
+    let name = mangle(&template, &types);
+    if bindings.get_instance(&name).is_some() { return Ok(name) }
+    bindings.register_instance(name.clone(), template.clone(), types.clone());
+    // placeholders of inference can't have layout
+    if types.iter().any(|x| has_param(x, bindings)) { return Ok(name) }
+    check_bounds(&template, &params, &bounds, &types, tokens, bindings)?;
+
+    let generics = params.into_iter().zip(types.iter().cloned()).collect();
+    let sv = SharedValue::new(Value::PromisedStruct { name: name.clone(), body, generics }.into());
+    bindings.root_insert(&name, Bind::Type(Some(sv.clone())));
+    bindings.declare_type(name.clone(), sv.clone());
+    bindings.push_type(sv.clone());
+    let Value::StructTemplate { instances, .. } = &mut *l.borrow_mut() else { unreachable!() };
+    instances.push((types, sv));
+    Ok(name)
+}
+
+/// Binds parameters found in `pattern` to parts of `typ`. Types of literals are weak and give way to others
+fn unify (pattern: &Type, typ: &Type, strong: bool, found: &mut [Option<(Type, bool)>], bindings: &Bindings) -> Result<(), usize> {
+    match (pattern, typ) {
+        (Type::Param(i), t) => match &found[*i] {
+            None => found[*i] = Some((t.clone(), strong)),
+            Some((_, false)) if strong => found[*i] = Some((t.clone(), strong)),
+            Some((x, true)) if strong && x != t => return Err(*i),
+            _ => {}
+        },
+        (Type::Ptr(Some(a), _), Type::Ptr(Some(b), _)) | (Type::Array(a, _), Type::Array(b, _)) | (Type::Slice(a, _), Type::Slice(b, _)) => unify(a, b, strong, found, bindings)?,
+        (Type::Tuple(a), Type::Tuple(b)) if a.len() == b.len() => {
+            for (a, b) in a.iter().zip(b) { unify(a, b, strong, found, bindings)? }
+        }
+        (Type::Fn(a, r), Type::Fn(b, s)) if a.len() == b.len() => {
+            for (a, b) in a.iter().zip(b) { unify(a, b, strong, found, bindings)? }
+            unify(r, s, strong, found, bindings)?
+        }
+        (Type::Struct(a), Type::Struct(b)) => {
+            let (Some((x, a)), Some((y, b))) = (bindings.get_instance(a), bindings.get_instance(b)) else { return Ok(()) };
+            if x != y { return Ok(()) }
+            for (a, b) in a.iter().zip(b) { unify(a, b, strong, found, bindings)? }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Reference to generic function: `name::<T>` or call with inferred type arguments
+pub fn generic_call<'a> (off: &mut usize, tokens: &'a [Token], l: SharedValue<'a>, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    let (name, params) = match &*l.borrow() { Value::FnTemplate { name, params, .. } => (name.clone(), params.clone()), _ => unreachable!() };
+    let start = *off;
+    if tokens.get(*off).is_some_and(|x| x.value == "::") && tokens.get(*off+1).is_some_and(|x| x.value == "<") {
+        *off += 1;
+        let types = next_type_args(off, tokens, bindings)?.unwrap();
+        if types.len() != params.len() {
+            return Err(error_range(&tokens[start-1..*off], bindings, &format!("Function '{name}' takes {} type arguments, got {}", params.len(), types.len()), "type-argument-count"))
+        }
+        instructions.push(instantiate_fn(&l, types, &tokens[start-1..*off], bindings)?);
+        return next_call(off, tokens, instructions, bindings)
+    }
+
+    let Some(body) = next_body_optional(off, tokens, ("(", ")")) else {
+        return Err(bindings.fatal(
+            Diagnostic::error(&format!("Can't infer type arguments of generic function '{name}' without call"))
+            .span(Span::token(&tokens[start-1]))
+            .help(&format!("specify them explicitly: '{}::<...>'", tokens[start-1].value))
+            .code("type-annotations-needed")
+        ))
+    };
+    let mut argv = vec![];
+    let mut boff = 0;
+    while boff < body.len() {
+        let s = boff;
+        let Some(value) = next_value(&mut boff, body, instructions, bindings)? else { bindings.gentle_error(&body[s], "Expected function argument", "expected-value"); return Ok(()) };
+        argv.push((value, s..boff));
+        ignore_separator(&mut boff, body);
+    }
+
+    let (_, pattern, _) = header(&l, &(0..params.len()).map(Type::Param).collect::<Vec<_>>(), bindings)?;
+    if pattern.len() != argv.len() {
+        bindings.gentle_error_range(&tokens[start-1..*off], &format!("Function '{name}' takes {} arguments, got {}", pattern.len(), argv.len()), "argument-count");
+        return Ok(())
+    }
+    let mut found = vec![None; params.len()];
+    for (arg, (value, range)) in pattern.iter().zip(&argv) {
+        let (typ, strong) = match extract_type(value) { Some(x) => (x, true), None => (default_type(value), false) };
+        if let Err(i) = unify(&arg.typ, &typ, strong, &mut found, bindings) {
+            let Some((first, _)) = &found[i] else { unreachable!() };
+            return Err(bindings.fatal(
+                Diagnostic::error(&format!("Conflicting types for type parameter '{}' of '{name}'", params[i]))
+                .span(Span::range(&body[range.clone()]))
+                .label(Span::range(&body[range.clone()]), &format!("here it's '{}', but earlier it was '{}'", typ.display(), first.display()))
+                .code("type-annotations-needed")
+            ))
+        }
+    }
+    let mut types = vec![];
+    for (i, x) in found.into_iter().enumerate() {
+        let Some((x, _)) = x else {
+            return Err(bindings.fatal(
+                Diagnostic::error(&format!("Can't infer type parameter '{}' of '{name}'", params[i]))
+                .span(Span::range(&tokens[start-1..*off]))
+                .help(&format!("specify it explicitly: '{}::<...>(...)'", tokens[start-1].value))
+                .code("type-annotations-needed")
+            ))
+        };
+        types.push(x);
+    }
+
+    let f = instantiate_fn(&l, types, &tokens[start-1..*off], bindings)?;
+    let Some(Type::Fn(argt, ret)) = extract_type(&f) else { unreachable!() };
+    for (typ, (value, range)) in argt.iter().zip(&argv) {
+        typ.check_strict(value, &body[range.clone()], bindings);
+    }
+    *off += construct_call(f, argv.into_iter().map(|x| x.0).collect(), tokens, *off, instructions, bindings, *ret);
+    next_call(off, tokens, instructions, bindings)
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::{Bind, Bindings, Context}, components::{expr::{is_branch, is_cmp}, pattern::int_bounds}, message::{error, Diagnostic, PResult, Span}, numbers::check_literals, parse, simpler::{next_mutable_flag, next_token, next_type, next_value}, r#type::{default_type, extract_type, extract_types, Type}, Operation, Value}};
+
+const NOT_CONSTANT: &str = "Value must be known at compile time";
+
+/// Result of evaluating constant expression
+#[derive(Debug, Clone, Copy)]
+enum Const { Int(i128), Float(f64), Bool(bool) }
+
+fn bits (typ: &Type) -> i128 {
+    match typ { Type::I(x) | Type::U(x) => *x as i128, _ => 0 }
+}
+
+/// Integer truncated to width of type, like it happens at runtime
+fn wrap (x: i128, typ: &Type) -> i128 {
+    let bits = bits(typ);
+    if bits == 0 || bits >= 128 { return x }
+    let x = x & ((1 << bits) - 1);
+    if matches!(typ, Type::I(_)) && x >> (bits - 1) == 1 { x - (1 << bits) } else { x }
+}
+
+fn int (x: Option<i128>, typ: &Type) -> Result<Const, String> {
+    match (x, int_bounds(typ)) {
+        (Some(x), Some((min, max))) if (min..=max).contains(&x) => Ok(Const::Int(x)),
+        (Some(x), None) => Ok(Const::Int(x)),
+        _ => Err(format!("Overflow while evaluating constant of type '{}'", typ.display()))
+    }
+}
+
+fn compare<T: PartialOrd> (x: T, y: T, op: Operation) -> Const {
+    Const::Bool(match op {
+        Operation::LT => x < y, Operation::LE => x <= y,
+        Operation::GT => x > y, Operation::GE => x >= y,
+        Operation::Eq => x == y, Operation::NE => x != y,
+        _ => unreachable!()
+    })
+}
+
+fn cast (x: Const, to: &Type) -> Const {
+    match (x, to) {
+        (Const::Int(x), Type::F(_)) => cast(Const::Float(x as f64), to),
+        (Const::Int(x), Type::Bool) => Const::Bool(x != 0),
+        (Const::Int(x), _) => Const::Int(wrap(x, to)),
+        (Const::Float(x), Type::F(32)) => Const::Float(x as f32 as f64),
+        // saturates, like 'fptosi.sat'
+        (Const::Float(x), Type::I(_) | Type::U(_)) => {
+            let (min, max) = int_bounds(to).unwrap();
+            Const::Int((x as i128).clamp(min, max))
+        }
+        (Const::Bool(x), Type::I(_) | Type::U(_)) => Const::Int(x as i128),
+        (x, _) => x
+    }
+}
+
+fn binary (lhs: Const, rhs: Const, op: Operation, typ: &Type) -> Result<Const, String> {
+    Ok(match (lhs, rhs) {
+        (Const::Int(x), Const::Int(y)) => match op {
+            Operation::Add => return int(x.checked_add(y), typ),
+            Operation::Sub => return int(x.checked_sub(y), typ),
+            Operation::Mul => return int(x.checked_mul(y), typ),
+            Operation::Div | Operation::Rem if y == 0 => return Err("Division by zero while evaluating constant".into()),
+            Operation::Div => return int(x.checked_div(y), typ),
+            Operation::Rem => return int(x.checked_rem(y), typ),
+            Operation::BitAnd => Const::Int(x & y),
+            Operation::BitOr => Const::Int(x | y),
+            Operation::Xor => Const::Int(x ^ y),
+            Operation::Shl | Operation::Shr if !(0..bits(typ)).contains(&y) => return Err(format!("Shift by {y} is out of range for '{}'", typ.display())),
+            Operation::Shl => Const::Int(wrap(((x as u128) << y) as i128, typ)),
+            Operation::Shr => Const::Int(x >> y),
+            op if is_cmp(&op) => compare(x, y, op),
+            _ => return Err(NOT_CONSTANT.into())
+        },
+        (Const::Float(x), Const::Float(y)) => match op {
+            Operation::Add => Const::Float(x + y),
+            Operation::Sub => Const::Float(x - y),
+            Operation::Mul => Const::Float(x * y),
+            Operation::Div => Const::Float(x / y),
+            Operation::Rem => Const::Float(x % y),
+            op if is_cmp(&op) => compare(x, y, op),
+            _ => return Err(NOT_CONSTANT.into())
+        },
+        (Const::Bool(x), Const::Bool(y)) => Const::Bool(match op {
+            Operation::And | Operation::BitAnd => x && y,
+            Operation::Or | Operation::BitOr => x || y,
+            Operation::Xor | Operation::NE => x != y,
+            Operation::Eq => x == y,
+            _ => return Err(NOT_CONSTANT.into())
+        }),
+        _ => return Err(NOT_CONSTANT.into())
+    })
+}
+
+/// Scalar value of `x`, which has type `typ`
+fn eval (x: &Value, typ: &Type, bindings: &Bindings) -> Result<Const, String> {
+    Ok(match x {
+        Value::Int(x) => return int(i128::try_from(*x).ok(), typ),
+        Value::SInt(x) => return int(Some(*x), typ),
+        Value::Char(x) => Const::Int(*x as i128),
+        Value::Byte(x) => Const::Int(*x as i128),
+        Value::Float(x) => Const::Float(*x),
+        Value::Bool(x) => Const::Bool(*x),
+        Value::Typecast(x, from, to) => {
+            // untyped operand is computed as is and wraps into type it's cast from, like at runtime
+            let x = if extract_type(x).is_some() { eval(x, from, bindings)? } else { cast(eval(x, &Type::I(128), bindings)?, from) };
+            cast(x, to)
+        }
+        Value::Unary(l) => match (l.0, eval(&l.1, typ, bindings)?) {
+            (Operation::Sub, Const::Int(x)) => return int(x.checked_neg(), typ),
+            (Operation::Sub, Const::Float(x)) => Const::Float(-x),
+            (Operation::Not, Const::Bool(x)) => Const::Bool(!x),
+            (Operation::Not | Operation::BitNot, Const::Int(x)) => Const::Int(wrap(!x, typ)),
+            _ => return Err(NOT_CONSTANT.into())
+        },
+        Value::Expr(l) => {
+            let (lhs, rhs, op) = &**l;
+            // operands of comparisons have their own type
+            let typ = if is_branch(op) { Type::Bool } else if is_cmp(op) { extract_types(&[lhs, rhs]).unwrap_or(Type::Void) } else { typ.clone() };
+            binary(eval(lhs, &typ, bindings)?, eval(rhs, &typ, bindings)?, *op, &typ)?
+        }
+        // indexing of constant array
+        Value::LoadFromPtr(x, _) => {
+            let Value::LoadAddress(from, index, Type::Array(t, len), _) = &**x else { return Err(NOT_CONSTANT.into()) };
+            let Value::Array(items) = &**from else { return Err(NOT_CONSTANT.into()) };
+            let Const::Int(i) = eval(index, &Type::U(bindings.target_ptr_bits() as u32), bindings)? else { unreachable!() };
+            if i >= *len as i128 { return Err(format!("Index {i} is out of bounds of array of length {len}")) }
+            eval(&items[i as usize], t, bindings)?
+        }
+        _ => return Err(NOT_CONSTANT.into())
+    })
+}
+
+fn literal<'a> (x: Const, typ: &Type) -> Value<'a> {
+    let x = match x {
+        Const::Int(x) if x < 0 => Value::SInt(x),
+        Const::Int(x) => Value::Int(x as u128),
+        Const::Float(x) => Value::Float(x),
+        Const::Bool(x) => return Value::Bool(x)
+    };
+    Value::Typecast(Box::new(x), typ.clone(), typ.clone())
+}
+
+fn fold<'a> (x: &Value<'a>, typ: &Type, bindings: &Bindings) -> Result<Value<'a>, String> {
+    match (x, typ) {
+        (Value::Array(x), Type::Array(t, _)) => x.iter().map(|x| fold(x, t, bindings)).collect::<Result<_, _>>().map(Value::Array),
+        (Value::Tuple(x), Type::Tuple(t)) => x.iter().zip(t).map(|(x, t)| fold(x, t, bindings)).collect::<Result<_, _>>().map(Value::Tuple),
+        (Value::String(_), _) => Ok(x.clone()),
+        _ => eval(x, typ, bindings).map(|x| literal(x, typ))
+    }
+}
+
+/// Evaluates value of type `typ` at compile time, into literals of its type. \
+/// `tokens` is the value as it's written in code, to report it if it can't be evaluated
+pub fn evaluate<'a> (x: &Value<'a>, typ: &Type, tokens: &[Token], bindings: &Bindings) -> Option<Value<'a>> {
+    fold(x, typ, bindings).map_err(|e| bindings.diagnose(
+        Diagnostic::error(&e)
+        .span(Span::range(tokens))
+        .code("const-eval")
+    )).ok()
+}
+
+/// Length of array type, which may be any constant integer expression: `i32[N * 2]`. `tokens` start with '['
+pub fn array_length<'a> (tokens: &'a [Token], body: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let mut values = parse(body, bindings)?;
+    if values.len() != 1 { return Err(error(&tokens[0], bindings, "Expected length of array", "expected-value")) }
+    let x = values.pop().unwrap();
+    let typ = extract_type(&x).unwrap_or(Type::U(bindings.target_ptr_bits() as u32));
+    let (Type::I(_) | Type::U(_)) = typ else { return Err(error(&tokens[0], bindings, "Length of array must be integer", "mismatched-types")) };
+    Ok(match evaluate(&x, &typ, body, bindings).map(|x| eval(&x, &typ, bindings)) {
+        Some(Ok(Const::Int(x))) if x < 0 => { bindings.gentle_error_range(body, "Length of array can't be negative", "invalid-length"); 0 }
+        Some(Ok(Const::Int(x))) => x as usize,
+        _ => 0
+    })
+}
+
+/// Type and value after name of constant or static, value is evaluated
+fn initializer<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, what: &str) -> PResult<(Type, Value<'a>)> {
+    let start = *off - 1;
+    let typ = next_type(off, tokens, bindings)?;
+    if next_token(off, tokens, Some("="), Some(TokenKind::Assign)).is_none() {
+        return Err(error(&tokens[*off-1], bindings, &format!("{what} must be initialized with value"), "missing-initializer"))
+    }
+    let value_start = *off;
+    let Some(value) = next_value(off, tokens, instructions, bindings)? else { return Err(error(&tokens[value_start-1], bindings, &format!("Expected value of {}", what.to_lowercase()), "expected-value")) };
+    let typ = match typ {
+        Some(typ) => { typ.check_strict(&value, &tokens[start..*off], bindings); typ }
+        None => {
+            let typ = default_type(&value);
+            check_literals(&typ, &value, &tokens[start..*off], bindings);
+            typ
+        }
+    };
+    let value = evaluate(&value, &typ, &tokens[value_start..*off], bindings).unwrap_or(value);
+    Ok((typ, value))
+}
+
+/// Remembers constants and statics declared at top level of `tokens`, so their use before declaration is told from unknown name
+pub fn scan_globals<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) {
+    let mut depth = 0;
+    for (i, x) in tokens.iter().enumerate() {
+        match (x.typ, x.value.as_str()) {
+            (TokenKind::Brackets, "(" | "[" | "{") => depth += 1,
+            (TokenKind::Brackets, _) => depth -= 1,
+            (TokenKind::Word, "const" | "static") if depth == 0 => {
+                let what = if x.value == "const" { "Constant" } else { "Static" };
+                let mut off = i + 1;
+                next_mutable_flag(&mut off, tokens);
+                if let Some(name) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) { bindings.declare_ahead(bindings.global_name(&name.value), name, what); }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Binds global declaration by name in current namespace, same as functions
+fn declare<'a> (token: &Token, bind: Bind<'a>, public: bool, bindings: &mut Bindings<'a>) -> PResult<String> {
+    let lname = &token.value;
+    let name = bindings.global_name(lname);
+    if bindings.get_at(0, &name).or(bindings.get_at_current(&name)).is_some() { return Err(error(token, bindings, "Name must be unique in current namespace", "duplicate-name")) }
+    if public { bindings.global_insert(lname, bind); }
+    else {
+        bindings.insert(&name, bind);
+        if lname != &name { bindings.insert(lname, Bind::Alias(name.clone())); }
+    }
+    Ok(name)
+}
+
+/// `const NAME: T = value`. Value is evaluated at compile time and substituted wherever constant is used
+pub fn r#const<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, public: bool) -> PResult<usize> {
+    let mut off = 1;
+    let token = next_token(&mut off, tokens, None, Some(TokenKind::Word)).ok_or_else(|| error(&tokens[0], bindings, "Expected name of constant", "expected-name"))?;
+    let (_, value) = initializer(&mut off, tokens, instructions, bindings, "Constant")?;
+    // in functions constants are local, like variables
+    if bindings.get_context_noval(&Context::Fn(Type::Void)).is_some() { bindings.insert(&token.value, Bind::Const(value)); }
+    else { declare(token, Bind::Const(value), public, bindings)?; }
+    Ok(off)
+}
+
+/// `static NAME: T = value` or `static mut ..`, global variable with value known at compile time
+pub fn r#static<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, public: bool) -> PResult<usize> {
+    let mut off = 1;
+    if bindings.get_context_noval(&Context::Fn(Type::Void)).is_some() {
+        return Err(error(&tokens[0], bindings, "'static' is not allowed in functions", "misplaced-item"))
+    }
+    let mutable = next_mutable_flag(&mut off, tokens);
+    let token = next_token(&mut off, tokens, None, Some(TokenKind::Word)).ok_or_else(|| error(&tokens[0], bindings, "Expected name of static", "expected-name"))?;
+    let (typ, value) = initializer(&mut off, tokens, instructions, bindings, "Static")?;
+    let name = declare(token, Bind::Static(typ.clone(), mutable), public, bindings)?;
+    instructions.push(Value::Static { name, typ, value: Box::new(value), mutable });
+    Ok(off)
+}
\ No newline at end of file
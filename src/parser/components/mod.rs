@@ -7,4 +7,9 @@ pub mod unary;
 pub mod binds;
 pub mod arrays;
 pub mod types;
-pub mod pointer;
\ No newline at end of file
+pub mod pattern;
+pub mod pointer;
+pub mod generics;
+pub mod traits;
+pub mod globals;
+pub mod slices;
\ No newline at end of file
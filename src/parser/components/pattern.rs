@@ -0,0 +1,397 @@
+use std::rc::Rc;
+
+use insordmap::InsordMap;
+
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::{Bind, Bindings}, components::types::force_type, message::{error, PResult, token_at}, numbers::parse_int, simpler::{ignore_separator, next_body_optional, next_token}, r#type::{parse_type, Type}, SharedValue, Value}};
+
+/// Pattern of `match` arm. Types of nested values are kept along for code generation
+#[derive(Debug, Clone)]
+pub enum Pattern<'a> {
+    Wild,
+    Bind(&'a str),
+    /// Inclusive bounds, literal is range of single value
+    Range(i128, i128),
+    Bool(bool),
+    /// Elements of tuple or fields of struct, in order of declaration
+    Fields(Vec<(Pattern<'a>, Type)>),
+    Variant { index: usize, tag: Type, fields: Vec<(Pattern<'a>, Type)> }
+}
+
+#[derive(Debug, Clone)]
+pub struct Arm<'a> {
+    pub pattern: Pattern<'a>,
+    pub guard: Option<Value<'a>>,
+    pub body: Vec<Value<'a>>
+}
+
+impl Pattern<'_> {
+    pub fn is_irrefutable (&self) -> bool {
+        match self {
+            Pattern::Wild | Pattern::Bind(_) => true,
+            Pattern::Fields(x) => x.iter().all(|(p, _)| p.is_irrefutable()),
+            _ => false
+        }
+    }
+}
+
+/// What values of type look like, as far as patterns are concerned
+enum Layout {
+    Bool,
+    Int(i128, i128),
+    Fields(Vec<Type>),
+    Enum(Type, Vec<Vec<Type>>),
+    /// Only wildcards and bindings can match it
+    Opaque
+}
+
+pub fn int_bounds (typ: &Type) -> Option<(i128, i128)> {
+    match typ {
+        Type::I(x) if *x >= 128 => Some((i128::MIN, i128::MAX)),
+        Type::I(x) => Some((-(1 << (x - 1)), (1 << (x - 1)) - 1)),
+        Type::U(x) if *x >= 127 => Some((0, i128::MAX)),
+        Type::U(x) => Some((0, (1 << x) - 1)),
+        _ => None
+    }
+}
+
+fn declaration<'a> (name: &str, bindings: &mut Bindings<'a>) -> PResult<Option<SharedValue<'a>>> {
+    let mut x = bindings.get(name);
+    while let Some(Bind::Alias(l)) = x { x = bindings.get(l); }
+    // global name of type is known even outside of its namespace
+    let l = match x {
+        Some(Bind::Type(Some(l))) => l.clone(),
+        _ => match bindings.get_type(name) { Some(l) => l.clone(), None => return Ok(None) }
+    };
+    force_type(&l, bindings)?;
+    Ok(Some(l))
+}
+
+fn layout<'a> (typ: &Type, bindings: &mut Bindings<'a>) -> PResult<Layout> {
+    if let Some((lo, hi)) = int_bounds(typ) { return Ok(Layout::Int(lo, hi)) }
+    Ok(match typ {
+        Type::Bool => Layout::Bool,
+        Type::Tuple(x) => Layout::Fields(x.clone()),
+        Type::Struct(name) => {
+            let Some(l) = declaration(name, bindings)? else { return Ok(Layout::Opaque) };
+            match &*l.borrow() {
+                Value::Struct { kv, .. } => Layout::Fields(kv.values().cloned().collect()),
+                Value::Enum { variants, tag, .. } => Layout::Enum(tag.clone(), variants.values().map(|x| x.fields.values().cloned().collect()).collect()),
+                _ => Layout::Opaque
+            }
+        }
+        _ => Layout::Opaque
+    })
+}
+
+fn next_int<'a> (off: &mut usize, tokens: &'a [Token], bindings: &Bindings<'a>) -> PResult<Option<i128>> {
+    let negative = next_token(off, tokens, Some("-"), Some(TokenKind::Arithmetic)).is_some();
+    let Some(x) = next_token(off, tokens, None, Some(TokenKind::Int)) else {
+        if negative { return Err(error(&tokens[*off-1], bindings, "Expected integer after '-'", "invalid-literal")) }
+        return Ok(None)
+    };
+    let Ok(x) = i128::try_from(parse_int(x, bindings)?.0) else { return Err(error(x, bindings, "Integer literal is too large", "invalid-literal")) };
+    Ok(Some(if negative { -x } else { x }))
+}
+
+/// Parses pattern for value of type `typ`. Names bound by it are collected into `binds`
+pub fn next_pattern<'a> (off: &mut usize, tokens: &'a [Token], typ: &Type, binds: &mut Vec<(&'a Token, Type)>, bindings: &mut Bindings<'a>) -> PResult<Pattern<'a>> {
+    let Some(token) = tokens.get(*off) else { return Err(error(&tokens[tokens.len()-1], bindings, "Expected pattern", "syntax")) };
+    let start = *off;
+
+    if let Some(lo) = next_int(off, tokens, bindings)? {
+        let Some((min, max)) = int_bounds(typ) else {
+            bindings.gentle_error_range(&tokens[start..*off], &format!("Mismatched types: expected '{}', got integer", typ.display()), "mismatched-types");
+            return Ok(Pattern::Wild)
+        };
+        let hi = if next_token(off, tokens, Some("..="), Some(TokenKind::Special)).is_some() {
+            next_int(off, tokens, bindings)?.ok_or_else(|| error(&tokens[*off-1], bindings, "Expected end of range", "expected-value"))?
+        }
+        else if next_token(off, tokens, Some(".."), Some(TokenKind::Special)).is_some() {
+            next_int(off, tokens, bindings)?.ok_or_else(|| error(&tokens[*off-1], bindings, "Expected end of range", "expected-value"))? - 1
+        }
+        else { lo };
+        if lo > hi {
+            bindings.gentle_error_range(&tokens[start..*off], "Range pattern is empty", "empty-range");
+        }
+        else if lo < min || hi > max {
+            bindings.gentle_error_range(&tokens[start..*off], &format!("Literal is out of range for '{}'", typ.display()), "literal-out-of-range");
+        }
+        return Ok(Pattern::Range(lo.max(min), hi.min(max)))
+    }
+
+    Ok(match (&token.typ, token.value.as_str()) {
+        (TokenKind::Word, "_") => { *off += 1; Pattern::Wild }
+        (TokenKind::Word, "true" | "false") => {
+            *off += 1;
+            if typ != &Type::Bool {
+                bindings.gentle_error(token, &format!("Mismatched types: expected '{}', got 'bool'", typ.display()), "mismatched-types");
+                return Ok(Pattern::Wild)
+            }
+            Pattern::Bool(token.value == "true")
+        }
+        (TokenKind::Brackets, "(") => {
+            let body = next_body_optional(off, tokens, ("(", ")")).unwrap();
+            let Type::Tuple(types) = typ else {
+                bindings.gentle_error_range(&tokens[start..*off], &format!("Mismatched types: expected '{}', got tuple", typ.display()), "mismatched-types");
+                return Ok(Pattern::Wild)
+            };
+            Pattern::Fields(positional(body, types, &tokens[start], binds, bindings)?)
+        }
+        (TokenKind::Word, _) => {
+            let Some((Type::Struct(name), j)) = parse_type(&tokens[*off..], bindings)? else {
+                *off += 1;
+                if let Some((x, _)) = binds.iter().find(|(x, _)| x.value == token.value) {
+                    return Err(error(token, bindings, &format!("Identifier '{}' is bound more than once in the same pattern", x.value), "duplicate-name"))
+                }
+                binds.push((token, typ.clone()));
+                return Ok(Pattern::Bind(&token.value))
+            };
+            *off += j;
+            type_pattern(off, tokens, start, &name, typ, binds, bindings)?
+        }
+        _ => return Err(error(token, bindings, "Expected pattern", "syntax"))
+    })
+}
+
+/// Pattern of struct or enum variant; `off` points after name of type
+fn type_pattern<'a> (off: &mut usize, tokens: &'a [Token], start: usize, name: &str, typ: &Type, binds: &mut Vec<(&'a Token, Type)>, bindings: &mut Bindings<'a>) -> PResult<Pattern<'a>> {
+    let expected = if let Type::Struct(x) = typ { declaration(x, bindings)? } else { None };
+    let Some(l) = declaration(name, bindings)?.filter(|l| expected.is_some_and(|x| Rc::ptr_eq(&x, l))) else {
+        error_mismatch(&tokens[start..*off], typ, bindings);
+        // skipping rest of pattern to report errors from next arms
+        next_token(off, tokens, Some("::"), Some(TokenKind::Special)).and_then(|_| next_token(off, tokens, None, Some(TokenKind::Word)));
+        next_body_optional(off, tokens, ("(", ")")).or_else(|| next_body_optional(off, tokens, ("{", "}")));
+        return Ok(Pattern::Wild)
+    };
+    let decl = l.borrow().clone();
+    Ok(match decl {
+        Value::Struct { kv, .. } => {
+            let Some(body) = next_body_optional(off, tokens, ("{", "}")) else { return Err(error(&tokens[*off-1], bindings, "Expected fields of struct in braces", "syntax")) };
+            Pattern::Fields(named(body, &kv, binds, bindings)?)
+        }
+        Value::Enum { name, variants, tag, .. } => {
+            if next_token(off, tokens, Some("::"), Some(TokenKind::Special)).is_none() { return Err(error(&tokens[*off-1], bindings, "Expected variant of enum after '::'", "expected-name")) }
+            let Some(vt) = next_token(off, tokens, None, Some(TokenKind::Word)) else { return Err(error(&tokens[*off-1], bindings, "Expected name of variant", "expected-name")) };
+            let Some((variant, index)) = variants.get_w_p(&vt.value) else {
+                return Err(error(vt, bindings, &format!("There is no variant \"{}\" in enum {name}", vt.value), "unknown-variant"))
+            };
+            let fields = if variant.named {
+                let Some(body) = next_body_optional(off, tokens, ("{", "}")) else { return Err(error(vt, bindings, "Expected fields of variant in braces", "syntax")) };
+                named(body, &variant.fields, binds, bindings)?
+            }
+            else if let Some(body) = next_body_optional(off, tokens, ("(", ")")) {
+                positional(body, &variant.fields.values().cloned().collect::<Vec<_>>(), vt, binds, bindings)?
+            }
+            else if variant.fields.values().next().is_some() {
+                return Err(error(vt, bindings, &format!("Expected patterns for fields of variant {name}::{} in parentheses", vt.value), "syntax"))
+            }
+            else { vec![] };
+            Pattern::Variant { index, tag, fields }
+        }
+        _ => unreachable!()
+    })
+}
+
+fn error_mismatch (tokens: &[Token], typ: &Type, bindings: &mut Bindings) {
+    bindings.gentle_error_range(tokens, &format!("Mismatched types: pattern doesn't match values of type '{}'", typ.display()), "mismatched-types");
+}
+
+/// `(a, b, ..)`, where `..` stands for all of the rest
+fn positional<'a> (body: &'a [Token], types: &[Type], at: &'a Token, binds: &mut Vec<(&'a Token, Type)>, bindings: &mut Bindings<'a>) -> PResult<Vec<(Pattern<'a>, Type)>> {
+    let mut fields = vec![];
+    let mut off = 0;
+    let mut rest = false;
+    while off < body.len() {
+        if next_token(&mut off, body, Some(".."), Some(TokenKind::Special)).is_some() { rest = true; break }
+        let typ = types.get(fields.len()).cloned().unwrap_or_default();
+        fields.push((next_pattern(&mut off, body, &typ, binds, bindings)?, typ));
+        ignore_separator(&mut off, body);
+    }
+    if fields.len() > types.len() || (!rest && fields.len() != types.len()) {
+        bindings.gentle_error(at, &format!("Pattern expects {} fields, got {}", types.len(), fields.len()), "argument-count");
+    }
+    fields.truncate(types.len());
+    while fields.len() < types.len() { fields.push((Pattern::Wild, types[fields.len()].clone())); }
+    Ok(fields)
+}
+
+/// `{ a pattern, b pattern }`, fields that are not mentioned are matched by wildcard
+fn named<'a> (body: &'a [Token], kv: &InsordMap<String, Type>, binds: &mut Vec<(&'a Token, Type)>, bindings: &mut Bindings<'a>) -> PResult<Vec<(Pattern<'a>, Type)>> {
+    let mut fields: Vec<(Pattern, Type)> = kv.values().map(|t| (Pattern::Wild, t.clone())).collect();
+    let mut seen = vec![false; fields.len()];
+    let mut off = 0;
+    while off < body.len() {
+        if next_token(&mut off, body, Some(".."), Some(TokenKind::Special)).is_some() { break }
+        let Some(key) = next_token(&mut off, body, None, Some(TokenKind::Word)) else { return Err(error(token_at(body, off), bindings, "Expected name of field", "expected-name")) };
+        let Some((typ, index)) = kv.get_w_p(&key.value) else {
+            let available = kv.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(", ");
+            return Err(error(key, bindings, &format!("There is no field \"{}\", available fields: {available}", key.value), "unknown-field"))
+        };
+        if seen[index] { bindings.gentle_error(key, &format!("Field \"{}\" is matched more than once", key.value), "duplicate-name"); }
+        seen[index] = true;
+        // `{ x, y }` is shorthand for `{ x x, y y }`
+        fields[index].0 = if matches!(body.get(off), None | Some(Token { typ: TokenKind::Punctuation, .. })) {
+            off -= 1;
+            next_pattern(&mut off, body, typ, binds, bindings)?
+        }
+        else { next_pattern(&mut off, body, typ, binds, bindings)? };
+        ignore_separator(&mut off, body);
+    }
+    Ok(fields)
+}
+
+// exhaustiveness checking, by usefulness of pattern against previous ones
+
+#[derive(Debug, Clone, PartialEq)]
+enum Ctor { Bool(bool), Range(i128, i128), Single, Variant(usize) }
+
+fn ctor_of (p: &Pattern) -> Option<Ctor> {
+    match p {
+        Pattern::Wild | Pattern::Bind(_) => None,
+        Pattern::Range(a, b) => Some(Ctor::Range(*a, *b)),
+        Pattern::Bool(x) => Some(Ctor::Bool(*x)),
+        Pattern::Fields(_) => Some(Ctor::Single),
+        Pattern::Variant { index, .. } => Some(Ctor::Variant(*index))
+    }
+}
+
+/// Splits `lo..=hi` into ranges, each of which is either fully inside or outside of any given one
+fn split_range (lo: i128, hi: i128, heads: &[&Pattern]) -> Vec<Ctor> {
+    let mut points = vec![lo];
+    for i in heads {
+        if let Pattern::Range(a, b) = i {
+            if *a > lo && *a <= hi { points.push(*a); }
+            if let Some(b) = b.checked_add(1).filter(|b| *b > lo && *b <= hi) { points.push(b); }
+        }
+    }
+    points.sort();
+    points.dedup();
+    points.iter().enumerate().map(|(i, a)| Ctor::Range(*a, points.get(i+1).map(|x| x - 1).unwrap_or(hi))).collect()
+}
+
+fn arity (ctor: &Ctor, layout: &Layout) -> Vec<Type> {
+    match (ctor, layout) {
+        (Ctor::Single, Layout::Fields(x)) => x.clone(),
+        (Ctor::Variant(i), Layout::Enum(_, x)) => x[*i].clone(),
+        _ => vec![]
+    }
+}
+
+fn specialize<'a> (row: &[Pattern<'a>], ctor: &Ctor, arity: usize) -> Option<Vec<Pattern<'a>>> {
+    let mut head = match (&row[0], ctor) {
+        (Pattern::Wild | Pattern::Bind(_), _) => vec![Pattern::Wild; arity],
+        (Pattern::Bool(x), Ctor::Bool(y)) if x == y => vec![],
+        (Pattern::Range(a, b), Ctor::Range(x, y)) if a <= x && y <= b => vec![],
+        (Pattern::Fields(x), Ctor::Single) => x.iter().map(|(p, _)| p.clone()).collect(),
+        (Pattern::Variant { index, fields, .. }, Ctor::Variant(i)) if index == i => fields.iter().map(|(p, _)| p.clone()).collect(),
+        _ => return None
+    };
+    head.extend_from_slice(&row[1..]);
+    Some(head)
+}
+
+/// Returns values (as patterns) matched by `q`, but not by any of `rows`
+fn useful<'a> (rows: &[Vec<Pattern<'a>>], q: &[Pattern<'a>], types: &[Type], bindings: &mut Bindings<'a>) -> PResult<Option<Vec<Pattern<'a>>>> {
+    if q.is_empty() { return Ok(rows.is_empty().then(Vec::new)) }
+    let layout = layout(&types[0], bindings)?;
+    let mut heads: Vec<&Pattern> = rows.iter().map(|x| &x[0]).collect();
+    heads.push(&q[0]);
+
+    let ctors = match (ctor_of(&q[0]), &layout) {
+        (Some(Ctor::Range(a, b)), _) => split_range(a, b, &heads),
+        (Some(x), _) => vec![x],
+        (None, Layout::Bool) => vec![Ctor::Bool(false), Ctor::Bool(true)],
+        (None, Layout::Int(lo, hi)) => split_range(*lo, *hi, &heads),
+        (None, Layout::Fields(_)) => vec![Ctor::Single],
+        (None, Layout::Enum(_, x)) => (0..x.len()).map(Ctor::Variant).collect(),
+        (None, Layout::Opaque) => {
+            let rows = rows.iter().filter(|x| matches!(x[0], Pattern::Wild | Pattern::Bind(_))).map(|x| x[1..].to_vec()).collect::<Vec<_>>();
+            let Some(mut w) = useful(&rows, &q[1..], &types[1..], bindings)? else { return Ok(None) };
+            w.insert(0, Pattern::Wild);
+            return Ok(Some(w))
+        }
+    };
+
+    for ctor in ctors {
+        let fields = arity(&ctor, &layout);
+        let rows = rows.iter().filter_map(|x| specialize(x, &ctor, fields.len())).collect::<Vec<_>>();
+        let Some(q) = specialize(q, &ctor, fields.len()) else { continue };
+        let mut types = [fields.as_slice(), &types[1..]].concat();
+        let Some(mut w) = useful(&rows, &q, &types, bindings)? else { continue };
+        let rest = w.split_off(fields.len());
+        types.truncate(fields.len());
+        let w = w.into_iter().zip(types).collect();
+        let head = match (ctor, &layout) {
+            (Ctor::Bool(x), _) => Pattern::Bool(x),
+            (Ctor::Range(a, b), _) => Pattern::Range(a, b),
+            (Ctor::Variant(index), Layout::Enum(tag, _)) => Pattern::Variant { index, tag: tag.clone(), fields: w },
+            _ => Pattern::Fields(w)
+        };
+        return Ok(Some([vec![head], rest].concat()))
+    }
+    Ok(None)
+}
+
+/// Checks arms (pattern and whether it has guard) of `match` over values of type `typ`. \
+/// Returns indices of arms that can never match and value that is matched by no arm, if there is any
+pub fn check_arms<'a> (arms: &[(Pattern<'a>, bool)], typ: &Type, bindings: &mut Bindings<'a>) -> PResult<(Vec<usize>, Option<String>)> {
+    let mut rows: Vec<Vec<Pattern>> = vec![];
+    let mut unreachable = vec![];
+    let types = [typ.clone()];
+    for (i, (p, guard)) in arms.iter().enumerate() {
+        if useful(&rows, std::slice::from_ref(p), &types, bindings)?.is_none() { unreachable.push(i); }
+        // guard can fail, so arm with it doesn't cover anything
+        if !guard { rows.push(vec![p.clone()]); }
+    }
+    let missing = match useful(&rows, &[Pattern::Wild], &types, bindings)? {
+        Some(x) => Some(display(&x[0], typ, bindings)?),
+        None => None
+    };
+    Ok((unreachable, missing))
+}
+
+fn display<'a> (p: &Pattern<'a>, typ: &Type, bindings: &mut Bindings<'a>) -> PResult<String> {
+    Ok(match p {
+        Pattern::Wild => "_".into(),
+        Pattern::Bind(x) => x.to_string(),
+        Pattern::Range(a, b) => {
+            let (min, max) = int_bounds(typ).unwrap();
+            let bound = |x: i128| match x {
+                x if x == min && x != 0 => format!("{}::MIN", typ.display()),
+                x if x == max => format!("{}::MAX", typ.display()),
+                x => x.to_string()
+            };
+            if (*a, *b) == (min, max) { "_".into() }
+            else if a == b { bound(*a) }
+            else { format!("{}..={}", bound(*a), bound(*b)) }
+        }
+        Pattern::Bool(x) => x.to_string(),
+        Pattern::Fields(x) => {
+            let Type::Struct(name) = typ else {
+                return Ok(format!("({})", x.iter().map(|(p, t)| display(p, t, bindings)).collect::<PResult<Vec<_>>>()?.join(", ")))
+            };
+            let Some(l) = declaration(name, bindings)? else { return Ok("_".into()) };
+            let Value::Struct { name, kv, .. } = l.borrow().clone() else { unreachable!() };
+            format!("{name} {}", display_named(x, &kv, bindings)?)
+        }
+        Pattern::Variant { index, fields, .. } => {
+            let Type::Struct(name) = typ else { unreachable!() };
+            let Some(l) = declaration(name, bindings)? else { return Ok("_".into()) };
+            let Value::Enum { name, variants, .. } = l.borrow().clone() else { unreachable!() };
+            let (vname, variant) = &variants.iter().nth(*index).unwrap();
+            if variant.named { format!("{name}::{vname} {}", display_named(fields, &variant.fields, bindings)?) }
+            else if fields.is_empty() { format!("{name}::{vname}") }
+            else { format!("{name}::{vname}({})", fields.iter().map(|(p, t)| display(p, t, bindings)).collect::<PResult<Vec<_>>>()?.join(", ")) }
+        }
+    })
+}
+
+fn display_named<'a> (fields: &[(Pattern<'a>, Type)], kv: &InsordMap<String, Type>, bindings: &mut Bindings<'a>) -> PResult<String> {
+    let mut shown = vec![];
+    for ((k, _), (p, t)) in kv.iter().zip(fields) {
+        let x = display(p, t, bindings)?;
+        if x != "_" { shown.push(format!("{k}: {x}")); }
+    }
+    if shown.len() < fields.len() { shown.push("..".into()); }
+    Ok(format!("{{ {} }}", shown.join(", ")))
+}
\ No newline at end of file
@@ -1,30 +1,41 @@
-use crate::{lexer::Token, parser::{Value, bindings::{Bind, Bindings}, message::error_range, r#type::Type}};
+use crate::{lexer::Token, parser::{Value, bindings::{Bind, Bindings}, message::{error_range, PResult}, r#type::Type}};
 const MUT_ADDR_MESSAGE: &str = "Cannot take mutable address to immutable data";
 
-pub fn take_pointer<'a> (erange: &'a [Token], bindings: &mut Bindings<'a>, mutable: bool, val: Value<'a>) -> Value<'a> {
+pub fn take_pointer<'a> (erange: &'a [Token], bindings: &mut Bindings<'a>, mutable: bool, val: Value<'a>) -> PResult<Value<'a>> {
     if let Value::Get(name, _) = val {
         if mutable {
-            let Bind::Let(_, true) = bindings.get(name).unwrap() else { error_range(&erange, bindings, MUT_ADDR_MESSAGE) };
-            return Value::Ptr(Box::new(val), true)
+            let Bind::Let(_, true) = bindings.get(name).unwrap() else { return Err(error_range(&erange, bindings, MUT_ADDR_MESSAGE, "immutable-borrow")) };
+            return Ok(Value::Ptr(Box::new(val), true))
         }
-        return Value::Ptr(Box::new(val), false)
+        return Ok(Value::Ptr(Box::new(val), false))
     }
     else if let Value::LoadFromPtr(x, _) = val {
-        if mutable { Type::Ptr(None, true).check(&x).unwrap_or_else(|| error_range(&erange, bindings, MUT_ADDR_MESSAGE)); }
+        if mutable { Type::Ptr(None, true).check(&x).ok_or_else(|| error_range(&erange, bindings, MUT_ADDR_MESSAGE, "immutable-borrow"))?; }
         else {
             match *x {
                 Value::LoadAddress(a, b, c, true) => {
-                    return Value::LoadAddress(a, b, c, false)
+                    return Ok(Value::LoadAddress(a, b, c, false))
                 }
                 Value::Ptr(a, true) => {
-                    return Value::Ptr(a, false)
+                    return Ok(Value::Ptr(a, false))
                 },
+                Value::StaticPtr(a, b, true) => return Ok(Value::StaticPtr(a, b, false)),
                 _ => {}
             }
         }
-        return *x
+        return Ok(*x)
+    }
+    // `&x[a..b]` is the slice itself
+    else if let Value::MakeSlice(x, Type::Slice(t, m)) = val {
+        if mutable && !m { return Err(error_range(erange, bindings, MUT_ADDR_MESSAGE, "immutable-borrow")) }
+        Ok(Value::MakeSlice(x, Type::Slice(t, mutable)))
+    }
+    // string literal is already read-only slice
+    else if let Value::String(_) = val {
+        if mutable { return Err(error_range(erange, bindings, MUT_ADDR_MESSAGE, "immutable-borrow")) }
+        Ok(val)
     }
     else {
-        return if mutable { Value::Ptr(Box::new(val), true) } else { Value::Ptr(Box::new(val), false) }
+        return Ok(if mutable { Value::Ptr(Box::new(val), true) } else { Value::Ptr(Box::new(val), false) })
     }
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::Bindings, components::control_flow::literal, message::{error_range, PResult}, simpler::next_value, r#type::{extract_type, Type}, Operation, Value}};
+
+fn usize (bindings: &Bindings) -> Type { Type::U(bindings.target_ptr_bits() as u32) }
+
+fn length<'a> (n: usize, bindings: &Bindings) -> Value<'a> {
+    Value::Typecast(Box::new(Value::Int(n as u128)), usize(bindings), usize(bindings))
+}
+
+/// Elements of slice are reached as if it was array of unknown length
+pub fn elements_array (el: Box<Type>) -> Type { Type::Array(el, 0) }
+
+/// Converts `&T[n]` to `&[T]` where the latter is expected, anything else is returned as is
+pub fn to_slice<'a> (typ: &Type, value: Value<'a>, bindings: &Bindings) -> Value<'a> {
+    let Type::Slice(t, mutable) = typ else { return value };
+    let Some(Type::Ptr(Some(a), m)) = extract_type(&value) else { return value };
+    // mismatch of anything else is up to caller
+    let Type::Array(e, n) = *a else { return value };
+    if e != *t || *mutable && !m { return value }
+    Value::MakeSlice(Box::new((value, length(n, bindings))), typ.clone())
+}
+
+/// Slice passed to 'extern' function, which expects 'ptr', is pointer to its first element
+pub fn to_c_ptr<'a> (typ: &Type, value: Value<'a>) -> Value<'a> {
+    match (typ, extract_type(&value)) {
+        (Type::Ptr(None, _), Some(Type::Slice(_, _))) => Value::SliceData(Box::new(value)),
+        _ => value
+    }
+}
+
+/// Position of '..' in brackets of indexing, outside of any nested brackets
+pub fn range_dots (body: &[Token]) -> Option<usize> {
+    let mut depth = 0;
+    for (i, x) in body.iter().enumerate() {
+        match (x.typ, x.value.as_str()) {
+            (TokenKind::Brackets, "(" | "[" | "{") => depth += 1,
+            (TokenKind::Brackets, _) => depth -= 1,
+            (TokenKind::Special, "..") if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Bound of range in `s[a..b]`, None if it's omitted
+fn bound<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<Option<Value<'a>>> {
+    if tokens.is_empty() { return Ok(None) }
+    let mut off = 0;
+    let Some(x) = next_value(&mut off, tokens, instructions, bindings)? else { return Err(error_range(tokens, bindings, "Expected bound of range", "expected-value")) };
+    if off != tokens.len() { return Err(error_range(&tokens[off..], bindings, "Unexpected tokens in bound of range", "syntax")) }
+    usize(bindings).check_strict(&x, tokens, bindings);
+    Ok(Some(x))
+}
+
+/// `x[a..b]`, `x[a..]`, `x[..b]` or `x[..]` of array or slice: slice of elements from `a` up to `b` exclusive. \
+/// `tokens` are the whole brackets and `dots` points to '..' in `body`
+pub fn subslice<'a> (from: Value<'a>, tokens: &'a [Token], body: &'a [Token], dots: usize, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<Value<'a>> {
+    let start = bound(&body[..dots], instructions, bindings)?;
+    let end = bound(&body[dots+1..], instructions, bindings)?;
+    let (from, typ, mutable) = match extract_type(&from) {
+        Some(Type::Ptr(Some(x), m)) if matches!(*x, Type::Array(_, _)) => (Value::LoadFromPtr(Box::new(from), *x.clone()), *x, m),
+        Some(x) => (from, x, mutable),
+        None => return Err(error_range(tokens, bindings, "Unindexable value", "invalid-access"))
+    };
+    let (elements, len, el, mutable) = match typ {
+        Type::Array(ref el, n) => {
+            let from = if let Value::LoadFromPtr(x, _) = from { *x } else { from };
+            (from, length(n, bindings), el.clone(), mutable)
+        }
+        Type::Slice(ref el, m) => (Value::SliceData(Box::new(from.clone())), Value::SliceLen(Box::new(from), usize(bindings)), el.clone(), m),
+        _ => return Err(error_range(tokens, bindings, &format!("Can't take slice of '{}', expected array or slice", typ.display()), "mismatched-types"))
+    };
+    let end = end.unwrap_or(len);
+    let len = match &start {
+        Some(start) => {
+            if let (Some(a), Some(b)) = (literal(start), literal(&end)) && a > b {
+                bindings.gentle_error_range(tokens, &format!("Start of range {a} is past its end {b}"), "invalid-range");
+            }
+            // reversed range is empty at runtime, instead of having wrapped length
+            let ordered = Value::Expr(Box::new((end.clone(), start.clone(), Operation::GT)));
+            let difference = Value::Expr(Box::new((end, start.clone(), Operation::Sub)));
+            let empty = Value::Else(vec![length(0, bindings)]);
+            Value::Valued(Box::new(Value::If { condition: Box::new(ordered), body: vec![difference], else_then: Box::new(Some(empty)) }), usize(bindings))
+        }
+        None => end
+    };
+    let start = start.unwrap_or_else(|| length(0, bindings));
+    let array = if let Type::Slice(_, _) = typ { elements_array(el.clone()) } else { typ };
+    Ok(Value::MakeSlice(Box::new((Value::LoadAddress(Box::new(elements), Box::new(start), array, mutable), len)), Type::Slice(el, mutable)))
+}
\ No newline at end of file
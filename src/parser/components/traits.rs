@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::{lexer::{Token, TokenKind}, parser::{bindings::{Bind, Bindings}, components::{binds::join_by_path, control_flow::retype, generics::has_param, slices::to_slice}, message::{error, error_range, Diagnostic, FatalError, PResult, Span, token_at}, parse_inplace, simpler::{ignore_separator, next_args, next_body, next_token, next_type, next_uniform_call}, r#type::{extract_type, Type}, SharedValue, Value}, strip_alias_get};
+
+pub fn r#trait<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>, public: bool) -> PResult<usize> {
+    let mut off = 1;
+
+    let Token { value: lname, .. } = next_token(&mut off, tokens, None, Some(TokenKind::Word))
+    .ok_or_else(|| error(&tokens[0], bindings, "Trait must have name", "expected-name"))?;
+
+    let name = bindings.global_name(lname);
+    if bindings.get_at(0, &name).is_some() { return Err(error(&tokens[1], bindings, "Trait name must be unique in current namespace", "duplicate-name")) }
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+
+    let mut methods: Vec<(&str, &[Token], &Token)> = vec![];
+    let mut boff = 0;
+    while boff < body.len() {
+        let Some(token) = next_token(&mut boff, body, Some("fn"), Some(TokenKind::Word)) else {
+            return Err(error(token_at(body, boff), bindings, "Expected method declaration: 'fn name (args) ret;'", "syntax"))
+        };
+        let Some(Token { value: method, .. }) = next_token(&mut boff, body, None, Some(TokenKind::Word)) else { return Err(error(token, bindings, "Expected name of method", "expected-name")) };
+        if methods.iter().any(|x| x.0 == method) { bindings.gentle_error(&body[boff-1], &format!("Method '{method}' is declared twice in trait '{lname}'"), "duplicate-name"); }
+        let start = boff;
+        while body.get(boff).is_some_and(|x| x.value != ";") { boff += 1; }
+        methods.push((method, &body[start..boff], token));
+        ignore_separator(&mut boff, body);
+    }
+
+    let sv = SharedValue::new(Value::Trait { name: name.clone(), methods }.into());
+    bindings.declare_trait(name.clone(), sv.clone());
+    if public { bindings.global_insert(lname, Bind::Trait(sv)); }
+    else {
+        bindings.insert(&name, Bind::Trait(sv));
+        if lname != &name { bindings.insert(lname, Bind::Alias(name)); }
+    }
+    Ok(off)
+}
+
+/// Finds trait by path as it's written in code, e.g. `geo::Shape`
+pub fn resolve_trait<'a> (path: &str, bindings: &Bindings<'a>) -> Option<SharedValue<'a>> {
+    let mut segments = path.split("::");
+    let mut name = segments.next()?.to_string();
+    loop {
+        match strip_alias_get!(name, bindings) {
+            Some(Bind::Trait(l)) => return segments.next().is_none().then(|| l.clone()),
+            Some(Bind::Namespace(_)) => { name += "::"; name += segments.next()?; }
+            _ => return None
+        }
+    }
+}
+
+/// Argument types and return type of trait method, where `Self` is `typ`
+pub fn method_signature<'a> (r#trait: &str, header: &'a [Token], typ: &Type, bindings: &mut Bindings<'a>) -> PResult<(Vec<Type>, Type)> {
+    join_by_path(r#trait, bindings, |bindings| {
+        bindings.push_scope(HashMap::from([("Self".into(), Bind::Generic(typ.clone()))]));
+        let mut off = 0;
+        let args = next_args(&mut off, header, bindings)?.into_iter().map(|x| x.typ).collect();
+        let ret = next_type(&mut off, header, bindings)?.unwrap_or(Type::Void);
+        bindings.pop_scope();
+        Ok((args, ret))
+    })
+}
+
+fn declaration<'a> (bind: &Bind<'a>) -> Option<&'a Token> {
+    let (Bind::Function(_, _, Some(l)) | Bind::Template(l)) = bind else { return None };
+    match &*l.borrow() {
+        Value::PromisedFunction { token, .. } | Value::FnTemplate { token, .. } => Some(*token),
+        _ => None
+    }
+}
+
+/// `impl Trait for Type { .. }`, `off` points right after `for`
+pub fn impl_trait<'a> (off: &mut usize, tokens: &'a [Token], path: &str, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    let Some(l) = resolve_trait(path, bindings) else { return Err(error_range(&tokens[1..*off-1], bindings, &format!("'{path}' is not a trait"), "unknown-name")) };
+    let Some(typ) = next_type(off, tokens, bindings)? else { return Err(error(&tokens[*off-1], bindings, "Expected type to implement trait for", "expected-type")) };
+    let head = &tokens[..*off];
+    let (r#trait, methods) = match &*l.borrow() { Value::Trait { name, methods } => (name.clone(), methods.clone()), _ => unreachable!() };
+    let body = next_body(off, tokens, bindings, ("{", "}"))?;
+    if bindings.implements(&typ, &r#trait) {
+        // methods of duplicate would only clash with ones already implemented
+        bindings.gentle_error_range(head, &format!("Trait '{trait}' is already implemented for '{}'", typ.display()), "duplicate-impl");
+        return Ok(())
+    }
+
+    // methods are named after type and trait, so they don't collide with ones from other impls
+    bindings.join_scope();
+    bindings.insert("Self", Bind::Generic(typ.clone()));
+    bindings.push_global_prefix(format!("<{} as {trait}>", typ.display()));
+    parse_inplace(body, instructions, bindings)?;
+    bindings.pop_global_prefix();
+    let scope = bindings.pop_scope();
+
+    let mut declared = vec![];
+    for (lname, bind) in &scope {
+        let Bind::Alias(global) = bind else { continue };
+        let Some(bind) = scope.get(global).or_else(|| bindings.get_at(0, global)).cloned() else { continue };
+        let Some(token) = declaration(&bind) else { continue };
+        declared.push((token, lname, global, bind));
+    }
+    declared.sort_by_key(|x| (x.0.line, x.0.col));
+
+    let mut implemented = HashMap::new();
+    for (token, lname, global, bind) in declared {
+        let Some((_, header, declaration)) = methods.iter().find(|x| x.0 == lname) else {
+            bindings.gentle_error(token, &format!("Method '{lname}' is not a member of trait '{trait}'"), "unknown-method");
+            continue
+        };
+        let Bind::Function(argt, ret, _) = &bind else {
+            bindings.gentle_error(token, "Method implementing trait can't be generic", "incompatible-method");
+            continue
+        };
+        let (eargt, eret) = method_signature(&r#trait, header, &typ, bindings)?;
+        if eargt != *argt || eret != *ret {
+            bindings.diagnose(
+                Diagnostic::error(&format!("Method '{lname}' doesn't match its declaration in trait '{trait}'"))
+                .span(Span::token(token))
+                .label(Span::token(declaration), "declared here")
+                .note(&format!("expected '{}', found '{}'", Type::Fn(eargt, eret.into()).display(), Type::Fn(argt.clone(), ret.clone().into()).display()))
+                .code("incompatible-method")
+            );
+        }
+        // visible wherever type is, as trait itself
+        bindings.root_insert(global, bind);
+        implemented.insert(lname.clone(), global.clone());
+    }
+    for (method, header, _) in &methods {
+        if implemented.contains_key(*method) { continue }
+        let (argt, ret) = method_signature(&r#trait, header, &typ, bindings)?;
+        bindings.diagnose(
+            Diagnostic::error(&format!("Method '{method}' of trait '{trait}' is not implemented for '{}'", typ.display()))
+            .span(Span::range(head))
+            .note(&format!("expected '{}'", Type::Fn(argt, ret.into()).display()))
+            .code("missing-method")
+        );
+    }
+    bindings.add_impl(typ, r#trait, implemented);
+    Ok(())
+}
+
+/// Function implementing method for type through some trait
+pub fn find_method (typ: &Type, method: &str, token: &Token, bindings: &Bindings) -> PResult<Option<String>> {
+    let found = bindings.find_methods(typ, method);
+    if found.len() > 1 {
+        return Err(bindings.fatal(
+            Diagnostic::error(&format!("Method '{method}' of '{}' is ambiguous", typ.display()))
+            .span(Span::token(token))
+            .note(&format!("it's provided by traits {}", found.iter().map(|x| format!("'{}'", x.0)).collect::<Vec<_>>().join(", ")))
+            .code("ambiguous-method")
+        ))
+    }
+    Ok(found.first().map(|x| x.1.to_string()))
+}
+
+/// Signature of trait method called through vtable, where receiver `&Self` is pointer to unknown type.
+/// None if method can't be called this way
+fn dyn_signature<'a> (r#trait: &str, header: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<(Vec<Type>, Type)>> {
+    let (mut argt, ret) = method_signature(r#trait, header, &Type::Param(0), bindings)?;
+    let Some(Type::Ptr(Some(x), mutable)) = argt.first() else { return Ok(None) };
+    if **x != Type::Param(0) { return Ok(None) }
+    argt[0] = Type::Ptr(None, *mutable);
+    if argt.iter().chain([&ret]).any(|x| has_param(x, bindings)) { return Ok(None) }
+    Ok(Some((argt, ret)))
+}
+
+fn dyn_incompatible (r#trait: &str, method: &str, tokens: &[Token], bindings: &Bindings) -> FatalError {
+    bindings.fatal(
+        Diagnostic::error(&format!("Trait '{trait}' can't be used with 'dyn'"))
+        .span(Span::range(tokens))
+        .note(&format!("method '{method}' must take '&Self' or '&mut Self' as first argument and not use 'Self' anywhere else"))
+        .code("dyn-incompatible")
+    )
+}
+
+fn trait_methods<'a> (r#trait: &str, bindings: &Bindings<'a>) -> Vec<(&'a str, &'a [Token], &'a Token)> {
+    let Value::Trait { methods, .. } = &*bindings.get_trait(r#trait).unwrap().borrow() else { unreachable!() };
+    methods.clone()
+}
+
+/// Converts `&T` to `&dyn Trait` (or to slice) where the latter is expected, anything else is returned as is. \
+/// Literals that `if`, block or `loop` result in take expected type as well
+pub fn coerce<'a> (typ: &Type, value: Value<'a>, tokens: &[Token], bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    if let Value::Valued(_, _) = value { return Ok(retype(typ, value)) }
+    if let Type::Slice(_, _) = typ { return Ok(to_slice(typ, value, bindings)) }
+    let Type::Dyn(r#trait, mutable) = typ else { return Ok(value) };
+    // mismatch of anything else is up to caller
+    let Some(Type::Ptr(Some(t), m)) = extract_type(&value) else { return Ok(value) };
+    if *mutable && !m { return Ok(value) }
+    let Some(implemented) = bindings.impl_methods(&t, r#trait).cloned() else {
+        bindings.diagnose(
+            Diagnostic::error(&format!("Type '{}' doesn't implement trait '{trait}'", t.display()))
+            .span(Span::range(tokens))
+            .note(&format!("required to use it as '{}'", typ.display()))
+            .code("unsatisfied-bound")
+        );
+        return Ok(Value::DynCast(Box::new(value), vec![], typ.clone()))
+    };
+    let mut vtable = vec![];
+    for (method, header, _) in trait_methods(r#trait, bindings) {
+        if dyn_signature(r#trait, header, bindings)?.is_none() { return Err(dyn_incompatible(r#trait, method, tokens, bindings)) }
+        // missing method is already reported
+        let Some(name) = implemented.get(method) else { continue };
+        if let Some(Bind::Function(_, _, Some(l))) = bindings.get_at(0, name) { bindings.push_function(l.clone()); }
+        vtable.push(name.clone());
+    }
+    Ok(Value::DynCast(Box::new(value), vtable, typ.clone()))
+}
+
+/// Call of method through vtable of `&dyn Trait` value, `off` points right after name of method
+pub fn dyn_method<'a> (off: &mut usize, from: Value<'a>, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    let Some(Type::Dyn(r#trait, mutable)) = extract_type(&from) else { unreachable!() };
+    let method = &tokens[*off-1].value;
+    let methods = trait_methods(&r#trait, bindings);
+    let Some(index) = methods.iter().position(|x| x.0 == method) else {
+        return Err(error(&tokens[*off-1], bindings, &format!("There is no method '{method}' in trait '{trait}'"), "unknown-method"))
+    };
+    let Some((argt, ret)) = dyn_signature(&r#trait, methods[index].1, bindings)? else { return Err(dyn_incompatible(&r#trait, method, &tokens[..*off], bindings)) };
+    let receiver = argt[0] == Type::Ptr(None, true);
+    if receiver && !mutable {
+        bindings.gentle_error_range(&tokens[..*off], &format!("Method '{method}' takes '&mut Self', but it's called through '&dyn {trait}'"), "immutable-receiver");
+    }
+    instructions.push(Value::VtableMethod(Box::new(from.clone()), index, Type::Fn(argt, ret.into())));
+    // mutability is already reported
+    next_uniform_call(off, Value::DynData(Box::new(from), receiver), tokens, instructions, bindings)
+}
\ No newline at end of file
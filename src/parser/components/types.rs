@@ -2,126 +2,341 @@ use std::collections::HashMap;
 
 use insordmap::InsordMap;
 
-use crate::{lexer::{Token, TokenKind}, parser::{SharedValue, Value, bindings::{Bind, Bindings}, components::binds::join_by_path, message::{error, error_range}, simpler::{ignore_separator, next_body, next_body_optional, next_token, next_type, next_value}, r#type::{Type, extract_type, parse_type}}};
+use crate::{lexer::{Token, TokenKind}, parser::{SharedValue, Value, bindings::{Bind, Bindings}, components::{binds::join_by_path, generics::{generic_scope, next_generic_params}}, lint::Lint, message::{error, error_range, Diagnostic, PResult, Span, token_at}, simpler::{ignore_separator, next_body, next_body_optional, next_token, next_type, next_value}, r#type::{Type, default_type, extract_type, parse_type}}};
 
 const VALUE_REQUIRED_ERR: &str = "Typecast operation requires value on left side";
 
-pub fn typecast<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn typecast<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
-    let (to, toff) = parse_type(&tokens[off..], bindings).unwrap_or_else(|| error(&tokens[off], bindings, "Expected type"));
+    let (to, toff) = parse_type(&tokens[off..], bindings)?.ok_or_else(|| error(token_at(tokens, off), bindings, "Expected type", "expected-type"))?;
     off += toff;
     
-    let value = instructions.pop().unwrap_or_else(|| error(&tokens[0], bindings, VALUE_REQUIRED_ERR));
+    let value = instructions.pop().ok_or_else(|| error(&tokens[0], bindings, VALUE_REQUIRED_ERR, "expected-value"))?;
     // dbg!(&value);
     // let from = default_type(&value);
     let from = if let Some(x) = extract_type(&value) { x }
     else if let Some(_) = to.check(&value) { to.clone() }
-    else { todo!() };
+    else { default_type(&value) };
 
     match (&from, &to) {
-        (Type::I(_) | Type::U(_) | Type::Bool, Type::U(_) | Type::I(_) | Type::Bool)
+        (Type::F(_), Type::Bool) | (Type::Bool, Type::F(_)) => return Err(error_range(&tokens[1..off], bindings, "Typecast between 'bool' and floating point types is not available", "invalid-cast")),
+        (Type::I(_) | Type::U(_) | Type::F(_) | Type::Bool, Type::U(_) | Type::I(_) | Type::F(_) | Type::Bool)
         => instructions.push(Value::Typecast(Box::new(value), from, to)),
-        _ => error_range(&tokens[1..off], bindings, "Typecast only available for primitive number-types")
+        _ => return Err(error_range(&tokens[1..off], bindings, "Typecast only available for primitive number-types", "invalid-cast"))
     }
 
-    off
+    Ok(off)
 }
 
-pub fn r#struct<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+/// Binds type declared with global name `name` by its local name `lname`, outside of its namespace it's visible if it's `public`
+fn declare_type<'a> (lname: &str, name: &str, bind: Bind<'a>, public: bool, bindings: &mut Bindings<'a>) {
+    if let Bind::Type(Some(l)) = &bind { bindings.declare_type(name.into(), l.clone()); }
+    if public { bindings.global_insert(lname, bind); }
+    else {
+        bindings.insert(name, bind);
+        if lname != name { bindings.insert(lname, Bind::Alias(name.into())); }
+    }
+}
+
+pub fn r#struct<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, public: bool) -> PResult<usize> {
     let mut off = 1;
 
     let Token { value: name, .. } = next_token(&mut off, tokens, None, Some(TokenKind::Word))
-    .unwrap_or_else(|| error(&tokens[0], bindings, "Struct must have name"));
+    .ok_or_else(|| error(&tokens[0], bindings, "Struct must have name", "expected-name"))?;
 
     let global_name = bindings.global_name(&name);
+    if let Some((params, bounds)) = next_generic_params(&mut off, tokens, bindings)? {
+        let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+        let sv = SharedValue::new(Value::StructTemplate { name: global_name.clone(), params, bounds, body, instances: vec![] }.into());
+        declare_type(name, &global_name, Bind::Type(Some(sv.clone())), public, bindings);
+        instructions.push(Value::SharedValue(sv));
+        return Ok(off)
+    }
+    // opaque type, it isn't defined in IR, as only pointers to it are used
     let Some(body) = next_body_optional(&mut off, tokens, ("{", "}")) else {
-        instructions.push(Value::Struct { name: global_name, kv: InsordMap::new(), alignment: 1, size: 1 });
-        bindings.global_insert(name, Bind::Type(None));
-        return off
+        declare_type(name, &global_name, Bind::Type(None), public, bindings);
+        return Ok(off)
     };
     let sv = SharedValue::new(Value::PromisedStruct {
-        name: global_name,
+        name: global_name.clone(),
+        body,
+        generics: vec![]
+    }.into());
+
+    declare_type(name, &global_name, Bind::Type(Some(sv.clone())), public, bindings);
+    bindings.push_type(sv.clone());
+    instructions.push(Value::SharedValue(sv));
+
+    Ok(off)
+}
+
+/// Variant of enum. Fields of tuple-like variants are named by their position
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub fields: InsordMap<String, Type>,
+    pub named: bool
+}
+
+pub fn r#enum<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, public: bool) -> PResult<usize> {
+    let mut off = 1;
+
+    let Token { value: lname, .. } = next_token(&mut off, tokens, None, Some(TokenKind::Word))
+    .ok_or_else(|| error(&tokens[0], bindings, "Enum must have name", "expected-name"))?;
+
+    let name = bindings.global_name(lname);
+    if bindings.get_at(0, &name).is_some() { return Err(error(&tokens[1], bindings, "Enum name must be unique in current namespace", "duplicate-name")) }
+    let body = next_body(&mut off, tokens, bindings, ("{", "}"))?;
+    let sv = SharedValue::new(Value::PromisedEnum {
+        name: name.clone(),
         body
     }.into());
 
-    // maybe redo
-    bindings.global_insert(name, Bind::Type(Some(sv.clone())));
+    declare_type(lname, &name, Bind::Type(Some(sv.clone())), public, bindings);
     bindings.push_type(sv.clone());
     instructions.push(Value::SharedValue(sv));
 
-    off
+    Ok(off)
 }
 
-pub fn parse_struct<'a> (name: String, body: &'a [Token], bindings: &mut Bindings<'a>) -> Value<'a> {
+/// Parses body of promised type declaration, anything else is returned as is
+pub fn parse_type_body<'a> (value: Value<'a>, bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    Ok(match value {
+        Value::PromisedStruct { name, body, generics } => {
+            bindings.push_scope(generic_scope(&generics));
+            let x = parse_struct(name, body, bindings)?;
+            bindings.pop_scope();
+            x
+        }
+        Value::PromisedEnum { name, body } => parse_enum(name, body, bindings)?,
+        x => x
+    })
+}
+
+pub fn force_type<'a> (l: &SharedValue<'a>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    if let Value::PromisedStruct { .. } | Value::PromisedEnum { .. } = &*l.borrow() {}
+    else { return Ok(()) }
+    let x = l.replace(Value::Unreachable);
+    l.replace(parse_type_body(x, bindings)?);
+    Ok(())
+}
+
+/// Type without values, that can't be stored in field
+fn unsized_part (typ: &Type) -> Option<&Type> {
+    match typ {
+        Type::Void | Type::Noret => Some(typ),
+        Type::Array(x, _) => unsized_part(x),
+        Type::Tuple(x) => x.iter().find_map(unsized_part),
+        _ => None
+    }
+}
+
+fn next_field_type<'a> (off: &mut usize, name: &str, body: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<Type>> {
+    let point = *off;
+    // quite unefficient. todo: optimize somehow to get this operation (join_by_path) from loop
+    let Some(value) = join_by_path(name, bindings, |bindings| next_type(off, body, bindings))? else { return Ok(None) };
+    if let Some(x) = unsized_part(&value) {
+        return Err(bindings.fatal(
+            Diagnostic::error(&format!("Field can't be of type '{}', as it has no values", value.display()))
+            .span(Span::range(&body[point..*off]))
+            .note(&format!("'{}' is only used as return type of functions", x.display()))
+            .code("unsized-field")
+        ))
+    }
+    if let Type::Struct(l) = &value && let Some(x) = bindings.get_type(l) {
+        validate_struct(x.clone(), bindings, name, l, &body[point])?;
+    }
+    Ok(Some(value))
+}
+
+fn parse_fields<'a> (name: &str, body: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<InsordMap<String, Type>> {
     let mut kv = InsordMap::new();
     let mut off = 0;
     while let Some(Token { value: key, .. }) = next_token(&mut off, body, None, Some(TokenKind::Word)) {
-        let point = off;
-        // quite unefficient. todo: optimize somehow to get this operation (join_by_path) from loop
-        if let Some(value) = join_by_path(&name, bindings, |bindings| next_type(&mut off, body, bindings)) {
-            if let Type::Struct(l) = &value {
-                let Bind::Type(x) = bindings.get(l).unwrap() else { unreachable!() };
-                if let Some(x) = x {
-                    validate_struct(x.clone(), bindings, &name, l, &body[point]);
-                }
-            }
+        if let Some(value) = next_field_type(&mut off, name, body, bindings)? {
             kv.insert(key.clone(), value);
             ignore_separator(&mut off, body);
         }
         else {
-            error_range(&body[off-1..=(body.len()-1).min(off)], bindings, &format!("Expected type after field \"{key}\""))
+            return Err(error_range(&body[off-1..=(body.len()-1).min(off)], bindings, &format!("Expected type after field \"{key}\""), "expected-type"))
         }
     }
+    Ok(kv)
+}
+
+pub fn parse_struct<'a> (name: String, body: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    let kv = parse_fields(&name, body, bindings)?;
 
     let alignment = kv.values().fold(1, |acc, x| acc.max(x.alignment(bindings)));
     let size = kv.values().fold(0, |acc, x| acc + (x.sizeof(bindings) + alignment as usize - 1) & !(alignment as usize - 1));
-    Value::Struct { name, kv, alignment, size: size.max(1) }
+    Ok(Value::Struct { name, kv, alignment, size: size.max(1) })
 }
 
-fn validate_struct<'a> (x: SharedValue<'a>, bindings: &mut Bindings<'a>, from: &str, to: &str, tk: &'a Token) {
-    let mut l = x.replace(Value::Unreachable);
-    if let Value::PromisedStruct { name, body } = l {
-        l = parse_struct(name, body, bindings);
-    }
-
-    if let Value::Struct { name: _stn, kv, .. } = &l {
-        for typ in kv.values() {
-            if let Type::Struct(name) = typ {
-                if let Some(Bind::Type(Some(l))) = bindings.get(name) {
-                    validate_struct(l.clone(), bindings, from, to, tk);
-                }
+pub fn parse_enum<'a> (name: String, body: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    let mut variants = InsordMap::new();
+    let mut off = 0;
+    while off < body.len() {
+        let Some(vt @ Token { value: key, .. }) = next_token(&mut off, body, None, Some(TokenKind::Word)) else {
+            return Err(error(token_at(body, off), bindings, "Expected variant name", "expected-name"))
+        };
+        let variant = if let Some(fields) = next_body_optional(&mut off, body, ("(", ")")) {
+            let mut kv = InsordMap::new();
+            let mut foff = 0;
+            let mut i = 0usize;
+            while foff < fields.len() {
+                let Some(typ) = next_field_type(&mut foff, &name, fields, bindings)? else {
+                    return Err(error(token_at(fields, foff), bindings, &format!("Expected type in variant \"{key}\""), "expected-type"))
+                };
+                kv.insert(i.to_string(), typ);
+                i += 1;
+                ignore_separator(&mut foff, fields);
             }
+            Variant { fields: kv, named: false }
+        }
+        else if let Some(fields) = next_body_optional(&mut off, body, ("{", "}")) {
+            Variant { fields: parse_fields(&name, fields, bindings)?, named: true }
         }
+        else { Variant { fields: InsordMap::new(), named: false } };
+        if variants.insert(key.clone(), variant).is_some() {
+            bindings.gentle_error(vt, &format!("Variant \"{key}\" is declared more than once"), "duplicate-name");
+        }
+        ignore_separator(&mut off, body);
     }
-    else {
-        error(tk, bindings, &format!("Recursive type has infinite size: {from} <-> {to}"));
+
+    let tag = match variants.values().count() {
+        0..=256 => Type::U(8),
+        257..=65536 => Type::U(16),
+        _ => Type::U(32)
+    };
+    // payload is laid out as tuple of biggest variant, right after the tag
+    let (mut palign, mut psize) = (1, 0);
+    for v in variants.values() {
+        let t = Type::Tuple(v.fields.values().cloned().collect());
+        palign = palign.max(t.alignment(bindings));
+        psize = psize.max(t.sizeof(bindings));
+    }
+    let psize = (psize + palign as usize - 1) & !(palign as usize - 1);
+    let alignment = tag.alignment(bindings).max(palign);
+    let offset = (tag.sizeof(bindings) + palign as usize - 1) & !(palign as usize - 1);
+    let size = (offset + psize + alignment as usize - 1) & !(alignment as usize - 1);
+    Ok(Value::Enum { name, variants, tag, payload: (palign, psize), alignment, size })
+}
+
+fn validate_struct<'a> (x: SharedValue<'a>, bindings: &mut Bindings<'a>, from: &str, to: &str, tk: &'a Token) -> PResult<()> {
+    let l = parse_type_body(x.replace(Value::Unreachable), bindings)?;
+
+    let fields: Vec<&Type> = match &l {
+        Value::Struct { kv, .. } => kv.values().collect(),
+        Value::Enum { variants, .. } => variants.values().flat_map(|v| v.fields.values()).collect(),
+        _ => return Err(error(tk, bindings, &format!("Recursive type has infinite size: {from} <-> {to}"), "recursive-type"))
+    };
+    for typ in fields {
+        if let Type::Struct(name) = typ && let Some(l) = bindings.get_type(name) {
+            validate_struct(l.clone(), bindings, from, to, tk)?;
+        }
     }
 
     // dbg!(&l);
     x.replace(l);
+    Ok(())
 }
 
-pub fn struct_init<'a> (l: SharedValue<'a>, body: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
-    let x = l.replace(Value::Unreachable);
-    if let Value::PromisedStruct { name, body } = x {
-        l.replace(parse_struct(name, body, bindings));
-    }
-    else { l.replace(x); }
+pub fn struct_init<'a> (l: SharedValue<'a>, body: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    force_type(&l, bindings)?;
     if let Value::Struct { name, kv, .. } = &*l.borrow() {
         let mut off = 0;
         let mut init: HashMap<&'a String, Value> = HashMap::new();
-        while let Some(Token { value: key, .. }) = next_token(&mut off, body, None, Some(TokenKind::Word)) {
+        let mut keys: HashMap<&'a String, &'a Token> = HashMap::new();
+        while let Some(key_token @ Token { value: key, .. }) = next_token(&mut off, body, None, Some(TokenKind::Word)) {
             let kp = off;
-            let Some(value) = next_value(&mut off, body, instructions, bindings) else { bindings.gentle_error(&body[off], "Expected value after key"); return };
+            let Some(value) = next_value(&mut off, body, instructions, bindings)? else { bindings.gentle_error(token_at(body, off), "Expected value after key", "expected-value"); return Ok(()) };
             // if kv.get(key).is_some_and(|x| x.check(&value).is_none()) {
             if let Some(x) = kv.get(&key) {
                 x.check_strict(&value, body, bindings);
             }
-            else { bindings.gentle_error(&body[kp], &format!("There is no such field in type {name}")); return }
-            if let Some(_) = init.insert(key, value) { todo!("warn about redefinition") }
+            else {
+                let fields = kv.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(", ");
+                bindings.diagnose(Diagnostic::error(&format!("There is no such field in type {name}")).span(Span::token(&body[kp-1])).note(&format!("available fields: {fields}")).code("unknown-field"));
+                return Ok(())
+            }
+            if let Some(first) = keys.insert(key, key_token) {
+                bindings.lint(Lint::DuplicateFields,
+                    Diagnostic::warning(&format!("Field '{key}' is specified more than once"))
+                    .span(Span::token(key_token))
+                    .label(Span::token(first), "first specified here")
+                    .note("the last value is used")
+                );
+            }
+            init.insert(key, value);
             ignore_separator(&mut off, body);
         }
         // if init.keys()
         instructions.push(Value::StructInit(name.clone(), init));
     }
+    Ok(())
+}
+
+/// Constructor of enum variant, e.g. `Name::B(1)` or `Name::C { x 1 }`; `off` points at variant name
+pub fn enum_init<'a> (off: &mut usize, tokens: &'a [Token], l: SharedValue<'a>, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    force_type(&l, bindings)?;
+    let (name, variants, tag) = match &*l.borrow() {
+        Value::Enum { name, variants, tag, .. } => (name.clone(), variants.clone(), tag.clone()),
+        Value::Struct { name, .. } => return Err(error(&tokens[*off-1], bindings, &format!("Type {name} is not enum and has no variants"), "unknown-variant")),
+        _ => unreachable!()
+    };
+    let Some(vt) = next_token(off, tokens, None, Some(TokenKind::Word)) else {
+        return Err(error(&tokens[*off-1], bindings, "Expected variant name after '::'", "expected-name"))
+    };
+    let Some((variant, index)) = variants.get_w_p(&vt.value) else {
+        let available = variants.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(", ");
+        return Err(bindings.fatal(Diagnostic::error(&format!("There is no variant \"{}\" in enum {name}", vt.value)).span(Span::token(vt)).note(&format!("available variants: {available}")).code("unknown-variant")))
+    };
+
+    let mut payload = Vec::new();
+    if variant.named {
+        let body = next_body(off, tokens, bindings, ("{", "}"))?;
+        let mut init: HashMap<&String, Value<'a>> = HashMap::new();
+        let mut boff = 0;
+        while let Some(Token { value: key, .. }) = next_token(&mut boff, body, None, Some(TokenKind::Word)) {
+            let kp = boff;
+            let Some(value) = next_value(&mut boff, body, instructions, bindings)? else { bindings.gentle_error(&body[boff-1], "Expected value after key", "expected-value"); return Ok(()) };
+            let Some(x) = variant.fields.get(key) else {
+                let fields = variant.fields.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(", ");
+                bindings.diagnose(Diagnostic::error(&format!("There is no such field in variant {name}::{}", vt.value)).span(Span::token(&body[kp-1])).note(&format!("available fields: {fields}")).code("unknown-field"));
+                ignore_separator(&mut boff, body);
+                continue
+            };
+            x.check_strict(&value, &body[kp..boff], bindings);
+            init.insert(key, value);
+            ignore_separator(&mut boff, body);
+        }
+        for (k, t) in variant.fields.iter() {
+            let Some(v) = init.remove(k.as_ref()) else {
+                bindings.gentle_error(vt, &format!("Missing field \"{k}\" in initializer of {name}::{}", vt.value), "missing-field");
+                continue
+            };
+            payload.push((v, t.clone()));
+        }
+    }
+    else if variant.fields.values().next().is_some() {
+        let body = next_body(off, tokens, bindings, ("(", ")"))?;
+        let mut boff = 0;
+        let mut types = variant.fields.values();
+        let mut given = 0;
+        while boff < body.len() {
+            let vp = boff;
+            let Some(value) = next_value(&mut boff, body, instructions, bindings)? else { bindings.gentle_error(&body[vp], "Expected value", "expected-value"); return Ok(()) };
+            if let Some(t) = types.next() {
+                t.check_strict(&value, &body[vp..boff], bindings);
+                payload.push((value, t.clone()));
+            }
+            given += 1;
+            ignore_separator(&mut boff, body);
+        }
+        if given != payload.len() || types.next().is_some() {
+            bindings.gentle_error(vt, &format!("Variant {name}::{} takes {} values, got {given}", vt.value, variant.fields.values().count()), "argument-count");
+        }
+    }
+
+    instructions.push(Value::EnumInit { name, tag: (index, tag), payload });
+    Ok(())
 }
\ No newline at end of file
@@ -1,29 +1,41 @@
-use crate::{lexer::Token, novalue, parser::{Value, bindings::Bindings, components::pointer::take_pointer, message::error, simpler::{next_mutable_flag, next_one_value}}};
+use crate::{lexer::{Token, TokenKind}, novalue, parser::{Value, bindings::Bindings, numbers::int_literal, r#type::{default_type, Type}, components::pointer::take_pointer, message::{error, PResult}, simpler::{next_mutable_flag, next_one_value}}};
 
-use super::{expr::get_op};
+use super::{expr::{get_op, prefix_operand}};
 
-pub fn unary<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn unary<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let mut off = 1;
     let token = &tokens[0];
     match tokens[0].value.as_str() {
         "-" => {
+            // negative literal may be out of range of its positive
+            if let Some(x @ Token { typ: TokenKind::Int, .. }) = tokens.get(1) {
+                instructions.push(int_literal(x, true, bindings)?);
+                return Ok(2)
+            }
             // let val = next_one_value(&mut off, tokens, instructions, bindings, "Expected value for NEG operation");
             // let Some(val) = next_one_value(&mut off, tokens, instructions, bindings) else { bindings.gentle_error(&tokens[0], "Expected value for NEG operation"); return off };
-            let val = novalue!(&mut off, tokens, instructions, bindings, "Expected value for NEG operation", 0, off);
-            if let Value::Int(x) = val { instructions.push(Value::SInt(-(x as isize))); return off }
+            let Some(val) = prefix_operand(&mut off, tokens, instructions, bindings)? else { bindings.gentle_error(token, "Expected value for NEG operation", "expected-value"); return Ok(off) };
+            if let Value::Float(x) = val { instructions.push(Value::Float(-x)); return Ok(off) }
+            if !matches!(default_type(&val), Type::I(_) | Type::U(_) | Type::F(_)) { bindings.gentle_error(token, "Operator '-' is only defined for numbers", "invalid-operand"); }
             instructions.push(Value::Unary(Box::new((get_op(token).unwrap(), val))));
         }
         "!" => {
             // let val = next_one_value(&mut off, tokens, instructions, bindings, "Expected value for NOT operation");
-            let val = novalue!(&mut off, tokens, instructions, bindings, "Expected value for NOT operation", 0, off);
+            let Some(val) = prefix_operand(&mut off, tokens, instructions, bindings)? else { bindings.gentle_error(token, "Expected value for NOT operation", "expected-value"); return Ok(off) };
+            if !matches!(default_type(&val), Type::I(_) | Type::U(_) | Type::Bool) { bindings.gentle_error(token, "Operator '!' is only defined for integers and booleans", "invalid-operand"); }
+            instructions.push(Value::Unary(Box::new((get_op(token).unwrap(), val))));
+        }
+        "~" => {
+            let Some(val) = prefix_operand(&mut off, tokens, instructions, bindings)? else { bindings.gentle_error(token, "Expected value for bitwise NOT operation", "expected-value"); return Ok(off) };
+            if !matches!(default_type(&val), Type::I(_) | Type::U(_)) { bindings.gentle_error(token, "Operator '~' is only defined for integers", "invalid-operand"); }
             instructions.push(Value::Unary(Box::new((get_op(token).unwrap(), val))));
         }
         "&" => {
             let mutable = next_mutable_flag(&mut off, tokens);
             // let val = next_one_value(&mut off, tokens, instructions, bindings, "Expected value to get address from");
             let val = novalue!(&mut off, tokens, instructions, bindings, "Expected value to get address from", 0, off);
-            instructions.push(take_pointer(&tokens[..off], bindings, mutable, val));
-            return off
+            instructions.push(take_pointer(&tokens[..off], bindings, mutable, val)?);
+            return Ok(off)
             // if let Value::Get(name, _) = val {
             //     if mutable {
             //         let Bind::Let(_, true) = bindings.get(name).unwrap() else { error_range(&tokens[..off], bindings, MUT_ADDR_MESSAGE) };
@@ -54,8 +66,8 @@ pub fn unary<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindin
             // }
         }
         _ => {
-            error(&tokens[0], bindings, "This operation is not allowed here")
+            return Err(error(&tokens[0], bindings, "This operation is not allowed here", "syntax"))
         }
     }
-    return off
+    return Ok(off)
 }
\ No newline at end of file
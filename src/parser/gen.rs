@@ -2,16 +2,19 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use insordmap::InsordMap;
 
-use crate::{lexer::{Token, TokenKind}, parser::{components::{arrays::array, binds::{ASSIGN_NOT_EXPECTED, external_word, r#impl, join_by_path, namespace, r#pub, r#use, var}, control_flow::{r#break, r#continue, r#if, r#loop}, r#fn::{extrn, r#fn, parse_fn, r#return}, types::{parse_struct, r#struct, typecast}, unary::unary}, simpler::{next_access_type_member, next_body, next_token}}};
+use crate::{lexer::{Token, TokenKind}, parser::{components::{arrays::array, binds::{ASSIGN_NOT_EXPECTED, external_word, import, r#impl, join_by_path, namespace, r#pub, r#use, var}, generics::generic_scope, globals::{r#const, r#static, scan_globals}, control_flow::{block, defer, r#break, r#continue, r#for, r#if, r#loop, r#match, r#while, unreachable_after}, pattern::Arm, traits::r#trait, r#fn::{extrn, r#fn, parse_fn, r#return}, types::{parse_type_body, r#enum, r#struct, typecast, Variant}, unary::unary}, simpler::{next_access_type_member, next_body, next_token}}};
 
-use super::{bindings::Bindings, components::{args::Arg, binds::modify, expr::expr}, simpler::{next_deref, next_load_address}, strings::EscapeGen, r#type::Type};
+use super::{bindings::Bindings, components::{args::Arg, binds::modify, expr::expr}, lint::Lint, message::{Diagnostic, PResult, Span}, simpler::{next_deref, next_load_address}, strings::{char_literal, string_literal}, numbers::{float_literal, int_literal}, r#type::Type};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Operation { Add, Sub, Mul, Div, Rem, LE, LT, GT, GE, Eq, NE, And, Or, Not, BitAnd, BitOr }
+pub enum Operation { Add, Sub, Mul, Div, Rem, LE, LT, GT, GE, Eq, NE, And, Or, Not, BitAnd, BitOr, Xor, Shl, Shr, BitNot }
 
 #[derive(Debug, Clone)]
 pub enum Value<'a> {
-    String(String), Int(usize), SInt(isize), Bool(bool), Float(f64), Expr(Box<(Value<'a>, Value<'a>, Operation)>), Unary(Box<(Operation, Value<'a>)>),
+    String(String), Int(u128), SInt(i128), Bool(bool), Float(f64),
+    /// `'a'` as 'u32' and `b'a'` as 'u8'
+    Char(char), Byte(u8),
+    Expr(Box<(Value<'a>, Value<'a>, Operation)>), Unary(Box<(Operation, Value<'a>)>),
     Array(Vec<Value<'a>>), Tuple(Vec<Value<'a>>),
     Get(&'a str, Type),
     Ptr(Box<Value<'a>>, bool),
@@ -22,7 +25,10 @@ pub enum Value<'a> {
     // instructions
     // Function { name: &'a str, args: Vec<Arg<'a>>, body: Vec<Value<'a>>, ret: Type },
     Function { name: String, args: Vec<Arg<'a>>, body: Vec<Value<'a>>, ret: Type },
-    PromisedFunction { name: String, args: Vec<Arg<'a>>, body: &'a [Token], ret: Type, token: &'a Token },
+    /// `generics` are type arguments of template instance, bound while parsing its body
+    PromisedFunction { name: String, args: Vec<Arg<'a>>, body: &'a [Token], ret: Type, token: &'a Token, generics: Vec<(&'a str, Type)> },
+    /// Generic function. `header` is argument list and return type, parsed anew for each instance: (type arguments, pointer to instance, instance)
+    FnTemplate { name: String, params: Vec<&'a str>, bounds: Vec<&'a [Token]>, header: &'a [Token], body: &'a [Token], token: &'a Token, instances: Vec<(Vec<Type>, Value<'a>, SharedValue<'a>)> },
     FunctionPointer(String, Type, Vec<Type>),
     AnonFunction { args: Vec<Arg<'a>>, body: Vec<Value<'a>>, ret: Type },
     Call(Box<Value<'a>>, Vec<Value<'a>>),
@@ -34,60 +40,99 @@ pub enum Value<'a> {
 
     If { condition: Box<Value<'a>>, body: Vec<Value<'a>>, else_then: Box<Option<Value<'a>>> },
     Else(Vec<Value<'a>>),
-    Loop(Vec<Value<'a>>, Option<&'a str>), Break(Option<&'a str>), Continue(Option<&'a str>), Unreachable,
+    /// `break` with label and value, that is result of `loop`
+    Loop(Vec<Value<'a>>, Option<&'a str>), Break(Option<&'a str>, Option<Box<Value<'a>>>), Continue(Option<&'a str>), Unreachable,
+    /// Code that runs when enclosing scope is left
+    Defer(Vec<Value<'a>>),
+    /// Instructions in their own scope
+    Block(Vec<Value<'a>>),
+    /// `if` with `else`, block or `loop` used as value of type, which is what its branches end with or `break` leaves it with
+    Valued(Box<Value<'a>>, Type),
+    Match { value: Box<Value<'a>>, typ: Type, arms: Vec<Arm<'a>> },
     
     Typecast(Box<Value<'a>>, Type, Type),
     Namespace(String),
-    PromisedStruct { name: String, body: &'a [Token] }, Struct { name: String, kv: InsordMap<String, Type>, alignment: u32, size: usize },
+    PromisedStruct { name: String, body: &'a [Token], generics: Vec<(&'a str, Type)> }, Struct { name: String, kv: InsordMap<String, Type>, alignment: u32, size: usize },
     StructInit(String, HashMap<&'a String, Value<'a>>),
+    /// Generic struct, its instances are (type arguments, instance)
+    StructTemplate { name: String, params: Vec<&'a str>, bounds: Vec<&'a [Token]>, body: &'a [Token], instances: Vec<(Vec<Type>, SharedValue<'a>)> },
+    /// `payload` is (alignment, size) of storage shared by variants, placed after tag
+    PromisedEnum { name: String, body: &'a [Token] }, Enum { name: String, variants: InsordMap<String, Variant>, tag: Type, payload: (u32, usize), alignment: u32, size: usize },
+    /// (variant index, tag type), payload values with their types
+    EnumInit { name: String, tag: (usize, Type), payload: Vec<(Value<'a>, Type)> },
+    /// Methods are (name, argument list and return type with `Self` in them, declaration)
+    Trait { name: String, methods: Vec<(&'a str, &'a [Token], &'a Token)> },
+    /// `&T` converted to `&dyn Trait`: pointer, functions of vtable in order of trait methods, resulting type
+    DynCast(Box<Value<'a>>, Vec<String>, Type),
+    /// Function pointer taken from vtable of `&dyn Trait` value by index of method
+    VtableMethod(Box<Value<'a>>, usize, Type),
+    /// Pointer to value behind `&dyn Trait`, and its mutability
+    DynData(Box<Value<'a>>, bool),
+    /// Slice from pointer to its first element and length, resulting type
+    MakeSlice(Box<(Value<'a>, Value<'a>)>, Type),
+    /// Pointer to elements of slice
+    SliceData(Box<Value<'a>>),
+    /// Length of slice, as 'usize'
+    SliceLen(Box<Value<'a>>, Type),
+    /// Global variable with constant initializer
+    Static { name: String, typ: Type, value: Box<Value<'a>>, mutable: bool },
+    /// Address of static: (global name, type, mutability)
+    StaticPtr(String, Type, bool),
 
     SharedValue(SharedValue<'a>)
 }
 
 pub type SharedValue<'a> = Rc<RefCell<Value<'a>>>;
 
-pub fn parse_inplace<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
+pub fn parse_inplace<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
     let mut i = 0;
     let len = tokens.len();
     loop {
         if i >= len { break }
-        i += value_lookaround(&tokens[i..], instructions, bindings);
+        i += value_lookaround(&tokens[i..], instructions, bindings)?;
     }
+    Ok(())
 }
 
-pub fn parse<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Vec<Value<'a>> {
+pub fn parse<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Vec<Value<'a>>> {
     let mut instructions = vec![];
-    parse_inplace(tokens, &mut instructions, bindings);
-    instructions
+    parse_inplace(tokens, &mut instructions, bindings)?;
+    Ok(instructions)
 }
 
-pub fn parse_np<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
-    parse_inplace(tokens, instructions, bindings);
-    fn_pass(bindings);
+pub fn parse_np<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    scan_globals(tokens, bindings);
+    parse_inplace(tokens, instructions, bindings)?;
+    fn_pass(bindings)
     // type_pass(bindings);
 }
 
-pub fn parse_program<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Vec<Value<'a>> {
-    let instructions = parse(tokens, bindings);
-    type_pass(bindings);
-    fn_pass(bindings);
-    instructions
+pub fn parse_program<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Vec<Value<'a>>> {
+    scan_globals(tokens, bindings);
+    let instructions = parse(tokens, bindings)?;
+    type_pass(bindings)?;
+    fn_pass(bindings)?;
+    unused_functions(&instructions, bindings);
+    bindings.report_unused_imports();
+    Ok(instructions)
 }
 
-pub fn value<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
-    let Some(token) = tokens.get(0) else { return 1 };
+pub fn value<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
+    let Some(token) = tokens.get(0) else { return Ok(1) };
 
     match token.typ {
-        TokenKind::String => instructions.push(Value::String(token.value.to_string().escape_escaping())),
-        TokenKind::Int => instructions.push(Value::Int(token.value.parse().unwrap())),
+        TokenKind::String => instructions.push(Value::String(string_literal(token, bindings))),
+        TokenKind::Char => instructions.push(char_literal(token, bindings)),
+        TokenKind::Int => instructions.push(int_literal(token, false, bindings)?),
+        TokenKind::Float => instructions.push(float_literal(token, bindings)?),
         TokenKind::Arithmetic | TokenKind::Logical | TokenKind::Bitwise | TokenKind::Special => return unary(tokens, instructions, bindings),
-        TokenKind::Assign => bindings.gentle_error(token, ASSIGN_NOT_EXPECTED),
+        TokenKind::Assign => bindings.gentle_error(token, ASSIGN_NOT_EXPECTED, "unexpected-assign"),
         // TokenKind::In => return load_field(tokens, instructions, bindings),
         TokenKind::Brackets => match token.value.as_str() {
             "(" => {
                 let mut off = 0;
-                let body = next_body(&mut off, tokens, bindings, ("(", ")"));
-                let mut bl = parse(body, bindings);
+                let body = next_body(&mut off, tokens, bindings, ("(", ")"))?;
+                let mut bl = parse(body, bindings)?;
                 if bl.len() != 1 {
                     // reserve_local(bindings);
                     instructions.push(Value::Tuple(bl));
@@ -95,77 +140,95 @@ pub fn value<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindin
                 else {
                     instructions.push(bl.pop().unwrap());
                 }
-                return off
+                return Ok(off)
             }
             "[" => return array(tokens, instructions, bindings),
-            _ => { dbg!(&token.value); }
+            "{" => return block(tokens, instructions, bindings),
+            _ => {}
         }
         TokenKind::Meta => match &token.value.as_str()[1..] {
             "link" => {
                 let mut off = 1;
-                let Some(Token { value: lib, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::String)) else { bindings.gentle_error(&tokens[0], "#link requires path (string) next to it"); return off };
+                let Some(Token { value: lib, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::String)) else { bindings.gentle_error(&tokens[0], "#link requires path (string) next to it", "syntax"); return Ok(off) };
                 bindings.link(&lib[1..lib.len()-1]);
-                return off
+                return Ok(off)
             },
-            _ => todo!()
+            x => bindings.diagnose(
+                Diagnostic::error(&format!("Unknown directive '#{x}'"))
+                .span(Span::token(token))
+                .code("unknown-directive")
+            )
         }
         TokenKind::Word => match token.value.as_str() {
             "extern" => return extrn(tokens, instructions, bindings),
             "fn" => return r#fn(tokens, instructions, bindings, false),
             "return" => return r#return(tokens, instructions, bindings),
             "let" => return var(tokens, instructions, bindings),
+            "const" => return r#const(tokens, instructions, bindings, false),
+            "static" => return r#static(tokens, instructions, bindings, false),
 
             "true" | "false" => instructions.push(Value::Bool(token.value == "true")),
             "if" => return r#if(tokens, instructions, bindings),
             "loop" => return r#loop(tokens, instructions, bindings),
+            "while" => return r#while(tokens, instructions, bindings),
+            "for" => return r#for(tokens, instructions, bindings),
+            "match" => return r#match(tokens, instructions, bindings),
             "break" => return r#break(tokens, instructions, bindings),
             "continue" => return r#continue(tokens, instructions, bindings),
-            "unreachable" => { instructions.push(Value::Unreachable); return tokens.len() },
+            "defer" => return defer(tokens, instructions, bindings),
+            "unreachable" => {
+                instructions.push(Value::Unreachable);
+                unreachable_after(tokens, 1, Span::token(token), bindings);
+                return Ok(tokens.len())
+            },
 
             "namespace" => return namespace(tokens, instructions, bindings),
+            "import" => return import(tokens, instructions, bindings),
             "use" => return r#use(tokens, bindings),
             "pub" => return r#pub(tokens, instructions, bindings),
 
-            "struct" => return r#struct(tokens, instructions, bindings),
+            "struct" => return r#struct(tokens, instructions, bindings, false),
+            "enum" => return r#enum(tokens, instructions, bindings, false),
             "impl" => return r#impl(tokens, instructions, bindings),
+            "trait" => return r#trait(tokens, bindings, false),
 
             _ => return external_word(tokens, instructions, bindings)
         }
         _ => {}
     }
 
-    return 1
+    return Ok(1)
 }
 
-pub fn value_loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, v_off: usize, ignore: &[(Option<TokenKind>, Option<&str>)]) -> usize {
-    let Some(tk) = tokens.get(v_off) else { return v_off };
+pub fn value_loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, v_off: usize, ignore: &[(Option<TokenKind>, Option<&str>)]) -> PResult<usize> {
+    let Some(tk) = tokens.get(v_off) else { return Ok(v_off) };
     for ignore in ignore {
         match ignore {
-            (Some(ignore_typ), None) => if ignore_typ == &tk.typ { return v_off },
-            (None, Some(ignore_val)) => if ignore_val == &tk.value { return v_off },
-            (Some(ignore_typ), Some(ignore_val)) => if ignore_typ == &tk.typ && ignore_val == &tk.value { return v_off },
+            (Some(ignore_typ), None) => if ignore_typ == &tk.typ { return Ok(v_off) },
+            (None, Some(ignore_val)) => if ignore_val == &tk.value { return Ok(v_off) },
+            (Some(ignore_typ), Some(ignore_val)) => if ignore_typ == &tk.typ && ignore_val == &tk.value { return Ok(v_off) },
             _ => {}
         }
     }
     let o = match tk {
         Token { typ: TokenKind::Arithmetic | TokenKind::Logical | TokenKind::Bitwise, .. } => {
-            let y = expr(&tokens[v_off..], instructions, bindings);
+            let y = expr(&tokens[v_off..], instructions, bindings)?;
             v_off + y
         }
         Token { typ: TokenKind::Assign, .. } => {
             let mut last = v_off;
-            modify(&mut last, tokens, instructions, bindings);
+            modify(&mut last, tokens, instructions, bindings)?;
             last
         }
         Token { typ: TokenKind::Brackets, .. } => {
             let mut off = v_off;
-            next_load_address(&mut off, tokens, instructions, bindings, false);
+            next_load_address(&mut off, tokens, instructions, bindings, false)?;
             off
         }
         Token { typ: TokenKind::Special, value, .. } => {
             let mut off = v_off;
             match value.as_str() {
-                ".*" => { next_deref(&mut off, tokens, instructions, bindings); },
+                ".*" => { next_deref(&mut off, tokens, instructions, bindings)?; },
                 // "::" => {
                 //     let Some(Value::Namespace(mut name)) = instructions.pop() else { error(&tokens[0], bindings, "Not a namespace") };
                 //     let Some(Token { value: next, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word)) else { error(&tokens[0], bindings, "Unexpected end of path") };
@@ -178,12 +241,12 @@ pub fn value_loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, b
         }
         Token { typ: TokenKind::In, .. } => {
             let mut off = v_off;
-            next_access_type_member(&mut off, tokens, instructions, bindings, false);
+            next_access_type_member(&mut off, tokens, instructions, bindings, false)?;
             off
         }
         Token { typ: TokenKind::Word, value, .. } => {
             if value == "as" {
-                let y = typecast(&tokens[v_off..], instructions, bindings);
+                let y = typecast(&tokens[v_off..], instructions, bindings)?;
                 v_off + y
             }
             else { v_off }
@@ -191,39 +254,59 @@ pub fn value_loop<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, b
         _ => v_off
     };
     if o > v_off { return value_loop(tokens, instructions, bindings, o, ignore) }
-    return o
+    return Ok(o)
 }
 
-pub fn value_lookaround<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> usize {
+pub fn value_lookaround<'a> (tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<usize> {
     let ls = instructions.len();
-    let x = value(tokens, instructions, bindings);
+    let x = value(tokens, instructions, bindings)?;
     if ls != instructions.len() {
         return value_loop(tokens, instructions, bindings, x, &[]);
     }
-    return x
+    return Ok(x)
 }
 
-fn fn_pass<'a> (bindings: &mut Bindings<'a>) {
+fn fn_pass<'a> (bindings: &mut Bindings<'a>) -> PResult<()> {
+    // instances of generic structs may appear in function bodies
+    type_pass(bindings)?;
     for i in bindings.move_functions() {
         let ia = i.replace(Value::Unreachable);
-        if let Value::PromisedFunction { name, args, body, ret, token } = ia {
+        if let Value::PromisedFunction { name, args, body, ret, token, generics } = ia {
             join_by_path(&name.clone(), bindings, |bindings| {
-                let body = parse_fn(token, body, bindings, &args, ret.clone());
+                bindings.push_scope(generic_scope(&generics));
+                let body = bindings.instance_scope(&name, |bindings| parse_fn(token, body, bindings, &args, ret.clone()))?;
+                bindings.pop_scope();
                 i.replace(Value::Function { name, args, body, ret });
-            });
+                Ok(())
+            })?;
         }
         else { i.replace(ia); }
     }
-    if bindings.get_functions().len() > 0 { return fn_pass(bindings) }
+    if !bindings.get_functions().is_empty() || !bindings.get_types().is_empty() { fn_pass(bindings) }
+    else { Ok(()) }
 }
 
-fn type_pass<'a> (bindings: &mut Bindings<'a>) {
+fn type_pass<'a> (bindings: &mut Bindings<'a>) -> PResult<()> {
     for i in bindings.move_types() {
         let ia = i.replace(Value::Unreachable);
-        if let Value::PromisedStruct { name, body } = ia {
-            i.replace(parse_struct(name, body, bindings));
+        i.replace(parse_type_body(ia, bindings)?);
+    }
+    if !bindings.get_types().is_empty() { type_pass(bindings) }
+    else { Ok(()) }
+}
+/// Functions are parsed only once something refers to them, so ones still promised after `fn_pass` are never used
+fn unused_functions<'a> (instructions: &[Value<'a>], bindings: &Bindings<'a>) {
+    for i in instructions {
+        let Value::SharedValue(x) = i else { continue };
+        match &*x.borrow() {
+            // methods implementing trait are part of the impl, even if never called
+            Value::PromisedFunction { name, .. } if name.contains(" as ") => {},
+            Value::PromisedFunction { name, token, .. } => unused_function(name, token, bindings),
+            Value::FnTemplate { name, token, instances, .. } if instances.is_empty() => unused_function(name, token, bindings),
+            _ => {}
         }
-        else { i.replace(ia); }
     }
-    if bindings.get_types().len() > 0 { return type_pass(bindings) }
+}
+fn unused_function (name: &str, token: &Token, bindings: &Bindings) {
+    bindings.lint(Lint::UnusedFunctions, Diagnostic::warning(&format!("Function '{name}' is never used")).span(Span::token(token)));
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use super::message::{Diagnostic, Severity};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint { UnusedVariables, UnusedFunctions, UnreachableCode, UnusedImports, DuplicateFields }
+
+impl Lint {
+    pub const ALL: [Lint; 5] = [Lint::UnusedVariables, Lint::UnusedFunctions, Lint::UnreachableCode, Lint::UnusedImports, Lint::DuplicateFields];
+
+    pub fn name (&self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused-variables",
+            Lint::UnusedFunctions => "unused-functions",
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::UnusedImports => "unused-imports",
+            Lint::DuplicateFields => "duplicate-fields"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level { Allow, Warn, Deny }
+
+/// Levels of lints requested from command line. Every lint warns by default
+#[derive(Debug, Clone, Default)]
+pub struct LintLevels { levels: HashMap<Lint, Level> }
+
+impl LintLevels {
+    /// Sets level of lint by its name. `warnings` stands for all lints at once
+    pub fn set (&mut self, name: &str, level: Level) -> Result<(), String> {
+        let name = name.replace('_', "-");
+        if name == "warnings" {
+            for lint in Lint::ALL { self.levels.insert(lint, level); }
+            return Ok(())
+        }
+        let Some(lint) = Lint::ALL.into_iter().find(|x| x.name() == name) else {
+            return Err(format!("Unknown lint '{name}' (expected one of: warnings, {})", Lint::ALL.map(|x| x.name()).join(", ")))
+        };
+        self.levels.insert(lint, level);
+        Ok(())
+    }
+
+    pub fn get (&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+
+    /// Turns lint report into diagnostic of severity requested for it, if it's not allowed
+    pub fn apply (&self, lint: Lint, mut d: Diagnostic) -> Option<Diagnostic> {
+        let level = self.get(lint);
+        d.severity = match level {
+            Level::Allow => return None,
+            Level::Warn => Severity::Warning,
+            Level::Deny => Severity::Error
+        };
+        if level == Level::Deny { d = d.note(&format!("'-D {}' is set", lint.name())); }
+        Some(d.code(lint.name()))
+    }
+}
\ No newline at end of file
@@ -1,85 +1,203 @@
-use std::panic;
+use std::{cell::{Cell, RefCell}, fmt::Write, path::Path};
 
 use crate::lexer::Token;
 
 use super::bindings::Bindings;
 
-pub fn point (lines: Vec<&str>, line: usize, col: usize, amount: usize, color: &str) {
-    println!("{}\x1b{color}", lines[line-1]);
-    println!("{}{}\x1b[0m", " ".repeat(col-1), "^".repeat(amount));
-}
-
-pub fn point_range (tokens: &[Token], lines: Vec<&str>, color: &str) {
-    let mut i = 0;
-    let mut line = 0;
-    let mut il = 0;
-    let mut joined = false;
-    while i < tokens.len() {
-        let t = &tokens[i];
-        if line != t.line {
-            il = i;
-            line = t.line;
-            if joined == true { joined = false; print!("\x1b[0m\n"); }
-            println!("{}\x1b{color}", lines[line-1]);
-        }
-        let s = &tokens[il];
-        let e = &tokens[i];
-        if joined == false { print!("{}", " ".repeat(s.col-1)); joined = true; }
-        let nearest = if i > 0 { tokens.get(i-1) } else { None };
-        let space = if let Some(nearest) = nearest {
-            if nearest.line == e.line { e.col - nearest.col - nearest.value.chars().count() }
-            else { 0 }
-        }
-        else { 0 };
-        print!("{}", "^".repeat(e.value.chars().count() + space));
-        i += 1;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity { Error, Warning }
+
+/// Place in source code, from (line, col) up to (end_line, end_col) exclusive. Columns are counted in chars, starting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span { pub file: usize, pub line: usize, pub col: usize, pub end_line: usize, pub end_col: usize }
+
+impl Span {
+    pub fn token (token: &Token) -> Self {
+        Self { file: token.file, line: token.line, col: token.col, end_line: token.line, end_col: token.col + token.value.chars().count() }
+    }
+    pub fn range (tokens: &[Token]) -> Self {
+        let (f, l) = (Self::token(&tokens[0]), Self::token(&tokens[tokens.len()-1]));
+        Self { end_line: l.end_line, end_col: l.end_col, ..f }
     }
+}
 
-    print!("\x1b[0m\n");
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub primary: Option<Span>,
+    /// Additional places with labels explaining them
+    pub secondary: Vec<(Span, String)>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+    /// Stable identifier of diagnostic kind, for tools
+    pub code: Option<String>
 }
 
+impl Diagnostic {
+    pub fn new (severity: Severity, message: &str) -> Self {
+        Self { severity, message: message.into(), primary: None, secondary: vec![], notes: vec![], help: None, code: None }
+    }
+    pub fn error (message: &str) -> Self { Self::new(Severity::Error, message) }
+    pub fn warning (message: &str) -> Self { Self::new(Severity::Warning, message) }
+
+    pub fn span (mut self, span: Span) -> Self { self.primary = Some(span); self }
+    pub fn label (mut self, span: Span, label: &str) -> Self { self.secondary.push((span, label.into())); self }
+    pub fn note (mut self, note: &str) -> Self { self.notes.push(note.into()); self }
+    pub fn help (mut self, help: &str) -> Self { self.help = Some(help.into()); self }
+    pub fn code (mut self, code: &str) -> Self { self.code = Some(code.into()); self }
+}
 
+/// Sink for diagnostics reported during compilation. Interior mutability lets reporting go through shared references
+#[derive(Debug, Default)]
+pub struct Diagnostics { list: RefCell<Vec<Diagnostic>>, errors: Cell<usize> }
 
-pub fn parsing_error_message (bindings: &Bindings, message: &str, line: usize, col: usize) {
-    let path = bindings.get_current_file_path();
-    let link = format!("\x1B]8;;{}\x1B\\{}:{line}:{col}\x1B]8;;\x1B\\", path.to_str().unwrap(), path.as_path().file_name().unwrap().to_str().unwrap());
-    println!("\x1b[91mError\x1b[0m at {link}: \x1b[1m{message}\x1b[0m");
+impl Diagnostics {
+    pub fn push (&self, d: Diagnostic) {
+        // tokens of value that failed to parse may be parsed once again as instruction
+        if self.list.borrow().contains(&d) { return }
+        if d.severity == Severity::Error { self.errors.set(self.errors.get() + 1); }
+        self.list.borrow_mut().push(d);
+    }
+    pub fn has_errors (&self) -> bool { self.errors.get() > 0 }
+    pub fn error_count (&self) -> usize { self.errors.get() }
+    pub fn take (&self) -> Vec<Diagnostic> { self.list.take() }
 }
 
-pub fn error (token: &Token, bindings: &Bindings, message: &str) -> ! {
-    // panic::set_hook(Box::new(|_| {}));
-    let Token { col, line, value, .. } = token;
-    parsing_error_message(bindings, message, *line, *col);
-    let lines: Vec<&str> = bindings.get_initial_code().lines().collect();
-    point(lines, *line, *col, value.chars().count(), "[91m");
-    // exit(1)
-    panic!()
+/// Error after which parsing can't continue. It's already reported by the time it's returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FatalError;
+
+/// Result of parsing, that may be aborted by fatal error
+pub type PResult<T> = Result<T, FatalError>;
+
+/// How diagnostics are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat { Human { color: bool }, Json }
+
+fn paint (s: &str, style: &str, color: bool) -> String {
+    if color { format!("\x1b{style}{s}\x1b[0m") } else { s.into() }
 }
 
-// pub fn error_eots () -> ! {
-//     parsing_error_message(&Bindings::default(), "Unexpected end of token stream somewhere in program", 0, 0);
-//     panic!()
-// }
+fn underline (buf: &mut String, lines: &[&str], span: &Span, style: &str, color: bool, label: Option<&str>) {
+    for line in span.line..=span.end_line {
+        let Some(text) = lines.get(line-1) else { break };
+        let indent = text.chars().take_while(|x| x.is_whitespace()).count() + 1;
+        let start = if line == span.line { span.col } else { indent };
+        let end = if line == span.end_line { span.end_col } else { text.chars().count() + 1 };
+        let mut mark = format!("{}{}", " ".repeat(start-1), "^".repeat(end.saturating_sub(start).max(1)));
+        if let (Some(label), true) = (label, line == span.end_line) { mark = format!("{mark} {label}"); }
+        writeln!(buf, "{text}\n{}", paint(&mark, style, color)).unwrap();
+    }
+}
 
-pub fn error_range (tokens: &[Token], bindings: &Bindings, message: &str) -> ! {
-    let f = &tokens[0];
-    parsing_error_message(bindings, message, f.line, f.col);
+/// `files` are paths and code of source files by their index
+fn render_human (d: &Diagnostic, files: &[(&Path, &str)], color: bool) -> String {
+    let mut buf = String::new();
+    let (name, style) = match d.severity {
+        Severity::Error => ("Error", "[91m"),
+        Severity::Warning => ("Warning", "[93m")
+    };
+    let name = match &d.code { Some(code) => format!("{name}[{code}]"), None => name.into() };
+    let primary = d.primary.and_then(|x| files.get(x.file));
+    let at = match (d.primary, primary) {
+        (Some(Span { line, col, .. }), Some((path, _))) => {
+            let file = path.file_name().and_then(|x| x.to_str()).unwrap_or_default();
+            if color { format!(" at \x1B]8;;{}\x1B\\{file}:{line}:{col}\x1B]8;;\x1B\\", path.display()) }
+            else { format!(" at {file}:{line}:{col}") }
+        }
+        _ => String::new()
+    };
+    writeln!(buf, "{}{at}: {}", paint(&name, style, color), paint(&d.message, "[1m", color)).unwrap();
+
+    if let (Some(span), Some((_, code))) = (&d.primary, primary) { underline(&mut buf, &code.lines().collect::<Vec<_>>(), span, style, color, None); }
+    for (span, label) in &d.secondary {
+        let Some((path, code)) = files.get(span.file) else { continue };
+        // label in another file is preceded by its location
+        if d.primary.is_none_or(|x| x.file != span.file) {
+            let file = path.file_name().and_then(|x| x.to_str()).unwrap_or_default();
+            writeln!(buf, "{}", paint(&format!("in {file}:{}:{}", span.line, span.col), "[94m", color)).unwrap();
+        }
+        underline(&mut buf, &code.lines().collect::<Vec<_>>(), span, "[94m", color, Some(label));
+    }
+    for note in &d.notes { writeln!(buf, "{}: {note}", paint("note", "[1m", color)).unwrap(); }
+    if let Some(help) = &d.help { writeln!(buf, "{}: {help}", paint("help", "[1m", color)).unwrap(); }
+    buf
+}
+
+fn json_string (s: &str) -> String {
+    let mut buf = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => buf += "\\\"",
+            '\\' => buf += "\\\\",
+            '\n' => buf += "\\n",
+            '\r' => buf += "\\r",
+            '\t' => buf += "\\t",
+            c if (c as u32) < 0x20 => write!(buf, "\\u{:04x}", c as u32).unwrap(),
+            c => buf.push(c)
+        }
+    }
+    buf + "\""
+}
+
+fn json_span (span: Option<&Span>, files: &[(&Path, &str)]) -> String {
+    let file = span.and_then(|x| files.get(x.file)).map(|x| json_string(&x.0.display().to_string())).unwrap_or("null".into());
+    match span {
+        Some(Span { line, col, end_line, end_col, .. }) => format!("\"file\":{file},\"line\":{line},\"column\":{col},\"end_line\":{end_line},\"end_column\":{end_col}"),
+        None => String::from("\"file\":null,\"line\":null,\"column\":null,\"end_line\":null,\"end_column\":null")
+    }
+}
 
-    let lines: Vec<&str> = bindings.get_initial_code().lines().collect();
-    point_range(tokens, lines, "[91m");
+/// One-line JSON object, so tools can read diagnostics line by line
+fn render_json (d: &Diagnostic, files: &[(&Path, &str)]) -> String {
+    let severity = match d.severity { Severity::Error => "error", Severity::Warning => "warning" };
+    let code = d.code.as_deref().map(json_string).unwrap_or("null".into());
+    let notes = d.notes.iter().map(|x| json_string(x)).collect::<Vec<_>>().join(",");
+    let help = d.help.as_deref().map(json_string).unwrap_or("null".into());
+    let labels = d.secondary.iter().map(|(span, label)| format!("{{{},\"label\":{}}}", json_span(Some(span), files), json_string(label))).collect::<Vec<_>>().join(",");
+    format!(
+        "{{{},\"severity\":\"{severity}\",\"code\":{code},\"message\":{},\"notes\":[{notes}],\"help\":{help},\"labels\":[{labels}]}}\n",
+        json_span(d.primary.as_ref(), files), json_string(&d.message)
+    )
+}
+
+/// Renders diagnostic, its spans refer to `files`: paths and code of source files by their index (none for errors outside of any file)
+pub fn render (d: &Diagnostic, files: &[(&Path, &str)], format: ErrorFormat) -> String {
+    match format {
+        ErrorFormat::Human { color } => render_human(d, files, color),
+        ErrorFormat::Json => render_json(d, files)
+    }
+}
+
+/// Token at `off` to point diagnostic at, or the last one when code ends before it
+pub fn token_at (tokens: &[Token], off: usize) -> &Token {
+    tokens.get(off).or(tokens.last()).expect("diagnostic must point at some code")
+}
+
+pub fn error (token: &Token, bindings: &Bindings, message: &str, code: &str) -> FatalError {
+    bindings.fatal(Diagnostic::error(message).span(Span::token(token)).code(code))
+}
 
-    // exit(1)
-    panic!()
+pub fn error_range (tokens: &[Token], bindings: &Bindings, message: &str, code: &str) -> FatalError {
+    bindings.fatal(Diagnostic::error(message).span(Span::range(tokens)).code(code))
 }
 
-pub fn assert (a: bool, token: &Token, bindings: &Bindings, message: &str) {
-    if !a { error(token, bindings, message) }
+pub fn assert (a: bool, token: &Token, bindings: &Bindings, message: &str, code: &str) -> PResult<()> {
+    if !a { return Err(error(token, bindings, message, code)) }
+    Ok(())
 }
 
-pub fn assert_range (a: bool, tokens: &[Token], bindings: &Bindings, message: &str) {
-    if !a { error_range(tokens, bindings, message) }
+pub fn assert_range (a: bool, tokens: &[Token], bindings: &Bindings, message: &str, code: &str) -> PResult<()> {
+    if !a { return Err(error_range(tokens, bindings, message, code)) }
+    Ok(())
 }
 
-pub fn err_expected_body (token: &Token, bindings: &Bindings, pair: (&str, &str)) -> ! {
-    error(token, bindings, &format!("Expected body of shape '{}...{}'", pair.0, pair.1))
+pub fn err_expected_body (token: &Token, bindings: &Bindings, pair: (&str, &str)) -> FatalError {
+    bindings.fatal(
+        Diagnostic::error(&format!("Expected body of shape '{}...{}'", pair.0, pair.1))
+        .span(Span::token(token))
+        .help(&format!("check that every '{}' has matching '{}'", pair.0, pair.1))
+        .code("unbalanced-brackets")
+    )
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 pub mod components;
-mod message;
+pub mod message;
+pub mod lint;
 mod r#gen;
 // mod expr;
 mod body;
 mod strings;
+mod numbers;
 pub mod bindings;
 pub use r#gen::*;
 mod simpler;
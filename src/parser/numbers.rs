@@ -0,0 +1,121 @@
+use crate::{lexer::Token, parser::{bindings::Bindings, components::{control_flow::valued_results, expr::is_cmp, pattern::int_bounds}, message::{error, Diagnostic, PResult, Span}, r#type::{extract_types, Type}, Value}};
+
+/// Type named by suffix of number literal, like 'u8' or 'isize'
+fn suffix_type (suffix: &str, bindings: &Bindings) -> Option<Type> {
+    let bits = match suffix.get(1..)? {
+        "size" => bindings.target_ptr_bits() as u32,
+        x => x.parse().ok().filter(|x| matches!(x, 8 | 16 | 32 | 64 | 128))?
+    };
+    match &suffix[..1] {
+        "i" => Some(Type::I(bits)),
+        "u" => Some(Type::U(bits)),
+        _ => None
+    }
+}
+
+/// Integer literal: decimal or with '0x', '0o', '0b' prefix, '_' separators and optional type suffix
+pub fn parse_int (token: &Token, bindings: &Bindings) -> PResult<(u128, Option<Type>)> {
+    let text = token.value.replace('_', "");
+    let (radix, body) = match text.get(..2) {
+        Some("0x") => (16, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        _ => (10, &text[..])
+    };
+    let split = body.find(|x: char| !(x.is_ascii_digit() || radix == 16 && x.is_ascii_hexdigit())).unwrap_or(body.len());
+    let (digits, suffix) = body.split_at(split);
+    if digits.is_empty() { return Err(error(token, bindings, &format!("Expected digits after '{}'", &text[..2]), "invalid-literal")) }
+    if let Some(x) = digits.chars().find(|x| !x.is_digit(radix)) {
+        return Err(error(token, bindings, &format!("Invalid digit '{x}' in base {radix} literal"), "invalid-literal"))
+    }
+    let Ok(x) = u128::from_str_radix(digits, radix) else { return Err(error(token, bindings, "Integer literal is too large, even for 'u128'", "invalid-literal")) };
+    if suffix.is_empty() { return Ok((x, None)) }
+    let Some(typ) = suffix_type(suffix, bindings) else {
+        return Err(bindings.fatal(
+            Diagnostic::error(&format!("Invalid suffix '{suffix}' of integer literal"))
+            .span(Span::token(token))
+            .help("suffix is type of literal: 'i8' to 'i128', 'u8' to 'u128', 'isize' or 'usize'")
+            .code("invalid-literal")
+        ))
+    };
+    Ok((x, Some(typ)))
+}
+
+/// Value of integer literal, negated if it follows '-'. With suffix it's cast to type of suffix
+pub fn int_literal<'a> (token: &Token, negative: bool, bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    // like '1f32'
+    if !token.value.starts_with("0x") && (token.value.ends_with("f32") || token.value.ends_with("f64")) {
+        let Value::Typecast(x, from, to) = float_literal(token, bindings)? else { unreachable!() };
+        let Value::Float(x) = *x else { unreachable!() };
+        return Ok(Value::Typecast(Box::new(Value::Float(if negative { -x } else { x })), from, to))
+    }
+    let (x, typ) = parse_int(token, bindings)?;
+    let value = if negative {
+        let Some(x) = 0i128.checked_sub_unsigned(x) else { return Err(error(token, bindings, "Integer literal is too large, even for 'i128'", "invalid-literal")) };
+        Value::SInt(x)
+    }
+    else { Value::Int(x) };
+    let Some(typ) = typ else { return Ok(value) };
+    if !fits(&value, &typ) { out_of_range(&value, &typ, Span::token(token), bindings); }
+    Ok(Value::Typecast(Box::new(value), typ.clone(), typ))
+}
+
+/// Floating point literal with optional 'f32' or 'f64' suffix
+pub fn float_literal<'a> (token: &Token, bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    let text = token.value.replace('_', "");
+    let (text, typ) = match text.find('f') {
+        Some(x) => (&text[..x], Some(&text[x..])),
+        None => (&text[..], None)
+    };
+    let Ok(x) = text.parse() else { return Err(error(token, bindings, "Invalid floating point literal", "invalid-literal")) };
+    match typ {
+        None => Ok(Value::Float(x)),
+        Some("f32") => Ok(Value::Typecast(Box::new(Value::Float(x)), Type::F(32), Type::F(32))),
+        Some("f64") => Ok(Value::Typecast(Box::new(Value::Float(x)), Type::F(64), Type::F(64))),
+        Some(x) => Err(error(token, bindings, &format!("Invalid suffix '{x}' of floating point literal, expected 'f32' or 'f64'"), "invalid-literal"))
+    }
+}
+
+/// Whether integer literal can be represented in type
+fn fits (x: &Value, typ: &Type) -> bool {
+    let Some((min, max)) = int_bounds(typ) else { return true };
+    match (x, typ) {
+        (Value::Int(_), Type::U(x)) if *x >= 128 => true,
+        (Value::Int(x), _) => i128::try_from(*x).is_ok_and(|x| x <= max),
+        (Value::SInt(x), _) => (min..=max).contains(x),
+        _ => true
+    }
+}
+
+fn out_of_range (x: &Value, typ: &Type, span: Span, bindings: &Bindings) {
+    let literal = match x { Value::Int(x) => x.to_string(), Value::SInt(x) => x.to_string(), _ => unreachable!() };
+    let (min, max) = int_bounds(typ).unwrap();
+    let max = if *typ == Type::U(128) { u128::MAX.to_string() } else { max.to_string() };
+    bindings.diagnose(
+        Diagnostic::error(&format!("Literal '{literal}' is out of range for '{}'", typ.display()))
+        .span(span)
+        .note(&format!("'{}' holds values from {min} to {max}", typ.display()))
+        .code("literal-out-of-range")
+    );
+}
+
+/// Reports integer literals in `x`, which can't be represented in `typ` it's going to have
+pub fn check_literals (typ: &Type, x: &Value, tokens: &[Token], bindings: &Bindings) {
+    match (x, typ) {
+        (Value::Int(_) | Value::SInt(_), _) if !fits(x, typ) => out_of_range(x, typ, Span::range(tokens), bindings),
+        // operands of comparisons have type of each other
+        (Value::Expr(l), _) if is_cmp(&l.2) => if let Some(t) = extract_types(&[&l.0, &l.1]) {
+            check_literals(&t, &l.0, tokens, bindings);
+            check_literals(&t, &l.1, tokens, bindings);
+        }
+        (Value::Expr(l), _) => {
+            check_literals(typ, &l.0, tokens, bindings);
+            check_literals(typ, &l.1, tokens, bindings);
+        }
+        (Value::Valued(x, _), _) => valued_results(x).into_iter().flatten().for_each(|x| check_literals(typ, x, tokens, bindings)),
+        (Value::Unary(l), _) => check_literals(typ, &l.1, tokens, bindings),
+        (Value::Array(x), Type::Array(t, _)) => x.iter().for_each(|x| check_literals(t, x, tokens, bindings)),
+        (Value::Tuple(x), Type::Tuple(t)) => x.iter().zip(t).for_each(|(x, t)| check_literals(t, x, tokens, bindings)),
+        _ => {}
+    }
+}
\ No newline at end of file
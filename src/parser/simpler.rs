@@ -1,12 +1,12 @@
-use crate::{lexer::{Token, TokenKind}, parser::{components::{binds::access_type_member, pointer::take_pointer}, r#type::extract_type}};
+use crate::{lexer::{Token, TokenKind}, parser::{components::{binds::access_type_member, control_flow::{expect_value, unreachable_after}, pointer::take_pointer, slices::to_c_ptr, traits::coerce}, r#type::extract_type}};
 
-use super::{bindings::Bindings, body::parse_pair_symbols, components::{args::{parse_args, Arg}, binds::{deref, load_address}}, message::err_expected_body, r#type::{parse_type, Type}, value, value_lookaround, Value};
+use super::{bindings::{Bind, Bindings}, body::parse_pair_symbols, components::{args::{parse_args, Arg}, binds::{deref, load_address}}, message::{err_expected_body, Diagnostic, PResult, Span, token_at}, r#type::{parse_type, Type}, value, value_lookaround, Value};
 
-pub fn next_body<'a> (off: &mut usize, tokens: &'a [Token], bindings: &Bindings, pair: (&'a str, &'a str)) -> &'a [Token] {
-    let body = parse_pair_symbols(&tokens[*off..], pair).unwrap_or_else(|| err_expected_body(&tokens[*off], bindings, pair));
+pub fn next_body<'a> (off: &mut usize, tokens: &'a [Token], bindings: &Bindings, pair: (&'a str, &'a str)) -> PResult<&'a [Token]> {
+    let body = parse_pair_symbols(&tokens[*off..], pair).ok_or_else(|| err_expected_body(token_at(tokens, *off), bindings, pair))?;
     let res = &tokens[*off+1..*off + body - 1];
     *off += body;
-    res
+    Ok(res)
 }
 pub fn next_body_optional<'a> (off: &mut usize, tokens: &'a [Token], pair: (&'a str, &'a str)) -> Option<&'a [Token]> {
     let body = parse_pair_symbols(tokens.get(*off..)?, pair)?;
@@ -15,16 +15,18 @@ pub fn next_body_optional<'a> (off: &mut usize, tokens: &'a [Token], pair: (&'a
     Some(res)
 }
 
-pub fn next_value<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> Option<Value<'a>> {
+pub fn next_value<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<Option<Value<'a>>> {
     let ls = instructions.len();
-    let value_jump = value_lookaround(tokens.get(*off..)?, instructions, bindings) + *off;
+    let Some(rest) = tokens.get(*off..) else { return Ok(None) };
+    let value_jump = value_lookaround(rest, instructions, bindings)? + *off;
     let le = instructions.len();
     if ls != le {
-        let res = instructions.pop()?;
+        let Some(res) = instructions.pop() else { return Ok(None) };
+        expect_value(&res, &tokens[*off..value_jump], bindings)?;
         *off = value_jump;
-        return Some(res)
+        return Ok(Some(res))
     }
-    None
+    Ok(None)
 }
 
 #[macro_export]
@@ -32,34 +34,35 @@ macro_rules! nvalue {
     ($off:expr, $tokens:expr, $instructions:expr, $b:expr, $msg:expr) => {
         {
             // let abort = *$off;
-            let Some(x) = next_value($off, $tokens, $instructions, $b) else { $b.gentle_error(&$tokens[*$off], $msg); return *$off };
+            let Some(x) = next_value($off, $tokens, $instructions, $b)? else { $b.gentle_error(&$tokens[*$off], $msg, "expected-value"); return Ok(*$off) };
             x
         }
     };
     ($off:expr, $tokens:expr, $instructions:expr, $b:expr, $msg:expr, $r:expr) => {
         {
-            let Some(x) = next_value($off, $tokens, $instructions, $b) else { $b.gentle_error(&$tokens[*$off], $msg); return $r };
+            let Some(x) = next_value($off, $tokens, $instructions, $b)? else { $b.gentle_error(&$tokens[*$off], $msg, "expected-value"); return Ok($r) };
             x
         }
     };
     ($off:expr, $tokens:expr, $instructions:expr, $b:expr, $msg:expr, $o:expr, $r:expr) => {
         {
-            let Some(x) = next_value($off, $tokens, $instructions, $b) else { $b.gentle_error($o, $msg); return $r };
+            let Some(x) = next_value($off, $tokens, $instructions, $b)? else { $b.gentle_error($o, $msg, "expected-value"); return Ok($r) };
             x
         }
     };
 }
 
-pub fn next_one_value<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> Option<Value<'a>> {
-    *off += value(tokens.get(*off..)?, instructions, bindings);
-    instructions.pop()
+pub fn next_one_value<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<Option<Value<'a>>> {
+    let Some(rest) = tokens.get(*off..) else { return Ok(None) };
+    *off += value(rest, instructions, bindings)?;
+    Ok(instructions.pop())
 }
 
 #[macro_export]
 macro_rules! novalue {
     ($off:expr, $tokens:expr, $instructions:expr, $b:expr, $msg:expr, $o:expr, $r:expr) => {
         {
-            let Some(val) = next_one_value($off, $tokens, $instructions, $b) else { $b.gentle_error(&$tokens[0], $msg); return *$off };
+            let Some(val) = next_one_value($off, $tokens, $instructions, $b)? else { $b.gentle_error(&$tokens[0], $msg, "expected-value"); return Ok(*$off) };
             val
         }
     };
@@ -99,16 +102,17 @@ pub fn next_token<'a> (off: &mut usize, tokens: &'a [Token], value: Option<&'a s
     None
 }
 
-pub fn next_args<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Vec<Arg<'a>> {
-    let Some(tt) = next_body_optional(off, tokens, ("(", ")")) else { return vec![] };
-    let (res, _) = parse_args(tt, bindings);
-    res
+pub fn next_args<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Vec<Arg<'a>>> {
+    let Some(tt) = next_body_optional(off, tokens, ("(", ")")) else { return Ok(vec![]) };
+    let (res, _) = parse_args(tt, bindings)?;
+    Ok(res)
 }
 
-pub fn next_type<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Option<Type> {
-    let (typ, j) = parse_type(tokens.get(*off..)?, bindings)?;
+pub fn next_type<'a> (off: &mut usize, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<Type>> {
+    let Some(rest) = tokens.get(*off..) else { return Ok(None) };
+    let Some((typ, j)) = parse_type(rest, bindings)? else { return Ok(None) };
     *off += j;
-    Some(typ)
+    Ok(Some(typ))
 }
 
 pub fn next_mutable_flag (off: &mut usize, tokens: &[Token]) -> bool {
@@ -117,39 +121,39 @@ pub fn next_mutable_flag (off: &mut usize, tokens: &[Token]) -> bool {
 
 // TODO: MAYBE MERGE THESE FUNCTIONS IN ONE (because of, for example, cases like "foo.bar[0].abc[2]")
 
-pub fn next_load_address<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> Option<()> {
-    let mut value = &tokens.get(*off)?.value;
+pub fn next_load_address<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<()> {
+    let Some(mut value) = tokens.get(*off).map(|x| &x.value) else { return Ok(()) };
     while value == "[" {
-        *off += load_address(&tokens[*off..], instructions, bindings, mutable);
+        *off += load_address(&tokens[*off..], instructions, bindings, mutable)?;
         if let Some(Token { value: v, .. }) = tokens.get(*off) {
             value = v;
         }
         else { break }
     }
-    Some(())
+    Ok(())
 }
-pub fn next_access_type_member<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> Option<()> {
-    let mut value = &tokens.get(*off)?.value;
+pub fn next_access_type_member<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>, mutable: bool) -> PResult<()> {
+    let Some(mut value) = tokens.get(*off).map(|x| &x.value) else { return Ok(()) };
     while value == "." {
-        *off += access_type_member(&tokens[*off..], instructions, bindings, mutable);
+        *off += access_type_member(&tokens[*off..], instructions, bindings, mutable)?;
         if let Some(Token { value: v, .. }) = tokens.get(*off) {
             value = v;
         }
         else { break }
     }
-    Some(())
+    Ok(())
 }
 
-pub fn next_deref<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> Option<()> {
-    let mut token = &tokens.get(*off)?.value;
+pub fn next_deref<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<Option<()>> {
+    let Some(mut token) = tokens.get(*off).map(|x| &x.value) else { return Ok(None) };
     while token == ".*" {
-        *off += deref(&tokens[*off..], instructions, bindings);
+        *off += deref(&tokens[*off..], instructions, bindings)?;
         if let Some(Token { value: v, .. }) = tokens.get(*off) {
             token = v;
         }
         else { break }
     }
-    Some(())
+    Ok(Some(()))
 }
 
 pub fn ignore_separator<'a> (off: &mut usize, tokens: &'a [Token]) {
@@ -158,62 +162,119 @@ pub fn ignore_separator<'a> (off: &mut usize, tokens: &'a [Token]) {
 
 pub fn next_name<'a> (off: &mut usize, tokens: &'a [Token]) -> String {
     let mut buf = String::new();
-    loop {
-        let Some(Token { value: np, .. }) = next_token(off, tokens, None, Some(TokenKind::Word)) else { todo!() };
+    // incomplete path is left as is, it won't resolve to anything
+    while let Some(Token { value: np, .. }) = next_token(off, tokens, None, Some(TokenKind::Word)) {
         buf += np;
-        if let Some(Token { value: cc, .. }) = next_token(off, tokens, Some("::"), Some(TokenKind::Special)) {
-            buf += cc;
-        }
-        else { break }
+        let Some(Token { value: cc, .. }) = next_token(off, tokens, Some("::"), Some(TokenKind::Special)) else { break };
+        buf += cc;
     }
     // todo generics
     buf
 }
 
-fn fill_argv<'a> (off: &mut usize, argt: Vec<Type>, argv: &mut Vec<Value<'a>>, body: &'a [Token], tokens: &'a [Token], spoint: usize, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
+/// Arguments of call: types of arguments, `body` between parentheses, that open at `tokens[spoint]`
+struct CallArgs<'a> {
+    argt: Vec<Type>,
+    body: &'a [Token],
+    tokens: &'a [Token],
+    spoint: usize,
+    /// set for calls of 'extern' functions
+    extrn: bool
+}
+
+/// Parses arguments of `call` that follow ones already in `argv`
+fn fill_argv<'a> (call: CallArgs<'a>, argv: &mut Vec<Value<'a>>, instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
+    let CallArgs { argt, body, tokens, spoint, extrn } = call;
     let argoff = argv.len();
     for (p, typ) in argt[..argoff].iter().enumerate() {
         typ.check_strict(&argv[p], &tokens[spoint..=spoint], bindings);
     }
+    let mut off = 0;
     for typ in &argt[argoff..] {
-        let start = *off;
-        let value = nvalue!(off, body, instructions, bindings, "Expected function argument", &tokens[spoint+*off], ());
-        typ.check_strict(&value, &body[start..*off], bindings);
-        ignore_separator(off, body);
+        let start = off;
+        let value = nvalue!(&mut off, body, instructions, bindings, "Expected function argument", &tokens[spoint+off], ());
+        let value = coerce(typ, value, &body[start..off], bindings)?;
+        let value = if extrn { to_c_ptr(typ, value) } else { value };
+        typ.check_strict(&value, &body[start..off], bindings);
+        ignore_separator(&mut off, body);
         argv.push(value);
     }
+    Ok(())
 }
 
-fn construct_call<'a> (x: Value<'a>, argv: Vec<Value<'a>>, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, ret: Type) -> usize {
+/// `end` is offset right after the call
+pub fn construct_call<'a> (x: Value<'a>, argv: Vec<Value<'a>>, tokens: &'a [Token], end: usize, instructions: &mut Vec<Value<'a>>, bindings: &Bindings<'a>, ret: Type) -> usize {
     instructions.push(Value::Call(Box::new(x), argv));
-    if ret == Type::Noret { instructions.push(Value::Unreachable); return tokens.len() }
+    if ret == Type::Noret {
+        unreachable_after(tokens, end, Span::range(&tokens[..end]), bindings);
+        instructions.push(Value::Unreachable);
+        return tokens.len()
+    }
     0
 }
 
-pub fn next_call<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
+pub fn next_call<'a> (off: &mut usize, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
     let mut sb = *off;
     while let Some(body) = next_body_optional(off, tokens, ("(", ")")) {
         let x = instructions.pop().unwrap();
-        let Some(Type::Fn(argt, ret)) = extract_type(&x) else { instructions.push(x); *off = sb; return () };
+        let Some(Type::Fn(argt, ret)) = extract_type(&x) else { instructions.push(x); *off = sb; return Ok(()) };
+        let extrn = matches!(&x, Value::FunctionPointer(name, _, _) if matches!(bindings.get(name), Some(Bind::Function(_, _, None))));
         let mut argv = vec![];
-        fill_argv(&mut 0, argt, &mut argv, body, tokens, sb, instructions, bindings);
-        *off += construct_call(x, argv, tokens, instructions, *ret);
+        fill_argv(CallArgs { argt, body, tokens, spoint: sb, extrn }, &mut argv, instructions, bindings)?;
+        *off += construct_call(x, argv, tokens, *off, instructions, bindings, *ret);
         sb = *off;
     }
+    Ok(())
 }
-pub fn next_uniform_call<'a> (off: &mut usize, mut farg: Value<'a>, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) {
+/// Takes address of receiver or dereferences it, so it fits first argument of method. `tokens` end with name of method
+fn receiver<'a> (expected: &Type, farg: Value<'a>, tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Value<'a>> {
+    let Some(actual) = extract_type(&farg) else { return Ok(farg) };
+    let method = &tokens[tokens.len()-1].value;
+    Ok(match expected {
+        Type::Ptr(Some(t), m) if **t == actual => {
+            let immutable = match &farg {
+                Value::Get(name, _) => matches!(bindings.get(name), Some(Bind::Let(_, false))).then_some(*name),
+                _ => None
+            };
+            if let (true, Some(name)) = (*m, immutable) {
+                bindings.diagnose(
+                    Diagnostic::error(&format!("Cannot call method '{method}' on immutable variable '{name}'"))
+                    .span(Span::range(tokens))
+                    .note(&format!("method takes '{}'", expected.display()))
+                    .help(&format!("declare it as 'let mut {name}'"))
+                    .code("immutable-receiver")
+                );
+                return Ok(Value::Ptr(Box::new(farg), true))
+            }
+            take_pointer(tokens, bindings, *m, farg)?
+        }
+        Type::Ptr(Some(t), true) if actual == Type::Ptr(Some(t.clone()), false) => {
+            return Err(bindings.fatal(
+                Diagnostic::error(&format!("Cannot call method '{method}' through immutable reference"))
+                .span(Span::range(tokens))
+                .note(&format!("method takes '{}', but receiver is '{}'", expected.display(), actual.display()))
+                .code("immutable-receiver")
+            ))
+        }
+        // `self` by value from pointer
+        t => match actual {
+            Type::Ptr(Some(a), _) if *a == *t => Value::LoadFromPtr(Box::new(farg), t.clone()),
+            _ => farg
+        }
+    })
+}
+
+pub fn next_uniform_call<'a> (off: &mut usize, mut farg: Value<'a>, tokens: &'a [Token], instructions: &mut Vec<Value<'a>>, bindings: &mut Bindings<'a>) -> PResult<()> {
     let sb = *off;
     if let Some(body) = next_body_optional(off, tokens, ("(", ")")) {
         let x = instructions.pop().unwrap();
-        let Some(Type::Fn(argt, ret)) = extract_type(&x) else { instructions.push(x); *off = sb; return () };
-        if let Some(Type::Ptr(Some(_t), m)) = argt.get(0) {
-            if let None = argt[0].check(&farg) {
-                farg = take_pointer(&tokens[..*off], bindings, *m, farg);
-            }
-            // t.check_strict(&farg, &tokens[..*off], bindings);
+        let Some(Type::Fn(argt, ret)) = extract_type(&x) else { instructions.push(x); *off = sb; return Ok(()) };
+        if let Some(t) = argt.first() {
+            farg = receiver(t, farg, &tokens[..sb], bindings)?;
         }
         let mut argv = vec![farg];
-        fill_argv(&mut 0, argt, &mut argv, body, tokens, sb, instructions, bindings);
-        *off += construct_call(x, argv, tokens, instructions, *ret);
+        fill_argv(CallArgs { argt, body, tokens, spoint: sb, extrn: false }, &mut argv, instructions, bindings)?;
+        *off += construct_call(x, argv, tokens, *off, instructions, bindings, *ret);
     }
+    Ok(())
 }
\ No newline at end of file
@@ -1,11 +1,68 @@
-pub trait EscapeGen {
-    fn escape_escaping (self) -> String;
+use crate::{lexer::{unescape, EscapeError, Token}, parser::{bindings::Bindings, message::{Diagnostic, Span}, Value}};
+
+/// Chars `at..at+len` of literal contents, which start `prefix` chars after beginning of token
+fn inner_span (token: &Token, prefix: usize, at: usize, len: usize) -> Span {
+    let (mut line, mut col) = (token.line, token.col);
+    for c in token.value.chars().take(prefix + at) {
+        if c == '\n' { line += 1; col = 1; }
+        else { col += 1; }
+    }
+    Span { file: token.file, line, col, end_line: line, end_col: col + len }
 }
 
+fn report (token: &Token, prefix: usize, e: EscapeError, bindings: &Bindings) {
+    bindings.diagnose(
+        Diagnostic::error(&e.message)
+        .span(inner_span(token, prefix, e.at, e.len))
+        .code("invalid-escape")
+    );
+}
 
-// todo: maybe don't do this actually
-impl EscapeGen for String {
-    fn escape_escaping (self) -> String {
-        self[1..self.len()-1].replace("\\n", "\n")
+/// Contents of string literal with escape sequences decoded, `c"a"` also gets NUL terminator
+pub fn string_literal (token: &Token, bindings: &Bindings) -> String {
+    let c = token.value.starts_with('c');
+    let prefix = if c { 2 } else { 1 };
+    let mut x = match unescape(&token.value[prefix..token.value.len()-1], false) {
+        // without byte escapes above 0x7F it's always valid UTF-8
+        Ok(x) => String::from_utf8(x).unwrap(),
+        Err(e) => { report(token, prefix, e, bindings); return String::new() }
+    };
+    if c {
+        if x.contains('\0') {
+            bindings.diagnose(
+                Diagnostic::error("C string literal can't contain NUL")
+                .span(Span::token(token))
+                .note("C functions would see the string end there")
+                .code("invalid-escape")
+            );
+        }
+        x.push('\0');
     }
+    x
+}
+
+/// `'a'` is code of character as 'u32', `b'a'` is 'u8'
+pub fn char_literal<'a> (token: &Token, bindings: &Bindings) -> Value<'a> {
+    let byte = token.value.starts_with('b');
+    let prefix = if byte { 2 } else { 1 };
+    let body = &token.value[prefix..token.value.len()-1];
+    let fallback = if byte { Value::Byte(0) } else { Value::Char('\0') };
+    let bytes = match unescape(body, byte) {
+        Ok(x) => x,
+        Err(e) => { report(token, prefix, e, bindings); return fallback }
+    };
+    let value = if byte { (bytes.len() == 1).then(|| Value::Byte(bytes[0])) }
+    else {
+        let s = String::from_utf8(bytes).unwrap();
+        let mut chars = s.chars();
+        chars.next().filter(|_| chars.next().is_none()).map(Value::Char)
+    };
+    value.unwrap_or_else(|| {
+        bindings.diagnose(
+            Diagnostic::error(if byte { "Byte literal must contain exactly one byte" } else { "Character literal must contain exactly one character" })
+            .span(Span::token(token))
+            .code("invalid-char")
+        );
+        fallback
+    })
 }
\ No newline at end of file
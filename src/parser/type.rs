@@ -1,12 +1,14 @@
 use std::mem;
 
-use crate::{lexer::{Token, TokenKind}, parser::{Operation, Value, bindings::{Bind, Bindings}, message::error, simpler::{ignore_separator, next_body, next_body_optional, next_token, next_type}}, strip_alias_get};
+use crate::{lexer::{Token, TokenKind}, parser::{Operation, Value, bindings::{Bind, Bindings}, message::{error, error_range, Diagnostic, FatalError, PResult, Span, token_at}, numbers::check_literals, simpler::{ignore_separator, next_body, next_body_optional, next_name, next_token, next_type}}, strip_alias_get};
 
-use super::components::expr::{is_branch, is_cmp};
+use super::components::{expr::{is_branch, is_cmp}, globals::array_length, generics::{instantiate_struct, next_type_args}, traits::resolve_trait};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     I(u32), U(u32),
+    /// IEEE floating point number of 32 or 64 bits
+    F(u32),
     Bool,
     Array(Box<Type>, usize),
     /// (value, mutability)
@@ -14,6 +16,14 @@ pub enum Type {
     Struct(String), // todo maybe eval alias instead of this
     Tuple(Vec<Type>),
     Fn(Vec<Type>, Box<Type>),
+    /// `&dyn Trait`: pointer to value along with vtable of trait methods for its type. (trait, mutability)
+    Dyn(String, bool),
+    /// `&[T]`: pointer to elements along with their count. (element, mutability)
+    Slice(Box<Type>, bool),
+    /// Parameter of generic function, only appears while inferring its type arguments
+    Param(usize),
+    /// Struct declared without body, like handle of C library. Its size is unknown, so it's only used behind pointer
+    Opaque(String),
     Void, Noret, Guess
 }
 
@@ -34,6 +44,13 @@ impl Type {
                 }
             }
         }
+        // mutable slice may be used as immutable one
+        if let Type::Slice(t, false) = self {
+            return match extract_type(x) {
+                Some(Type::Slice(x, _)) if x == *t => Some(()),
+                _ => None
+            }
+        }
         if let Type::Ptr(_, true) = self {
             // dbg!(x);
             return match extract_type(x) {
@@ -63,8 +80,16 @@ impl Type {
                 self.check(&a.1)?;
                 return Some(())
             }
-            Value::Int(_) | Value::SInt(_) | Value::Bool(_) => return match self {
-                Self::I(_) | Self::U(_) | Self::Bool => Some(()),
+            Value::Int(_) | Value::SInt(_) => return match self {
+                Self::I(_) | Self::U(_) => Some(()),
+                _ => None
+            },
+            Value::Bool(_) => return match self {
+                Self::Bool => Some(()),
+                _ => None
+            },
+            Value::Float(_) => return match self {
+                Self::F(_) => Some(()),
                 _ => None
             },
             Value::Array(x) => {
@@ -93,13 +118,14 @@ impl Type {
     }
 
     pub fn check_strict (&self, x: &Value, tokens: &[Token], bindings: &mut Bindings) {
+        check_literals(self, x, tokens, bindings);
         let c = self.check(x);
         if c == None {
             let x = match x {
                 Value::Tuple(v) => Type::Tuple(v.iter().map(|x| extract_type(x).unwrap_or(Type::Guess)).collect()),
                 x => extract_type(x).unwrap_or(Type::Guess)
             };
-            bindings.gentle_error_range(tokens, &format!("Mismatched types: expected '{}', got '{}'", self.display(), x.display()));
+            bindings.gentle_error_range(tokens, &format!("Mismatched types: expected '{}', got '{}'", self.display(), x.display()), "mismatched-types");
         }
     }
 
@@ -107,6 +133,7 @@ impl Type {
         match self {
             Type::I(x) => return format!("i{x}"),
             Type::U(x) => return format!("u{x}"),
+            Type::F(x) => return format!("f{x}"),
             Type::Ptr(Some(x), false) => return format!("&{}", x.display()),
             Type::Ptr(None, false) => "ptr",
             Type::Ptr(Some(x), true) => return format!("&mut {}", x.display()),
@@ -117,7 +144,12 @@ impl Type {
             Type::Tuple(t) => return format!("({})", t.iter().map(|x| x.display()).collect::<Vec<_>>().join(", ")),
             Type::Noret => "!",
             Type::Fn(args, ret) => return format!("fn ({}) {}", args.iter().map(|x| x.display()).collect::<Vec<String>>().join(", "), ret.display()),
-            Type::Struct(x) => return format!("{x}"),
+            Type::Struct(x) | Type::Opaque(x) => return x.to_string(),
+            Type::Dyn(x, false) => return format!("&dyn {x}"),
+            Type::Dyn(x, true) => return format!("&mut dyn {x}"),
+            Type::Slice(x, false) => return format!("&[{}]", x.display()),
+            Type::Slice(x, true) => return format!("&mut [{}]", x.display()),
+            Type::Param(x) => return format!("?{x}"),
             Type::Guess => "_",
         }.into()
     }
@@ -135,8 +167,9 @@ impl Type {
                 x += 1;
                 (x / 8).max(1)
             },
+            Type::F(x) => x / 8,
             Type::Bool => 1,
-            Type::Ptr(_, _) | Type::Fn(_, _) => (bindings.target_ptr_bits() / 8) as u32,
+            Type::Ptr(_, _) | Type::Fn(_, _) | Type::Dyn(_, _) | Type::Slice(_, _) => (bindings.target_ptr_bits() / 8) as u32,
             Type::Tuple(x) => {
                 let mut max = 0;
                 for i in x {
@@ -147,21 +180,22 @@ impl Type {
             }
             Type::Array(x, _) => x.alignment(bindings),
             Type::Struct(x) => {
-                let Some(Bind::Type(l)) = bindings.get(x) else { todo!() };
-                if let Some(l) = l {
-                    if let Value::Struct { alignment, .. } = &*l.borrow() {
+                if let Some(l) = bindings.get_type(x) {
+                    if let Value::Struct { alignment, .. } | Value::Enum { alignment, .. } = &*l.borrow() {
                         return *alignment
                     }
                 }
                 0
             }
-            x => todo!("{x:?}")
+            // values of these types are rejected where they're declared, so they have no layout
+            Type::Opaque(_) | Type::Void | Type::Noret | Type::Param(_) | Type::Guess => 0
         }
     }
     pub fn sizeof (&self, bindings: &Bindings) -> usize {
         match self {
-            Type::I(_) | Type::U(_) | Type::Ptr(_, _) | Type::Fn(_, _) => self.alignment(bindings) as usize,
+            Type::I(_) | Type::U(_) | Type::F(_) | Type::Ptr(_, _) | Type::Fn(_, _) => self.alignment(bindings) as usize,
             Type::Bool => 1,
+            Type::Dyn(_, _) | Type::Slice(_, _) => 2 * self.alignment(bindings) as usize,
             Type::Array(x, y) => x.sizeof(bindings) * y,
             Type::Tuple(x) => {
                 let align = self.alignment(bindings) as usize;
@@ -173,16 +207,14 @@ impl Type {
                 y
             }
             Type::Struct(x) => {
-                let Some(Bind::Type(l)) = bindings.get(x) else { todo!() };
-                if let Some(l) = l {
-                    if let Value::Struct { size, .. } = &*l.borrow() {
+                if let Some(l) = bindings.get_type(x) {
+                    if let Value::Struct { size, .. } | Value::Enum { size, .. } = &*l.borrow() {
                         return *size
                     }
-                    // else { todo!("inf size err") }
                 }
                 0
             }
-            _ => todo!()
+            Type::Opaque(_) | Type::Void | Type::Noret | Type::Param(_) | Type::Guess => 0
         }
     }
 }
@@ -193,36 +225,50 @@ impl Default for Type {
     }
 }
 
-fn resolve_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Option<(Type, usize)> {
-    let tg = tokens.get(0)?.value.as_str();
+fn resolve_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<(Type, usize)>> {
+    let Some(tg) = tokens.get(0).map(|x| x.value.as_str()) else { return Ok(None) };
     if tg.starts_with("i") {
-        if tg == "isize" { return Some((Type::I(bindings.target_ptr_bits() as u32), 1)) }
+        if tg == "isize" { return Ok(Some((Type::I(bindings.target_ptr_bits() as u32), 1))) }
         if let Ok(x) = tg[1..].parse::<u32>() {
-            return Some((Type::I(x), 1))
+            return Ok(Some((Type::I(x), 1)))
         }
     }
     else if tg.starts_with("u") {
-        if tg == "usize" { return Some((Type::U(bindings.target_ptr_bits() as u32), 1)) }
+        if tg == "usize" { return Ok(Some((Type::U(bindings.target_ptr_bits() as u32), 1))) }
         if let Ok(x) = tg[1..].parse::<u32>() {
-            return Some((Type::U(x), 1))
+            return Ok(Some((Type::U(x), 1)))
         }
     }
     
-    Some((match tokens.get(0)?.value.as_str() {
+    if let Some(Bind::Generic(t)) = bindings.get(tg) { return Ok(Some((t.clone(), 1))) }
+    Ok(Some((match tg {
         "&" => {
             let mut last = 1;
             let mutable = next_token(&mut last, tokens, Some("mut"), Some(TokenKind::Word)).is_some();
+            if next_token(&mut last, tokens, Some("dyn"), Some(TokenKind::Word)).is_some() {
+                let start = last;
+                if tokens.get(last).is_none_or(|x| x.typ != TokenKind::Word) { return Err(error(&tokens[last-1], bindings, "Expected trait after 'dyn'", "expected-type")) }
+                let path = next_name(&mut last, tokens);
+                let Some(l) = resolve_trait(&path, bindings) else { return Err(error_range(&tokens[start..last], bindings, &format!("'{path}' is not a trait"), "unknown-name")) };
+                let Value::Trait { name, .. } = &*l.borrow() else { unreachable!() };
+                return Ok(Some((Type::Dyn(name.clone(), mutable), last)))
+            }
+            if let Some(body) = next_body_optional(&mut last, tokens, ("[", "]")) {
+                let (t, j) = parse_type(body, bindings)?.ok_or_else(|| error(&tokens[last-1], bindings, "Expected type of slice elements: '&[T]'", "expected-type"))?;
+                if j != body.len() { return Err(error(&body[j], bindings, "Expected ']' after type of slice elements", "expected-type")) }
+                return Ok(Some((Type::Slice(Box::new(t), mutable), last)))
+            }
             // let (t, j) = parse_type(peek(tokens, bindings, last..), bindings)?;
-            let (t, j) = parse_type(tokens.get(last..)?, bindings)?;
+            let Some((t, j)) = pointee_type(&tokens[last..], bindings)? else { return Ok(None) };
             last += j;
-            return Some((Type::Ptr(Some(Box::new(t)), mutable), last))
+            return Ok(Some((Type::Ptr(Some(Box::new(t)), mutable), last)))
         }
         "&&" => {
             let mut last = 1;
             // let (t, j) = parse_type(peek(tokens, bindings, last..), bindings)?;
-            let (t, j) = parse_type(tokens.get(last..)?, bindings)?;
+            let Some((t, j)) = pointee_type(&tokens[last..], bindings)? else { return Ok(None) };
             last += j;
-            return Some((Type::Ptr(Some(Box::new(Type::Ptr(Some(Box::new(t)), false))), false), last))
+            return Ok(Some((Type::Ptr(Some(Box::new(Type::Ptr(Some(Box::new(t)), false))), false), last)))
         }
         "ptr" => Type::Ptr(None, false),
         "ptrmut" => Type::Ptr(None, true),
@@ -231,34 +277,45 @@ fn resolve_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Option
             let mut args = vec![];
             if let Some(body) = next_body_optional(&mut last, tokens, ("(", ")")) {
                 let mut boff = 0;
-                while let Some(x) = next_type(&mut boff, body, bindings) {
+                while let Some(x) = next_type(&mut boff, body, bindings)? {
                     args.push(x);
                     ignore_separator(&mut boff, body);
                 }
             }
-            let ret = next_type(&mut last, tokens, bindings).unwrap_or(Type::Void);
-            return Some((Type::Fn(args, Box::new(ret)), last))
+            let ret = next_type(&mut last, tokens, bindings)?.unwrap_or(Type::Void);
+            return Ok(Some((Type::Fn(args, Box::new(ret)), last)))
         }
         "void" => Type::Void,
         "!" | "noret" => Type::Noret,
         "bool" => Type::Bool,
-        "(" => return Some(tuple(tokens, bindings)),
+        "f32" => Type::F(32),
+        "f64" => Type::F(64),
+        "(" => return tuple(tokens, bindings).map(Some),
         v => {
             // todo: recusrive parsing, instead of wave-like implemented for functions
             // remove shit from Bindings structure then
             let mut v = String::from(v);
             let mut off = 1;
             while let Some(Bind::Namespace(_)) = strip_alias_get!(v, bindings) {
-                if let Some(_) = next_token(&mut off, tokens, Some("::"), Some(TokenKind::Special)) {
-                    let Some(Token { value, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word))
-                    else { error(&tokens[off-1], bindings, "Invalid path") };
-                    v += "::";
-                    v += value;
-                }
+                // namespace on its own isn't a type
+                if next_token(&mut off, tokens, Some("::"), Some(TokenKind::Special)).is_none() { break }
+                let Some(Token { value, .. }) = next_token(&mut off, tokens, None, Some(TokenKind::Word))
+                else { return Err(error(&tokens[off-1], bindings, "Invalid path", "unknown-name")) };
+                v += "::";
+                v += value;
             }
 
             // it'll validate each time. better to look for other ways to implement that
-            if let Some(Bind::Type(_l)) = strip_alias_get!(v, bindings) {
+            if let Some(Bind::Type(None)) = strip_alias_get!(v, bindings) {
+                return Ok(Some((Type::Opaque(v), off)))
+            }
+            if let Some(Bind::Type(l)) = strip_alias_get!(v, bindings) {
+                if let Some(l) = l.clone().filter(|l| matches!(&*l.borrow(), Value::StructTemplate { .. })) {
+                    let Some(types) = next_type_args(&mut off, tokens, bindings)? else {
+                        return Err(error(&tokens[off-1], bindings, &format!("Generic struct '{v}' requires type arguments: '{v}<...>'"), "type-argument-count"))
+                    };
+                    return Ok(Some((Type::Struct(instantiate_struct(&l, types, &tokens[..off], bindings)?), off)))
+                }
                 // if let Some(l) = l {
                 //     // validate_struct(l.clone(), bindings, &mut Vec::new());
                 //     let l = l.clone();
@@ -271,47 +328,60 @@ fn resolve_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Option
 
                 // todo: need to separate parsing struct itself from parsing struct name
 
-                return Some((Type::Struct(v), off))
+                return Ok(Some((Type::Struct(v), off)))
             }
-            return None
+            return Ok(None)
         }
-    }, 1))
+    }, 1)))
+}
+
+pub fn parse_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<(Type, usize)>> {
+    let Some((typ, j)) = pointee_type(tokens, bindings)? else { return Ok(None) };
+    if let Type::Opaque(x) = &typ { return Err(opaque_by_value(x, &tokens[..j], bindings)) }
+    Ok(Some((typ, j)))
 }
 
-pub fn parse_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> Option<(Type, usize)> {
-    let (mut par_typ, j) = resolve_type(tokens, bindings)?;
+fn opaque_by_value (name: &str, tokens: &[Token], bindings: &Bindings) -> FatalError {
+    bindings.fatal(
+        Diagnostic::error(&format!("Opaque type '{name}' has no known size, so it can't be used by value"))
+        .span(Span::range(tokens))
+        .help(&format!("use pointer to it instead: '&{name}' or '&mut {name}'"))
+        .code("opaque-by-value")
+    )
+}
+
+/// Same as `parse_type`, but also allows opaque types, as it's used for what pointer points to
+fn pointee_type<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<Option<(Type, usize)>> {
+    let Some((mut par_typ, j)) = resolve_type(tokens, bindings)? else { return Ok(None) };
     let mut last = j;
-    if j > 1 { return Some((par_typ, last)) }
+    if j > 1 { return Ok(Some((par_typ, last))) }
     while last < tokens.len() {
         match tokens[last].value.as_str() {
             "[" => {
-                par_typ = Type::Array(
-                    Box::new(par_typ),
-                    tokens[last + 1].value.parse().unwrap_or_else(|_| error(&tokens[last+1], bindings, "Expected length of array"))
-                );
-                last += 3;
+                if let Type::Opaque(x) = &par_typ { return Err(opaque_by_value(x, &tokens[..last+1], bindings)) }
+                let mut off = last;
+                let body = next_body(&mut off, tokens, bindings, ("[", "]"))?;
+                par_typ = Type::Array(Box::new(par_typ), array_length(&tokens[last..off], body, bindings)?);
+                last = off;
 
                 continue
             }
-            "<" => {
-                todo!()
-            }
             _ => break
         }
     }
-    Some((par_typ, last))
+    Ok(Some((par_typ, last)))
 }
-fn tuple<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> (Type, usize) {
+fn tuple<'a> (tokens: &'a [Token], bindings: &mut Bindings<'a>) -> PResult<(Type, usize)> {
     let mut off = 0;
     let mut types = vec![];
-    let body = next_body(&mut off, tokens, bindings, ("(", ")"));
+    let body = next_body(&mut off, tokens, bindings, ("(", ")"))?;
     let mut boff = 0;
     while boff < body.len() {
-        types.push(next_type(&mut boff, body, bindings).unwrap_or_else(|| error(&body[boff], bindings, "Not a type")));
+        types.push(next_type(&mut boff, body, bindings)?.ok_or_else(|| error(token_at(body, boff), bindings, "Not a type", "unknown-name"))?);
         // let _separator = next_token(&mut boff, body, None, Some(TokenKind::Punctuation));
         ignore_separator(&mut boff, body);
     }
-    return (Type::Tuple(types), off)
+    return Ok((Type::Tuple(types), off))
 }
 
 pub fn default_type_expr<'a> (lhs: &Value<'a>, rhs: &Value<'a>, op: Operation) -> Type {
@@ -321,17 +391,18 @@ pub fn default_type_expr<'a> (lhs: &Value<'a>, rhs: &Value<'a>, op: Operation) -
 
 pub fn default_type (x: &Value) -> Type {
     match x {
-        Value::Int(_) => Type::I(32),
+        Value::Int(_) | Value::SInt(_) => Type::I(32),
+        Value::Float(_) => Type::F(64),
         Value::Bool(_) => Type::Bool,
         Value::Expr(l) => default_type_expr(&l.0, &l.1, l.2),
-        Value::String(x) => Type::Array(Box::new(Type::I(8)), x.len()),
+        Value::Unary(l) => default_type(&l.1),
         Value::Array(t) => {
             let Some(x) = extract_types_move(&t) else { return Type::Void };
             Type::Array(Box::new(x), t.len())
         },
         Value::Ptr(t, m) => Type::Ptr(Some(Box::new(default_type(t))), *m),
         Value::Tuple(x) => Type::Tuple(x.iter().map(|x| default_type(x)).collect()),
-        _ => { extract_type(x).unwrap_or_else(|| {dbg!(x); panic!()}) }
+        _ => extract_type(x).unwrap_or_else(|| unreachable!("{x:?} has no type"))
     }
 }
 
@@ -378,13 +449,25 @@ pub fn extract_type (x: &Value) -> Option<Type> {
         },
         Value::InitVar(_, t, _) => t.clone(),
         Value::Typecast(_, _, t) => t.clone(),
+        Value::Char(_) => Type::U(32),
+        Value::Byte(_) => Type::U(8),
+        Value::String(_) => Type::Slice(Box::new(Type::U(8)), false),
         Value::FunctionPointer(_, ret, args) => Type::Fn(args.clone(), Box::new(ret.clone())),
         Value::AnonFunction { args, body: _, ret } => Type::Fn(args.iter().map(|x| x.typ.clone()).collect(), Box::new(ret.clone())),
         Value::Call(v, _) => {
             let Type::Fn(_, ret) = extract_type(v)? else { return None };
             *ret
         },
-        Value::StructInit(name, _) => Type::Struct(name.clone()),
+        Value::StructInit(name, _) | Value::EnumInit { name, .. } => Type::Struct(name.clone()),
+        Value::DynCast(_, _, t) | Value::VtableMethod(_, _, t) => t.clone(),
+        Value::DynData(_, mutable) => Type::Ptr(None, *mutable),
+        Value::MakeSlice(_, t) | Value::SliceLen(_, t) => t.clone(),
+        Value::SliceData(x) => {
+            let Type::Slice(t, mutable) = extract_type(x)? else { return None };
+            Type::Ptr(Some(t), mutable)
+        }
+        Value::StaticPtr(_, t, mutable) => Type::Ptr(Some(Box::new(t.clone())), *mutable),
+        Value::Valued(_, t) => t.clone(),
         _ => return None
     })
 }
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn ops () {
+        compile_test!("./bitwise/ops.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./bitwise/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:3:15: Operator '^' is only defined for integers"));
+        assert!(out.contains("at errors.alt:4:13: Operator '~' is only defined for integers"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./bitwise/ops.alt");
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "11111111\n");
+        assert_eq!(out.status.code(), Some(21));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn order () {
+        compile_test!("./defer/order.alt");
+    }
+
+    #[test]
+    fn escape () {
+        let (compileable, out) = diagnostics_test!("./defer/escape.alt");
+        assert!(!compileable);
+        assert!(out.contains("at escape.alt:3:9: Deferred code can't leave its scope"));
+        assert!(out.contains("at escape.alt:9:5: Deferred code can't leave its scope"));
+        // loop inside of deferred code is fine
+        assert_eq!(out.matches("Deferred code").count(), 2);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./defer/order.alt");
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "defe1234nn!\n");
+    }
+}
\ No newline at end of file
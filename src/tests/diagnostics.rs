@@ -0,0 +1,100 @@
+#[allow(unused)]
+macro_rules! diagnostics_test {
+    ($p:literal) => {
+        diagnostics_test!($p, $crate::parser::message::ErrorFormat::Human { color: false })
+    };
+    ($p:literal, $format:expr) => {
+        {
+            let code = include_str!($p);
+            let tokens = lex(code);
+            let sources = $crate::parser::bindings::Sources::default();
+            let mut bindings = Bindings::new(code, $p.into(), None, &sources);
+            let _ = parse_program(&tokens, &mut bindings);
+            (bindings.is_compileable(), bindings.render_diagnostics($format))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::lex, parser::{bindings::Bindings, message::ErrorFormat, parse_program}};
+
+    #[test]
+    fn immutable_assign () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/immutable_assign.alt");
+        assert!(!compileable);
+        assert!(out.contains("Cannot assign twice to immutable variable 'a'"));
+        assert!(out.contains("let mut a"));
+    }
+
+    #[test]
+    fn fatal () {
+        // must be reported, not panicked
+        let (compileable, out) = diagnostics_test!("./diagnostics/fatal.alt");
+        assert!(!compileable);
+        assert!(out.contains("Recursive type has infinite size"));
+    }
+
+    #[test]
+    fn json () {
+        let (_, out) = diagnostics_test!("./diagnostics/immutable_assign.alt", ErrorFormat::Json);
+        assert_eq!(out.lines().count(), 1);
+        let line = out.lines().next().unwrap();
+        assert!(line.starts_with("{\"file\":\"./diagnostics/immutable_assign.alt\",\"line\":3,\"column\":7,\"end_line\":3,\"end_column\":8,\"severity\":\"error\",\"code\":\"immutable-assign\""));
+        assert!(line.contains("\"help\":\"make it mutable: 'let mut a'\""));
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn unknown_keyword () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/unknown_keyword.alt");
+        assert!(!compileable);
+        assert_eq!(out.matches("Unknown keyword").count(), 1);
+    }
+
+    #[test]
+    fn unsized_field () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/unsized_field.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[unsized-field] at unsized_field.alt:2:8: Field can't be of type 'void', as it has no values"));
+    }
+
+    #[test]
+    fn unary () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/unary.alt");
+        assert!(!compileable);
+        assert!(out.contains("at unary.alt:3:14: Operator '!' is only defined for integers and booleans"));
+        assert!(out.contains("at unary.alt:4:14: Operator '-' is only defined for numbers"));
+        assert!(out.contains("at unary.alt:5:14: Operator '~' is only defined for integers"));
+    }
+
+    #[test]
+    fn use_all () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/use_all.alt");
+        assert!(!compileable);
+        assert!(out.contains("at use_all.alt:2:8: 'N' is not a namespace"));
+    }
+
+    #[test]
+    fn eof () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/eof.alt");
+        assert!(!compileable);
+        // code ends where body is expected, so it points at the last token
+        assert!(out.contains("at eof.alt:3:4: Expected function body"));
+    }
+
+    #[test]
+    fn namespace_type () {
+        let (compileable, out) = diagnostics_test!("./diagnostics/namespace_type.alt");
+        assert!(!compileable);
+        assert!(out.contains("at namespace_type.alt:5:6: 'geo' is not a type"));
+    }
+
+    #[test]
+    fn codes () {
+        let (_, out) = diagnostics_test!("./diagnostics/unknown_keyword.alt", ErrorFormat::Json);
+        assert!(out.lines().count() > 1);
+        assert!(out.lines().all(|x| !x.contains("\"code\":null")));
+        assert!(out.contains("\"code\":\"unknown-name\""));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{llvm::{codegen::emit_llvm, llc_test}, Target}, lexer::lex, parser::{bindings::{Bindings, Sources}, parse_program}};
+
+    #[test]
+    fn shapes () {
+        compile_test!("./enums/shapes.alt");
+    }
+
+    #[test]
+    fn layout () {
+        let code = include_str!("./enums/shapes.alt");
+        let tokens = lex(code);
+        let sources = Sources::default();
+        let mut bindings = Bindings::new(code, "shapes.alt".into(), None, &sources);
+        let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+        let ir = emit_llvm(program, &Target::default());
+        // payload of 'Line(u8, i64)' is the biggest one
+        assert!(ir.contains("%\"Shape\" = type { i8, [2 x i64] }"));
+        assert!(ir.contains("%\"geo::Dir\" = type { i8 }"));
+        assert!(ir.contains("store i8 3, ptr"));
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./enums/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:8:21: Variant Shape::Circle takes 1 values, got 2"));
+        assert!(out.contains("Missing field \"h\" in initializer of Shape::Rect"));
+        assert!(out.contains("Error[unknown-field] at errors.alt:10:36"));
+        assert!(out.contains("Error[unknown-variant] at errors.alt:11:21: There is no variant \"Square\" in enum Shape"));
+        assert!(out.contains("note: available variants: Empty, Circle, Rect"));
+    }
+
+    #[test]
+    fn private () {
+        let (compileable, out) = diagnostics_test!("./enums/private.alt");
+        assert!(!compileable);
+        assert!(out.contains("at private.alt:6:19"));
+    }
+
+    #[test]
+    fn recursive () {
+        let (compileable, out) = diagnostics_test!("./enums/recursive.alt");
+        assert!(!compileable);
+        assert!(out.contains("Recursive type has infinite size: List <-> List"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./enums/shapes.alt");
+        assert_eq!(out.status.code(), Some(12));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn expressions () {
+        compile_test!("./expressions/expressions.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./expressions/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:3:25: Only 'loop' can be left with value, not 'while' or 'for'"));
+        assert!(out.contains("at errors.alt:6:15: Mismatched types: expected 'f64', got 'i32'"));
+        assert!(out.contains("at errors.alt:8:13: Branches of 'if' end with values of different types: 'i32', 'f64'"));
+    }
+
+    #[test]
+    fn no_value () {
+        let (compileable, out) = diagnostics_test!("./expressions/novalue.alt");
+        assert!(!compileable);
+        assert!(out.contains("at novalue.alt:4:10: 'if' without 'else' has no value"));
+    }
+
+    #[test]
+    fn branches () {
+        let (compileable, out) = diagnostics_test!("./expressions/branches.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[literal-out-of-range] at branches.alt:3:9: Literal '300' is out of range for 'u8'"));
+        assert!(out.contains("Error[mismatched-types] at branches.alt:7:13: Branches of 'if' end with values of different types: 'i32', 'bool'"));
+        // literal at the end of branch gets type expected from the whole
+        assert!(out.contains("at branches.alt:4:9: Literal '300' is out of range for 'u8'"));
+        assert!(out.contains("at branches.alt:5:9: Literal '256' is out of range for 'u8'"));
+        assert!(out.contains("at branches.alt:6:9: Literal '257' is out of range for 'u8'"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./expressions/expressions.alt");
+        assert_eq!(out.status.code(), Some(42));
+    }
+}
\ No newline at end of file
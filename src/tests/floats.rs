@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn arith () {
+        compile_test!("./floats/arith.alt");
+    }
+
+    #[test]
+    fn mismatch () {
+        let (compileable, out) = diagnostics_test!("./floats/mismatch.alt");
+        assert!(!compileable);
+        assert!(out.contains("Mismatched types: expected 'f64'"));
+        assert!(out.contains("Heterogeneous types"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./floats/arith.alt");
+        assert_eq!(out.status.code(), Some(17));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{llvm::{codegen::emit_llvm, llc_test}, Target}, lexer::lex, parser::{bindings::{Bindings, Sources}, parse_program}};
+
+    #[test]
+    fn basic () {
+        compile_test!("./generics/basic.alt");
+    }
+
+    #[test]
+    fn instances () {
+        let code = include_str!("./generics/basic.alt");
+        let tokens = lex(code);
+        let sources = Sources::default();
+        let mut bindings = Bindings::new(code, "basic.alt".into(), None, &sources);
+        let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+        let ir = emit_llvm(program, &Target::default());
+        assert!(ir.contains("define i8 @\"max<u8>\" (i8, i8)"));
+        assert!(ir.contains("define i32 @\"max<i32>\""));
+        assert!(ir.contains("define i64 @\"max<i64>\""));
+        assert!(ir.contains("define %\"geo::Point\" @\"geo::pick<geo::Point>\""));
+        assert!(ir.contains("%\"Pair<i32, bool>\" = type { i32, i1 }"));
+        assert!(ir.contains("%\"Pair<u8, Pair<u8, u8>>\" = type"));
+        // only instances reached from 'main' are emitted
+        assert!(!ir.contains("never"));
+        assert_eq!(ir.matches("define i32 @\"count<i32>\"").count(), 1);
+    }
+
+    #[test]
+    fn conflict () {
+        let (compileable, out) = diagnostics_test!("./generics/conflict.alt");
+        assert!(!compileable);
+        assert!(out.contains("at conflict.alt:8:22: Conflicting types for type parameter 'T' of 'same'"));
+        assert!(out.contains("here it's 'i64', but earlier it was 'u8'"));
+    }
+
+    #[test]
+    fn infer () {
+        let (compileable, out) = diagnostics_test!("./generics/infer.alt");
+        assert!(!compileable);
+        assert!(out.contains("Can't infer type parameter 'T' of 'make'"));
+        assert!(out.contains("help: specify it explicitly: 'make::<...>(...)'"));
+    }
+
+    #[test]
+    fn arity () {
+        let (compileable, out) = diagnostics_test!("./generics/arity.alt");
+        assert!(!compileable);
+        assert!(out.contains("at arity.alt:4:12: Struct 'Box' takes 1 type arguments, got 2"));
+    }
+
+    #[test]
+    fn recursion () {
+        let (compileable, out) = diagnostics_test!("./generics/recursion.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[recursion-limit] at recursion.alt:3:31: Reached recursion limit while instantiating 'deep'"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./generics/basic.alt");
+        assert_eq!(out.status.code(), Some(24));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn globals () {
+        compile_test!("./globals/globals.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./globals/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:2:16: Value must be known at compile time"));
+        assert!(out.contains("at errors.alt:3:15: Overflow while evaluating constant of type 'u8'"));
+        assert!(out.contains("at errors.alt:4:11: Division by zero while evaluating constant"));
+        assert!(out.contains("at errors.alt:9:7: Cannot assign to immutable static 'S'"));
+        assert!(out.contains("at errors.alt:10:16: Length of array can't be negative"));
+        assert!(out.contains("at errors.alt:11:13: Cannot take mutable address to immutable data"));
+    }
+
+    #[test]
+    fn forward () {
+        let (compileable, out) = diagnostics_test!("./globals/forward.alt");
+        assert!(!compileable);
+        assert!(out.contains("at forward.alt:1:16: Constant 'B' is used before its declaration"));
+        assert!(out.contains("declared here"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./globals/globals.alt");
+        assert_eq!(out.status.code(), Some(47));
+    }
+}
\ No newline at end of file
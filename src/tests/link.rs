@@ -0,0 +1,44 @@
+#[allow(unused)]
+macro_rules! run_test {
+    ($p:literal) => {
+        {
+            let code = include_str!($p);
+            let tokens = lex(code);
+            let sources = $crate::parser::bindings::Sources::default();
+            let mut bindings = Bindings::new(code, $p.into(), None, &sources);
+            let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+            if !bindings.is_compileable() { print!("{}", bindings.render_diagnostics($crate::parser::message::ErrorFormat::Human { color: false })); panic!("This test isn't compilable") }
+            let exe = link_test(program, bindings, 0);
+            let out = std::process::Command::new(&exe).output().expect("Failed to run linked executable");
+            let _ = std::fs::remove_file(&exe);
+            out
+        }
+    };
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use crate::{compiler::llvm::{link_test, unix_link_args}, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn exit_code () {
+        let out = run_test!("./link/exit_code.alt");
+        assert_eq!(out.status.code(), Some(55));
+    }
+
+    #[test]
+    fn libc_write () {
+        let out = run_test!("./link/libc_write.alt");
+        assert_eq!(out.status.code(), Some(0));
+        assert_eq!(out.stdout, b"linked\n");
+    }
+    #[test]
+    fn link_args () {
+        assert_eq!(unix_link_args("m"), ["-lm"]);
+        assert_eq!(unix_link_args("libz"), ["-lz"]);
+        assert_eq!(unix_link_args("liblibfoo.so"), ["-llibfoo"]);
+        assert_eq!(unix_link_args("deps/libbar.a"), ["-Ldeps", "-lbar"]);
+        assert_eq!(unix_link_args("/opt/libbaz.so.1"), ["-L/opt", "-l:libbaz.so.1"]);
+        assert_eq!(unix_link_args("plugin.so"), ["-l:plugin.so"]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+#[allow(unused)]
+macro_rules! lint_test {
+    ($p:literal) => {
+        lint_test!($p, LintLevels::default())
+    };
+    ($p:literal, $lints:expr) => {
+        {
+            let code = include_str!($p);
+            let tokens = lex(code);
+            let sources = $crate::parser::bindings::Sources::default();
+            let mut bindings = Bindings::new(code, $p.into(), None, &sources);
+            bindings.set_lints($lints);
+            let _ = parse_program(&tokens, &mut bindings);
+            (bindings.is_compileable(), bindings.render_diagnostics(ErrorFormat::Human { color: false }))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::lex, parser::{bindings::Bindings, lint::{Level, LintLevels}, message::ErrorFormat, parse_program}};
+
+    #[test]
+    fn unused_variables () {
+        let (compileable, out) = lint_test!("./lints/unused_variables.alt");
+        assert!(compileable);
+        assert!(out.contains("Warning[unused-variables] at unused_variables.alt:1:17: Unused variable 'b'"));
+        assert!(out.contains("Unused variable 'c'"));
+        assert!(out.contains("Unused variable 'x'"));
+        // only assigned to, but never read
+        assert!(out.contains("Unused variable 'w'"));
+        assert!(!out.contains("'a'") && !out.contains("'y'") && !out.contains("'_d'"));
+        // in order of declaration within function
+        let at = |name: &str| out.find(&format!("Unused variable '{name}'")).unwrap();
+        assert!(at("b") < at("c") && at("x") < at("w"));
+    }
+
+    #[test]
+    fn aborted () {
+        // uses of variables are in code that is never parsed after syntax error
+        let mut lints = LintLevels::default();
+        lints.set("unused-variables", Level::Deny).unwrap();
+        let (compileable, out) = lint_test!("./lints/aborted.alt", lints);
+        assert!(!compileable);
+        assert!(out.contains("Error[unbalanced-brackets] at aborted.alt:6:17"));
+        assert!(!out.contains("Unused variable"));
+    }
+
+    #[test]
+    fn unused_functions () {
+        let (compileable, out) = lint_test!("./lints/unused_functions.alt");
+        assert!(compileable);
+        assert!(out.contains("Function 'util::forgotten' is never used"));
+        assert!(out.contains("Function 'helper' is never used"));
+        assert_eq!(out.matches("is never used").count(), 2);
+    }
+
+    #[test]
+    fn unreachable_code () {
+        let (compileable, out) = lint_test!("./lints/unreachable.alt");
+        assert!(compileable);
+        assert_eq!(out.matches("Warning[unreachable-code]").count(), 4);
+        assert!(out.contains("unreachable.alt:3:5"));
+        assert!(out.contains("unreachable.alt:8:5"));
+        assert!(out.contains("unreachable.alt:15:9"));
+        assert!(out.contains("unreachable.alt:19:5"));
+    }
+
+    #[test]
+    fn unused_imports () {
+        let (compileable, out) = lint_test!("./lints/unused_imports.alt");
+        assert!(compileable);
+        assert!(out.contains("Unused import 'a::y'"));
+        assert!(out.contains("Unused import 'a::b::*'"));
+        assert!(!out.contains("'a::x'"));
+    }
+
+    #[test]
+    fn duplicate_fields () {
+        let (compileable, out) = lint_test!("./lints/duplicate_fields.alt");
+        assert!(compileable);
+        assert!(out.contains("Field 'x' is specified more than once"));
+        assert!(out.contains("first specified here"));
+    }
+
+    #[test]
+    fn levels () {
+        let mut lints = LintLevels::default();
+        lints.set("unused_variables", Level::Deny).unwrap();
+        let (compileable, out) = lint_test!("./lints/unused_variables.alt", lints);
+        assert!(!compileable);
+        assert!(out.contains("Error[unused-variables]"));
+        assert!(out.contains("'-D unused-variables' is set"));
+
+        let mut lints = LintLevels::default();
+        lints.set("warnings", Level::Allow).unwrap();
+        let (compileable, out) = lint_test!("./lints/unused_variables.alt", lints);
+        assert!(compileable);
+        assert!(out.is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn escapes () {
+        compile_test!("./literals/escapes.alt");
+    }
+
+    #[test]
+    fn escape_errors () {
+        let (compileable, out) = diagnostics_test!("./literals/escape_errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at escape_errors.alt:2:18: Unknown escape sequence '\\q'"));
+        assert!(out.contains("at escape_errors.alt:3:19: Escape '\\x' in string can't go above '\\x7F'"));
+        assert!(out.contains("at escape_errors.alt:4:14: Invalid unicode escape"));
+        assert!(out.contains("at escape_errors.alt:5:13: Character literal must contain exactly one character"));
+        assert!(out.contains("at escape_errors.alt:6:15: Non-ASCII character in byte literal"));
+        assert!(out.contains("at escape_errors.alt:7:15: Unicode escapes aren't allowed in byte literals"));
+        // line of escape inside of multiline string
+        assert!(out.contains("at escape_errors.alt:9:6: Unknown escape sequence '\\z'"));
+    }
+
+    #[test]
+    fn ints () {
+        compile_test!("./literals/ints.alt");
+    }
+
+    #[test]
+    fn int_errors () {
+        let (compileable, out) = diagnostics_test!("./literals/int_errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at int_errors.alt:4:9: Literal '300' is out of range for 'i8'"));
+        assert!(out.contains("at int_errors.alt:5:13: Literal '256' is out of range for 'u8'"));
+        // inferred type
+        assert!(out.contains("at int_errors.alt:6:9: Literal '3000000000' is out of range for 'i32'"));
+        assert!(out.contains("at int_errors.alt:7:9: Literal '-1' is out of range for 'u8'"));
+        assert!(out.contains("at int_errors.alt:8:10: Literal '70000' is out of range for 'u16'"));
+        // operand of comparison has type of the other one
+        assert!(out.contains("at int_errors.alt:9:8: Literal '261' is out of range for 'u8'"));
+        assert!(out.contains("at int_errors.alt:10:8: Literal '300' is out of range for 'u8'"));
+        assert!(out.contains("at int_errors.alt:10:8: Literal '-200' is out of range for 'i8'"));
+        assert!(out.contains("at int_errors.alt:11:13: Invalid digit '8' in base 8 literal"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run_ints () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./literals/ints.alt");
+        assert_eq!(out.status.code(), Some(43));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run_escapes () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./literals/escapes.alt");
+        assert_eq!(out.stdout, "tab\t\"q\" \\ A\0\u{263A}\none line\nc'~\n".as_bytes());
+        assert_eq!(out.status.code(), Some(42));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn r#for () {
+        compile_test!("./loops/for.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./loops/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:3:15: Range must be of integers, not 'f64'"));
+        assert!(out.contains("at errors.alt:4:9: Range yields only numbers, there's no index"));
+        assert!(out.contains("Error[invalid-operand] at errors.alt:5:25: Step of range must be positive, not zero"));
+        assert!(out.contains("Error[invalid-operand] at errors.alt:6:26: Step of range must be positive, not negative"));
+        assert!(out.contains("Error[invalid-operand] at errors.alt:7:26: Step of range must be positive, not negative"));
+        assert!(out.contains("at errors.alt:9:15: Can't iterate over 'bool', expected range, array, slice or pointer to array"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./loops/for.alt");
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "01234 0369 3210 840 \n");
+        assert_eq!(out.status.code(), Some(224));
+    }
+}
\ No newline at end of file
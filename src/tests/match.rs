@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{llvm::{codegen::emit_llvm, llc_test}, Target}, lexer::lex, parser::{bindings::{Bindings, Sources}, parse_program}};
+
+    #[test]
+    fn basic () {
+        compile_test!("./match/basic.alt");
+    }
+
+    #[test]
+    fn value () {
+        compile_test!("./match/value.alt");
+    }
+
+    #[test]
+    fn arm_types () {
+        let (compileable, out) = diagnostics_test!("./match/arms.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[mismatched-types] at arms.alt:3:13: Arms of 'match' end with values of different types: 'i32', 'f64'"));
+    }
+
+    #[test]
+    fn no_value () {
+        let (compileable, out) = diagnostics_test!("./match/novalue.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[no-value] at novalue.alt:3:13: 'match' has no value, as not all of its arms end with one"));
+    }
+
+    #[test]
+    fn lowering () {
+        let code = include_str!("./match/basic.alt");
+        let tokens = lex(code);
+        let sources = Sources::default();
+        let mut bindings = Bindings::new(code, "basic.alt".into(), None, &sources);
+        let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+        let ir = emit_llvm(program, &Target::default());
+        // tags of 'Shape' and plain integer in 'main'
+        assert!(ir.contains("switch i8 %"));
+        assert!(ir.contains("switch i32 %"));
+        // ranges and guards are checked one by one
+        assert!(ir.contains("icmp ule i32"));
+    }
+
+    #[test]
+    fn exhaustive () {
+        let (compileable, out) = diagnostics_test!("./match/exhaustive.alt");
+        assert!(!compileable);
+        assert_eq!(out.matches("Error[non-exhaustive]").count(), 3);
+        assert!(out.contains("at exhaustive.alt:5:5: Match is not exhaustive"));
+        assert!(out.contains("pattern 'Shape::Rect { .. }' is not covered"));
+        assert!(out.contains("pattern 'i32::MIN..=-1' is not covered"));
+        assert!(out.contains("pattern '(false, i32::MIN..=-1)' is not covered"));
+    }
+
+    #[test]
+    fn unreachable () {
+        let (compileable, out) = diagnostics_test!("./match/unreachable.alt");
+        assert!(compileable);
+        assert!(out.contains("Warning[unreachable-code] at unreachable.alt:7:9: Unreachable pattern"));
+        assert!(out.contains("Warning[unreachable-code] at unreachable.alt:11:9: Unreachable pattern"));
+    }
+
+    #[test]
+    fn mismatch () {
+        let (compileable, out) = diagnostics_test!("./match/mismatch.alt");
+        assert!(!compileable);
+        assert!(out.contains("at mismatch.alt:7:9: Mismatched types: expected 'i32', got 'bool'"));
+        assert!(out.contains("at mismatch.alt:9:9: Range pattern is empty"));
+        assert!(out.contains("at mismatch.alt:10:9: Literal is out of range for 'i32'"));
+        assert!(out.contains("There is no field \"z\", available fields: x, y"));
+        // invalid patterns don't make other arms unreachable
+        assert!(!out.contains("Unreachable pattern"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./match/basic.alt");
+        assert_eq!(out.status.code(), Some(128));
+        let out = run_test!("./match/value.alt");
+        assert_eq!(out.status.code(), Some(42));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn receivers () {
+        compile_test!("./methods/receivers.alt");
+    }
+
+    #[test]
+    fn mutability () {
+        let (compileable, out) = diagnostics_test!("./methods/mutability.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[immutable-receiver] at mutability.alt:9:6: Cannot call method 'bump' on immutable variable 'c'"));
+        assert!(out.contains("help: declare it as 'let mut c'"));
+        assert!(out.contains("at mutability.alt:12:6: Cannot call method 'bump' through immutable reference"));
+        assert!(out.contains("note: method takes '&mut Counter', but receiver is '&Counter'"));
+    }
+
+    #[test]
+    fn outside () {
+        let (compileable, out) = diagnostics_test!("./methods/outside.alt");
+        assert!(!compileable);
+        assert!(out.contains("at outside.alt:1:10: 'self' is only allowed in methods inside 'impl'"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./methods/receivers.alt");
+        assert_eq!(out.status.code(), Some(19));
+    }
+}
\ No newline at end of file
@@ -3,12 +3,14 @@ macro_rules! compile_test {
     ($p:literal) => {
         let code = include_str!($p);
         let tokens = lex(code);
-        let mut bindings = Bindings::new(code, $p.into(), None);
-        let program = parse_program(&tokens, &mut bindings);
+        let sources = $crate::parser::bindings::Sources::default();
+        let mut bindings = Bindings::new(code, $p.into(), None, &sources);
+        let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
         if bindings.is_compileable() {
             llc_test(program, bindings, 0);
         }
         else {
+            print!("{}", bindings.render_diagnostics($crate::parser::message::ErrorFormat::Human { color: false }));
             panic!("This test isn't compilable");
         }
     };
@@ -18,4 +20,25 @@ mod namespaces;
 mod fnptr;
 mod control_flow;
 mod tuples_and_arrays;
-mod r#struct;
\ No newline at end of file
+#[macro_use]
+mod link;
+#[macro_use]
+mod diagnostics;
+mod r#struct;
+mod lints;
+mod modules;
+mod floats;
+mod enums;
+mod r#match;
+mod generics;
+mod traits;
+mod methods;
+mod defer;
+mod loops;
+mod bitwise;
+mod literals;
+mod globals;
+mod slices;
+mod strings;
+mod expressions;
+mod opaque;
\ No newline at end of file
@@ -0,0 +1,63 @@
+#[allow(unused)]
+macro_rules! module_test {
+    ($p:literal) => {
+        {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/modules/", $p);
+            let code = include_str!(concat!("./modules/", $p));
+            let tokens = lex(code);
+            let sources = $crate::parser::bindings::Sources::default();
+            let mut bindings = Bindings::new(code, path.into(), None, &sources);
+            let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+            let out = bindings.render_diagnostics(ErrorFormat::Human { color: false });
+            (bindings.is_compileable().then(|| emit_llvm(program, &Target::default())), out)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{llvm::codegen::emit_llvm, Target}, lexer::lex, parser::{bindings::Bindings, message::ErrorFormat, parse_program}};
+
+    #[test]
+    fn import () {
+        let (ir, out) = module_test!("main.alt");
+        let ir = ir.expect("must be compileable");
+        // 'math.alt' is imported twice, but parsed once
+        assert!(ir.contains("@\"math::square\""));
+        assert_eq!(ir.matches("define").count(), 4);
+        assert!(!out.contains("Error"));
+    }
+
+    #[test]
+    fn private () {
+        let (ir, out) = module_test!("private.alt");
+        assert!(ir.is_none());
+        assert!(out.contains("at private.alt:4:18"));
+    }
+
+    #[test]
+    fn cycle () {
+        let (ir, out) = module_test!("cycle_a.alt");
+        assert!(ir.is_none());
+        assert!(out.contains("Error[import-cycle] at cycle_b.alt:1:8: Import cycle detected"));
+        assert!(out.contains("cycle_a.alt -> "));
+    }
+
+    #[test]
+    fn foreign_diagnostics () {
+        // reported against file they are in
+        let (ir, out) = module_test!("broken.alt");
+        assert!(ir.is_none());
+        assert!(out.contains("at broken_lib.alt:3:7: Cannot assign twice to immutable variable 'a'"));
+        assert!(out.contains("    a = 2\n"));
+    }
+
+    #[test]
+    fn foreign_label () {
+        // label is rendered against its own file
+        let (ir, out) = module_test!("foreign_label.alt");
+        assert!(ir.is_none());
+        assert!(out.contains("at foreign_label.alt:6:5: Method 'area' doesn't match its declaration in trait 'shape::Shape'"));
+        assert!(out.contains("in shape.alt:2:5\n    fn area (self: &Self) i32\n    ^^ declared here"));
+    }
+}
\ No newline at end of file
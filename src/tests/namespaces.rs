@@ -4,8 +4,9 @@ macro_rules! nstest {
         {
             let code = include_str!($p);
             let tokens = lex(code);
-            let mut bindings = Bindings::new(code, $p.into(), None);
-            parse_program(&tokens, &mut bindings);
+            let sources = $crate::parser::bindings::Sources::default();
+            let mut bindings = Bindings::new(code, $p.into(), None, &sources);
+            let _ = parse_program(&tokens, &mut bindings);
             bindings.is_compileable()
         }
     };
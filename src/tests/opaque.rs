@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn opaque () {
+        compile_test!("./opaque/opaque.alt");
+    }
+
+    #[test]
+    fn by_value () {
+        let (compileable, out) = diagnostics_test!("./opaque/by_value.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[opaque-by-value] at by_value.alt:3:14: Opaque type 'FILE' has no known size, so it can't be used by value"));
+    }
+
+    #[test]
+    fn deref () {
+        let (compileable, out) = diagnostics_test!("./opaque/deref.alt");
+        assert!(!compileable);
+        assert!(out.contains("at deref.alt:6:14: Cannot get value of opaque type 'FILE' behind address"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./opaque/opaque.alt");
+        assert_eq!(out.status.code(), Some(4));
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "opaque\ntypes\n");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn slices () {
+        compile_test!("./slices/slices.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./slices/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:4:20: Mismatched types"));
+        assert!(out.contains("at errors.alt:5:9: Mismatched types: expected '&mut [i32]', got '&[i32]'"));
+        assert!(out.contains("Error[immutable-assign] at errors.alt:6:10: Cannot assign to element of immutable slice '&[i32]'"));
+        assert!(out.contains("Error[immutable-assign] at errors.alt:7:14: Cannot assign to element of immutable slice '&[u8]'"));
+        assert!(out.contains("note: string literals are read-only"));
+        assert!(out.contains("at errors.alt:10:15: There is no 'size' in type '&[i32]', only 'len'"));
+        assert!(out.contains("Error[invalid-range] at errors.alt:8:14: Start of range 2 is past its end 1"));
+        // omitted end of array is its length
+        assert!(out.contains("Error[invalid-range] at errors.alt:9:16: Start of range 4 is past its end 3"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./slices/slices.alt");
+        assert_eq!(out.status.code(), Some(36));
+    }
+}
\ No newline at end of file
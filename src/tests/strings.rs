@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::llvm::llc_test, lexer::lex, parser::{bindings::Bindings, parse_program}};
+
+    #[test]
+    fn strings () {
+        compile_test!("./strings/strings.alt");
+    }
+
+    #[test]
+    fn errors () {
+        let (compileable, out) = diagnostics_test!("./strings/errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("at errors.alt:4:13: C string literal can't contain NUL"));
+        assert!(out.contains("at errors.alt:5:11: Mismatched types: expected '&mut [u8]', got '&[u8]'"));
+        assert!(out.contains("at errors.alt:6:13: Cannot take mutable address to immutable data"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./strings/strings.alt");
+        assert_eq!(out.status.code(), Some(4));
+        // 'puts' is buffered, while 'write' isn't
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "ana\nstrings\n");
+    }
+}
\ No newline at end of file
@@ -7,4 +7,16 @@ mod tests {
     fn struct1 () {
         compile_test!("./struct/struct1.alt");
     }
+
+    #[test]
+    fn public () {
+        compile_test!("./struct/public.alt");
+    }
+
+    #[test]
+    fn private () {
+        let (compileable, out) = diagnostics_test!("./struct/private.alt");
+        assert!(!compileable);
+        assert!(out.contains("at private.alt:6:18"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::{compiler::{llvm::{codegen::emit_llvm, llc_test}, Target}, lexer::lex, parser::{bindings::{Bindings, Sources}, parse_program}};
+
+    #[test]
+    fn shapes () {
+        compile_test!("./traits/shapes.alt");
+    }
+
+    #[test]
+    fn impls () {
+        let (compileable, out) = diagnostics_test!("./traits/impls.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[incompatible-method] at impls.alt:9:5: Method 'area' doesn't match its declaration in trait 'Shape'"));
+        assert!(out.contains("note: expected 'fn (&Circle) i32', found 'fn (Circle) i32'"));
+        assert!(out.contains("at impls.alt:10:5: Method 'corners' is not a member of trait 'Shape'"));
+        assert!(out.contains("Error[missing-method] at impls.alt:8:1: Method 'sides' of trait 'Shape' is not implemented for 'Circle'"));
+        assert!(out.contains("at impls.alt:13:1: Trait 'Shape' is already implemented for 'Circle'"));
+        assert!(!out.contains("Function name must be unique"));
+    }
+
+    #[test]
+    fn bound () {
+        let (compileable, out) = diagnostics_test!("./traits/bound.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[unsatisfied-bound] at bound.alt:11:12: Type 'u8' doesn't implement trait 'Shape'"));
+        assert!(out.contains("note: required by bound 'T Shape' of 'total'"));
+    }
+
+    #[test]
+    fn vtables () {
+        let code = include_str!("./traits/dyn.alt");
+        let tokens = lex(code);
+        let sources = Sources::default();
+        let mut bindings = Bindings::new(code, "dyn.alt".into(), None, &sources);
+        let program = parse_program(&tokens, &mut bindings).unwrap_or_default();
+        let ir = emit_llvm(program, &Target::default());
+        assert!(ir.contains("constant [2 x ptr] [ptr @\"<Square as Shape>::area\", ptr @\"<Square as Shape>::grow\"]"));
+        assert!(ir.contains("constant [2 x ptr] [ptr @\"<Rect as Shape>::area\", ptr @\"<Rect as Shape>::grow\"]"));
+        assert!(ir.contains("define i32 @\"area_of\" ({ ptr, ptr })"));
+    }
+
+    #[test]
+    fn dyn_errors () {
+        let (compileable, out) = diagnostics_test!("./traits/dyn_errors.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[unsatisfied-bound] at dyn_errors.alt:21:9: Type 'i32' doesn't implement trait 'Shape'"));
+        assert!(out.contains("note: required to use it as '&dyn Shape'"));
+        assert!(out.contains("at dyn_errors.alt:14:6: Method 'grow' takes '&mut Self', but it's called through '&dyn Shape'"));
+        assert!(!out.contains("Mismatched types"));
+    }
+
+    #[test]
+    fn dyn_incompatible () {
+        let (compileable, out) = diagnostics_test!("./traits/dyn_incompatible.alt");
+        assert!(!compileable);
+        assert!(out.contains("Error[dyn-incompatible] at dyn_incompatible.alt:11:9: Trait 'Same' can't be used with 'dyn'"));
+        assert!(out.contains("note: method 'same' must take '&Self' or '&mut Self' as first argument"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./traits/shapes.alt");
+        assert_eq!(out.status.code(), Some(44));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn run_dyn () {
+        use crate::compiler::llvm::link_test;
+        let out = run_test!("./traits/dyn.alt");
+        assert_eq!(out.status.code(), Some(46));
+    }
+}
\ No newline at end of file
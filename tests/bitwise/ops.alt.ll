@@ -0,0 +1,90 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i32 @putchar (i32)
+define void @"bit" (i1) {
+entry:
+    %1 = alloca i1
+    %_0 = alloca i32
+    store i1 %0, ptr %1
+    %2 = load i1, ptr %1
+    br i1 %2, label %3, label %5
+    3:
+        %4 = call i32 @"putchar" (i32 49)
+        store i32 %4, ptr %_0
+        br label %7
+    5:
+        %6 = call i32 @"putchar" (i32 48)
+        store i32 %6, ptr %_0
+        br label %7
+    %8 = load i32, ptr %_0
+    ret void
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i8
+    %_1 = alloca i8
+    %_2 = alloca i8
+    %_3 = alloca i8
+    %_4 = alloca i32
+    store i8 12, ptr %_0
+    store i8 10, ptr %_1
+    %0 = load i8, ptr %_0
+    %1 = load i8, ptr %_1
+    %2 = and i8 %0, %1
+    %3 = icmp eq i8 %2, 8
+    call void @"bit" (i1 %3)
+    %4 = load i8, ptr %_0
+    %5 = load i8, ptr %_1
+    %6 = or i8 %4, %5
+    %7 = icmp eq i8 %6, 14
+    call void @"bit" (i1 %7)
+    %8 = load i8, ptr %_0
+    %9 = load i8, ptr %_1
+    %10 = xor i8 %8, %9
+    %11 = icmp eq i8 %10, 6
+    call void @"bit" (i1 %11)
+    %12 = load i8, ptr %_0
+    %13 = xor i8 %12, -1
+    %14 = icmp eq i8 %13, 243
+    call void @"bit" (i1 %14)
+    %15 = add i32 2, 1
+    %16 = shl i32 1, %15
+    %17 = icmp eq i32 %16, 8
+    call void @"bit" (i1 %17)
+    %18 = load i8, ptr %_0
+    %19 = lshr i8 %18, 2
+    %20 = icmp eq i8 %19, 3
+    call void @"bit" (i1 %20)
+    store i8 128, ptr %_2
+    store i8 -128, ptr %_3
+    %21 = load i8, ptr %_2
+    %22 = lshr i8 %21, 7
+    %23 = icmp eq i8 %22, 1
+    call void @"bit" (i1 %23)
+    %24 = load i8, ptr %_3
+    %25 = ashr i8 %24, 7
+    %26 = icmp eq i8 %25, -1
+    call void @"bit" (i1 %26)
+    %27 = call i32 @"putchar" (i32 10)
+    store i32 6, ptr %_4
+    %28 = load i32, ptr %_4
+    %29 = and i32 %28, 3
+    store i32 %29, ptr %_4
+    %30 = load i32, ptr %_4
+    %31 = or i32 %30, 8
+    store i32 %31, ptr %_4
+    %32 = load i32, ptr %_4
+    %33 = xor i32 %32, 1
+    store i32 %33, ptr %_4
+    %34 = load i32, ptr %_4
+    %35 = shl i32 %34, 2
+    store i32 %35, ptr %_4
+    %36 = load i32, ptr %_4
+    %37 = ashr i32 %36, 1
+    store i32 %37, ptr %_4
+    %38 = load i32, ptr %_4
+    %39 = xor i32 0, -1
+    %40 = add i32 %38, %39
+    ret i32 %40
+}
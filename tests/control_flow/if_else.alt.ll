@@ -1,3 +1,6 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
 define i32 @"cmp_x" (i32, i32) {
 entry:
     %2 = alloca i32
@@ -13,7 +16,7 @@ entry:
     8:
         %9 = load i32, ptr %2
         %10 = load i32, ptr %3
-        %11 = icmp ugt i32 %9, %10
+        %11 = icmp sgt i32 %9, %10
         br i1 %11, label %12, label %13
         12:
             ret i32 33
@@ -0,0 +1,84 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i32 @putchar (i32)
+define i32 @"early" (i1) {
+entry:
+    %1 = alloca i1
+    store i1 %0, ptr %1
+    %2 = load i1, ptr %1
+    br i1 %2, label %3, label %6
+    3:
+        %4 = call i32 @"putchar" (i32 100)
+        %5 = call i32 @"putchar" (i32 101)
+        ret i32 1
+    %7 = call i32 @"putchar" (i32 102)
+    %8 = call i32 @"putchar" (i32 101)
+    ret i32 0
+}
+define void @"counted" () {
+entry:
+    %_0 = alloca i32
+    store i32 0, ptr %_0
+    br label %0
+    0:
+        %1 = load i32, ptr %_0
+        %2 = add i32 %1, 1
+        store i32 %2, ptr %_0
+        %3 = load i32, ptr %_0
+        %4 = icmp eq i32 %3, 2
+        br i1 %4, label %5, label %9
+        5:
+            %6 = load i32, ptr %_0
+            %7 = add i32 48, %6
+            %8 = call i32 @"putchar" (i32 %7)
+            br label %0
+        %10 = load i32, ptr %_0
+        %11 = icmp eq i32 %10, 4
+        br i1 %11, label %12, label %16
+        12:
+            %13 = load i32, ptr %_0
+            %14 = add i32 48, %13
+            %15 = call i32 @"putchar" (i32 %14)
+            br label %end.0
+        %17 = load i32, ptr %_0
+        %18 = add i32 48, %17
+        %19 = call i32 @"putchar" (i32 %18)
+        br label %0
+    end.0:
+    ret void
+}
+define void @"nested" () {
+entry:
+    %_0 = alloca i32
+    store i32 0, ptr %_0
+    br label %0
+    0:
+        br label %1
+        1:
+            %2 = load i32, ptr %_0
+            %3 = add i32 %2, 1
+            store i32 %3, ptr %_0
+            %4 = load i32, ptr %_0
+            %5 = icmp eq i32 %4, 2
+            br i1 %5, label %6, label %8
+            6:
+                %7 = call i32 @"putchar" (i32 110)
+                br label %end.0
+            %9 = call i32 @"putchar" (i32 110)
+            br label %end.1
+        end.1:
+        br label %0
+    end.0:
+    ret void
+}
+define i32 @"main" () {
+entry:
+    %0 = call i32 @"early" (i1 1)
+    %1 = call i32 @"early" (i1 0)
+    call void @"counted" ()
+    call void @"nested" ()
+    %2 = call i32 @"putchar" (i32 33)
+    %3 = call i32 @"putchar" (i32 10)
+    ret i32 0
+}
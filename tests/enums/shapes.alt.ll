@@ -0,0 +1,76 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"Shape" = type { i8, [2 x i64] }
+%"geo::Dir" = type { i8 }
+define %"geo::Dir" @"geo::flip" (%"geo::Dir") {
+entry:
+    %1 = alloca %"geo::Dir"
+    %_0 = alloca %"geo::Dir"
+    store %"geo::Dir" %0, ptr %1
+    %2 = getelementptr inbounds %"geo::Dir", ptr %_0, i64 0, i32 0
+    store i8 0, ptr %2
+    %3 = load %"geo::Dir", ptr %_0
+    ret %"geo::Dir" %3
+}
+%"Tile" = type { %"Shape", %"geo::Dir" }
+define i32 @"keep" (%"Shape") {
+entry:
+    %1 = alloca %"Shape"
+    store %"Shape" %0, ptr %1
+    ret i32 7
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca %"Shape"
+    %_1 = alloca i32
+    %_2 = alloca %"Shape"
+    %_3 = alloca %"Shape"
+    %_4 = alloca %"Tile"
+    %_5 = alloca %"geo::Dir"
+    %_6 = alloca %"geo::Dir"
+    %_7 = alloca %"Shape"
+    %0 = getelementptr inbounds %"Shape", ptr %_0, i64 0, i32 0
+    store i8 0, ptr %0
+    store i32 2, ptr %_1
+    %1 = getelementptr inbounds %"Shape", ptr %_2, i64 0, i32 0
+    store i8 2, ptr %1
+    %2 = getelementptr inbounds %"Shape", ptr %_2, i64 0, i32 1
+    %3 = load i32, ptr %_1
+    %4 = getelementptr inbounds {i32, i32}, ptr %2, i64 0, i32 0
+    store i32 %3, ptr %4
+    %5 = load i32, ptr %_1
+    %6 = mul i32 %5, 3
+    %7 = getelementptr inbounds {i32, i32}, ptr %2, i64 0, i32 1
+    store i32 %6, ptr %7
+    %8 = getelementptr inbounds %"Shape", ptr %_3, i64 0, i32 0
+    store i8 3, ptr %8
+    %9 = getelementptr inbounds %"Shape", ptr %_3, i64 0, i32 1
+    %10 = getelementptr inbounds {i8, i64}, ptr %9, i64 0, i32 0
+    store i8 1, ptr %10
+    %11 = getelementptr inbounds {i8, i64}, ptr %9, i64 0, i32 1
+    store i64 2, ptr %11
+    %12 = getelementptr inbounds %"Tile", ptr %_4, i64 0, i32 0
+    %13 = getelementptr inbounds %"Shape", ptr %12, i64 0, i32 0
+    store i8 1, ptr %13
+    %14 = getelementptr inbounds %"Shape", ptr %12, i64 0, i32 1
+    %15 = getelementptr inbounds {i32}, ptr %14, i64 0, i32 0
+    store i32 4, ptr %15
+    %16 = getelementptr inbounds %"Tile", ptr %_4, i64 0, i32 1
+    %17 = getelementptr inbounds %"geo::Dir", ptr %16, i64 0, i32 0
+    store i8 2, ptr %17
+    %18 = getelementptr inbounds %"geo::Dir", ptr %_6, i64 0, i32 0
+    store i8 1, ptr %18
+    %19 = load %"geo::Dir", ptr %_6
+    %20 = call %"geo::Dir" @"geo::flip" (%"geo::Dir" %19)
+    store %"geo::Dir" %20, ptr %_5
+    %21 = getelementptr inbounds %"Shape", ptr %_7, i64 0, i32 0
+    store i8 1, ptr %21
+    %22 = getelementptr inbounds %"Shape", ptr %_7, i64 0, i32 1
+    %23 = getelementptr inbounds {i32}, ptr %22, i64 0, i32 0
+    store i32 5, ptr %23
+    %24 = load %"Shape", ptr %_7
+    %25 = call i32 @"keep" (%"Shape" %24)
+    %26 = add i32 %25, 5
+    ret i32 %26
+}
@@ -0,0 +1,392 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"P" = type { i32, i32 }
+define i32 @"add" (i32, i32) {
+entry:
+    %2 = alloca i32
+    %3 = alloca i32
+    store i32 %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = load i32, ptr %2
+    %5 = load i32, ptr %3
+    %6 = add i32 %4, %5
+    ret i32 %6
+}
+define i64 @"pick" (i1) {
+entry:
+    %1 = alloca i1
+    %_0 = alloca i64
+    store i1 %0, ptr %1
+    %2 = load i1, ptr %1
+    br i1 %2, label %3, label %4
+    3:
+        store i64 10, ptr %_0
+        br label %5
+    4:
+        store i64 20, ptr %_0
+        br label %5
+    %6 = load i64, ptr %_0
+    ret i64 %6
+}
+define i32 @"sign" (i32) {
+entry:
+    %1 = alloca i32
+    %_0 = alloca i32
+    store i32 %0, ptr %1
+    %2 = load i32, ptr %1
+    %3 = icmp slt i32 %2, 0
+    br i1 %3, label %4, label %5
+    4:
+        store i32 -1, ptr %_0
+        br label %10
+    5:
+        %6 = load i32, ptr %1
+        %7 = icmp eq i32 %6, 0
+        br i1 %7, label %8, label %9
+        8:
+            store i32 0, ptr %_0
+            br label %10
+        9:
+            store i32 1, ptr %_0
+            br label %10
+    %11 = load i32, ptr %_0
+    ret i32 %11
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i32
+    %_1 = alloca i64
+    %_2 = alloca i1
+    %_3 = alloca i1
+    %_4 = alloca i32
+    %_5 = alloca i32
+    %_6 = alloca i32
+    %_7 = alloca i32
+    %_8 = alloca i32
+    %_9 = alloca %"P"
+    %_10 = alloca %"P"
+    %_11 = alloca %"P"
+    %_12 = alloca i32
+    %_13 = alloca i32
+    %_14 = alloca i32
+    %_15 = alloca i8
+    %_16 = alloca i1
+    %_17 = alloca i1
+    %_18 = alloca [3 x i32]
+    %_19 = alloca i1
+    %_20 = alloca i1
+    %_21 = alloca {i32, i32}
+    %_22 = alloca i1
+    %_23 = alloca %"P"
+    %_24 = alloca i1
+    %_25 = alloca i32
+    %_26 = alloca i32
+    %_27 = alloca i32
+    %_28 = alloca i32
+    br i1 1, label %0, label %1
+    0:
+        store i32 1, ptr %_0
+        br label %2
+    1:
+        store i32 2, ptr %_0
+        br label %2
+    %3 = load i32, ptr %_0
+    %4 = icmp ne i32 %3, 1
+    br i1 %4, label %5, label %6
+    5:
+        ret i32 1
+    %7 = load i32, ptr %_0
+    %8 = icmp sgt i32 %7, 5
+    br i1 %8, label %9, label %10
+    9:
+        store i64 3, ptr %_1
+        br label %11
+    10:
+        store i64 4, ptr %_1
+        br label %11
+    %12 = load i64, ptr %_1
+    %13 = icmp ne i64 %12, 4
+    br i1 %13, label %14, label %15
+    14:
+        ret i32 2
+    %16 = call i64 @"pick" (i1 0)
+    %17 = icmp ne i64 %16, 20
+    br i1 %17, label %18, label %19
+    18:
+        ret i32 3
+    %20 = call i32 @"sign" (i32 -7)
+    %21 = icmp ne i32 %20, -1
+    store i1 %21, ptr %_2
+    br i1 %21, label %end.22, label %22
+    %23 = call i32 @"sign" (i32 0)
+    %24 = icmp ne i32 %23, 0
+    store i1 %24, ptr %_2
+    br label %end.22
+    end.22:
+    %25 = load i1, ptr %_2
+    store i1 %25, ptr %_3
+    br i1 %25, label %end.26, label %26
+    %27 = call i32 @"sign" (i32 9)
+    %28 = icmp ne i32 %27, 1
+    store i1 %28, ptr %_3
+    br label %end.26
+    end.26:
+    %29 = load i1, ptr %_3
+    br i1 %29, label %30, label %31
+    30:
+        ret i32 4
+    %32 = load i32, ptr %_0
+    %33 = icmp sgt i32 %32, 0
+    br i1 %33, label %34, label %41
+    34:
+        %35 = load i32, ptr %_0
+        %36 = icmp sgt i32 %35, 2
+        br i1 %36, label %37, label %38
+        37:
+            store i32 1, ptr %_5
+            br label %39
+        38:
+            store i32 2, ptr %_5
+            br label %39
+        %40 = load i32, ptr %_5
+        store i32 %40, ptr %_4
+        br label %42
+    41:
+        store i32 3, ptr %_4
+        br label %42
+    %43 = load i32, ptr %_4
+    store i32 5, ptr %_6
+    %44 = load i32, ptr %_6
+    %45 = call i32 @"add" (i32 %43, i32 %44)
+    %46 = icmp ne i32 %45, 7
+    br i1 %46, label %47, label %48
+    47:
+        ret i32 5
+    %49 = load i32, ptr %_0
+    %50 = add i32 %49, 2
+    store i32 %50, ptr %_8
+    %51 = load i32, ptr %_8
+    %52 = mul i32 %51, 2
+    store i32 %52, ptr %_7
+    %53 = load i32, ptr %_7
+    %54 = icmp ne i32 %53, 6
+    br i1 %54, label %55, label %56
+    55:
+        ret i32 6
+    %57 = load i32, ptr %_7
+    %58 = icmp eq i32 %57, 6
+    br i1 %58, label %59, label %63
+    59:
+        %60 = getelementptr inbounds %"P", ptr %_10, i64 0, i32 0
+        store i32 7, ptr %60
+        %61 = getelementptr inbounds %"P", ptr %_10, i64 0, i32 1
+        store i32 8, ptr %61
+        %62 = load %"P", ptr %_10
+        store %"P" %62, ptr %_9
+        br label %67
+    63:
+        %64 = getelementptr inbounds %"P", ptr %_11, i64 0, i32 0
+        store i32 0, ptr %64
+        %65 = getelementptr inbounds %"P", ptr %_11, i64 0, i32 1
+        store i32 0, ptr %65
+        %66 = load %"P", ptr %_11
+        store %"P" %66, ptr %_9
+        br label %67
+    %68 = getelementptr inbounds %"P", ptr %_9, i64 0, i32 0
+    %69 = load i32, ptr %68
+    %70 = getelementptr inbounds %"P", ptr %_9, i64 0, i32 1
+    %71 = load i32, ptr %70
+    %72 = add i32 %69, %71
+    %73 = icmp ne i32 %72, 15
+    br i1 %73, label %74, label %75
+    74:
+        ret i32 7
+    store i32 0, ptr %_12
+    br label %76
+    76:
+        %77 = load i32, ptr %_12
+        %78 = add i32 %77, 1
+        store i32 %78, ptr %_12
+        %79 = load i32, ptr %_12
+        %80 = icmp eq i32 %79, 4
+        br i1 %80, label %81, label %84
+        81:
+            %82 = load i32, ptr %_12
+            %83 = mul i32 %82, 10
+            store i32 %83, ptr %_13
+            br label %end.76
+        br label %76
+    end.76:
+    %85 = load i32, ptr %_13
+    %86 = icmp ne i32 %85, 40
+    br i1 %86, label %87, label %88
+    87:
+        ret i32 8
+    store i32 0, ptr %_14
+    br label %89
+    89:
+        br label %90
+        90:
+            %91 = load i32, ptr %_14
+            %92 = icmp eq i32 %91, 2
+            br i1 %92, label %93, label %96
+            93:
+                store i8 200, ptr %_15
+                %94 = load i32, ptr %_14
+                %95 = add i32 %94, 1
+                store i32 %95, ptr %_14
+                br label %end.89
+            br label %end.90
+        end.90:
+        %97 = load i32, ptr %_14
+        %98 = add i32 %97, 1
+        store i32 %98, ptr %_14
+        br label %89
+    end.89:
+    %99 = load i8, ptr %_15
+    %100 = icmp ne i8 %99, 200
+    store i1 %100, ptr %_16
+    br i1 %100, label %end.101, label %101
+    %102 = load i32, ptr %_14
+    %103 = icmp ne i32 %102, 3
+    store i1 %103, ptr %_16
+    br label %end.101
+    end.101:
+    %104 = load i1, ptr %_16
+    br i1 %104, label %105, label %106
+    105:
+        ret i32 9
+    store i1 1, ptr %_17
+    %107 = getelementptr inbounds i32, ptr %_18, i64 0
+    %108 = load i1, ptr %_17
+    br i1 %108, label %109, label %110
+    109:
+        store i32 4, ptr %107
+        br label %111
+    110:
+        store i32 5, ptr %107
+        br label %111
+    %112 = getelementptr inbounds i32, ptr %_18, i64 1
+    store i32 6, ptr %112
+    %113 = getelementptr inbounds i32, ptr %_18, i64 2
+    br label %114
+    114:
+        store i32 7, ptr %113
+        br label %end.114
+    end.114:
+    %115 = getelementptr inbounds [3 x i32], ptr %_18, i64 0, i64 0
+    %116 = load i32, ptr %115
+    %117 = icmp ne i32 %116, 4
+    store i1 %117, ptr %_19
+    br i1 %117, label %end.118, label %118
+    %119 = getelementptr inbounds [3 x i32], ptr %_18, i64 0, i64 1
+    %120 = load i32, ptr %119
+    %121 = icmp ne i32 %120, 6
+    store i1 %121, ptr %_19
+    br label %end.118
+    end.118:
+    %122 = load i1, ptr %_19
+    store i1 %122, ptr %_20
+    br i1 %122, label %end.123, label %123
+    %124 = getelementptr inbounds [3 x i32], ptr %_18, i64 0, i64 2
+    %125 = load i32, ptr %124
+    %126 = icmp ne i32 %125, 7
+    store i1 %126, ptr %_20
+    br label %end.123
+    end.123:
+    %127 = load i1, ptr %_20
+    br i1 %127, label %128, label %129
+    128:
+        ret i32 11
+    %130 = getelementptr inbounds {i32, i32}, ptr %_21, i64 0, i32 0
+    %131 = load i1, ptr %_17
+    br i1 %131, label %132, label %133
+    132:
+        store i32 1, ptr %130
+        br label %134
+    133:
+        store i32 2, ptr %130
+        br label %134
+    %135 = getelementptr inbounds {i32, i32}, ptr %_21, i64 0, i32 1
+    store i32 3, ptr %135
+    %136 = getelementptr inbounds {i32, i32}, ptr %_21, i64 0, i32 0
+    %137 = load i32, ptr %136
+    %138 = icmp ne i32 %137, 1
+    store i1 %138, ptr %_22
+    br i1 %138, label %end.139, label %139
+    %140 = getelementptr inbounds {i32, i32}, ptr %_21, i64 0, i32 1
+    %141 = load i32, ptr %140
+    %142 = icmp ne i32 %141, 3
+    store i1 %142, ptr %_22
+    br label %end.139
+    end.139:
+    %143 = load i1, ptr %_22
+    br i1 %143, label %144, label %145
+    144:
+        ret i32 12
+    %146 = getelementptr inbounds %"P", ptr %_23, i64 0, i32 0
+    %147 = load i1, ptr %_17
+    br i1 %147, label %148, label %149
+    148:
+        store i32 8, ptr %146
+        br label %150
+    149:
+        store i32 9, ptr %146
+        br label %150
+    %151 = getelementptr inbounds %"P", ptr %_23, i64 0, i32 1
+    br label %152
+    152:
+        store i32 2, ptr %151
+        br label %end.152
+    end.152:
+    %153 = getelementptr inbounds %"P", ptr %_23, i64 0, i32 0
+    %154 = load i32, ptr %153
+    %155 = icmp ne i32 %154, 8
+    store i1 %155, ptr %_24
+    br i1 %155, label %end.156, label %156
+    %157 = getelementptr inbounds %"P", ptr %_23, i64 0, i32 1
+    %158 = load i32, ptr %157
+    %159 = icmp ne i32 %158, 2
+    store i1 %159, ptr %_24
+    br label %end.156
+    end.156:
+    %160 = load i1, ptr %_24
+    br i1 %160, label %161, label %162
+    161:
+        ret i32 13
+    %163 = load i1, ptr %_17
+    br i1 %163, label %164, label %165
+    164:
+        store i32 3, ptr %_26
+        br label %166
+    165:
+        store i32 4, ptr %_26
+        br label %166
+    %167 = load i32, ptr %_26
+    br label %168
+    168:
+        store i32 5, ptr %_27
+        br label %end.168
+    end.168:
+    %169 = load i32, ptr %_27
+    %170 = call i32 @"add" (i32 %167, i32 %169)
+    store i32 %170, ptr %_25
+    %171 = load i32, ptr %_25
+    %172 = icmp ne i32 %171, 8
+    br i1 %172, label %173, label %174
+    173:
+        ret i32 14
+    store i32 1, ptr %_28
+    %175 = load i32, ptr %_28
+    %176 = icmp eq i32 %175, 1
+    br i1 %176, label %177, label %178
+    177:
+        store i32 50, ptr %_28
+        br label %179
+    178:
+        ret i32 10
+    %180 = load i32, ptr %_28
+    %181 = sub i32 %180, 8
+    ret i32 %181
+}
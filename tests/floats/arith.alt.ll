@@ -0,0 +1,48 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+define double @"half" (double) {
+entry:
+    %1 = alloca double
+    store double %0, ptr %1
+    %2 = load double, ptr %1
+    %3 = fdiv double %2, 0x4000000000000000
+    ret double %3
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca float
+    %_1 = alloca double
+    %_2 = alloca double
+    store float 0x3FF8000000000000, ptr %_0
+    %0 = call double @"half" (double 0x401C000000000000)
+    %1 = fadd double %0, 0x3FD0000000000000
+    store double %1, ptr %_1
+    %2 = load double, ptr %_1
+    %3 = fneg double %2
+    store double %3, ptr %_2
+    %4 = load double, ptr %_2
+    %5 = fcmp olt double %4, 0xC00C000000000000
+    br i1 %5, label %6, label %23
+    6:
+        %7 = load double, ptr %_1
+        %8 = fcmp une double %7, 0x4008000000000000
+        br i1 %8, label %9, label %22
+        9:
+            %10 = load float, ptr %_0
+            %11 = fpext float %10 to double
+            %12 = fcmp ogt double %11, 0x3FF0000000000000
+            br i1 %12, label %13, label %21
+            13:
+                %14 = load double, ptr %_1
+                %15 = fmul double %14, 0x4010000000000000
+                %16 = fptosi double %15 to i32
+                %17 = sitofp i32 10 to double
+                %18 = fdiv double %17, 0x4010000000000000
+                %19 = fptosi double %18 to i32
+                %20 = add i32 %16, %19
+                ret i32 %20
+            br label %22
+        br label %23
+    ret i32 0
+}
@@ -0,0 +1,163 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"Pair<i32, bool>" = type { i32, i1 }
+%"Pair<u8, u8>" = type { i8, i8 }
+%"Pair<u8, Pair<u8, u8>>" = type { i8, %"Pair<u8, u8>" }
+%"geo::Point" = type { i32, i32 }
+define %"geo::Point" @"geo::pick<geo::Point>" (i1, %"geo::Point", %"geo::Point") {
+entry:
+    %3 = alloca i1
+    %4 = alloca %"geo::Point"
+    %5 = alloca %"geo::Point"
+    store i1 %0, ptr %3
+    store %"geo::Point" %1, ptr %4
+    store %"geo::Point" %2, ptr %5
+    %6 = load i1, ptr %3
+    br i1 %6, label %7, label %9
+    7:
+        %8 = load %"geo::Point", ptr %4
+        ret %"geo::Point" %8
+    %10 = load %"geo::Point", ptr %5
+    ret %"geo::Point" %10
+}
+define i8 @"max<u8>" (i8, i8) {
+entry:
+    %2 = alloca i8
+    %3 = alloca i8
+    store i8 %0, ptr %2
+    store i8 %1, ptr %3
+    %4 = load i8, ptr %2
+    %5 = load i8, ptr %3
+    %6 = icmp ugt i8 %4, %5
+    br i1 %6, label %7, label %9
+    7:
+        %8 = load i8, ptr %2
+        ret i8 %8
+    %10 = load i8, ptr %3
+    ret i8 %10
+}
+define i32 @"max<i32>" (i32, i32) {
+entry:
+    %2 = alloca i32
+    %3 = alloca i32
+    store i32 %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = load i32, ptr %2
+    %5 = load i32, ptr %3
+    %6 = icmp sgt i32 %4, %5
+    br i1 %6, label %7, label %9
+    7:
+        %8 = load i32, ptr %2
+        ret i32 %8
+    %10 = load i32, ptr %3
+    ret i32 %10
+}
+define i64 @"max<i64>" (i64, i64) {
+entry:
+    %2 = alloca i64
+    %3 = alloca i64
+    store i64 %0, ptr %2
+    store i64 %1, ptr %3
+    %4 = load i64, ptr %2
+    %5 = load i64, ptr %3
+    %6 = icmp sgt i64 %4, %5
+    br i1 %6, label %7, label %9
+    7:
+        %8 = load i64, ptr %2
+        ret i64 %8
+    %10 = load i64, ptr %3
+    ret i64 %10
+}
+define i32 @"first<i32, bool>" (%"Pair<i32, bool>") {
+entry:
+    %1 = alloca %"Pair<i32, bool>"
+    store %"Pair<i32, bool>" %0, ptr %1
+    %2 = getelementptr inbounds %"Pair<i32, bool>", ptr %1, i64 0, i32 0
+    %3 = load i32, ptr %2
+    ret i32 %3
+}
+define %"Pair<u8, Pair<u8, u8>>" @"wrap<u8>" (i8) {
+entry:
+    %1 = alloca i8
+    %_0 = alloca %"Pair<u8, Pair<u8, u8>>"
+    store i8 %0, ptr %1
+    %2 = getelementptr inbounds %"Pair<u8, Pair<u8, u8>>", ptr %_0, i64 0, i32 0
+    %3 = load i8, ptr %1
+    store i8 %3, ptr %2
+    %4 = getelementptr inbounds %"Pair<u8, Pair<u8, u8>>", ptr %_0, i64 0, i32 1
+    %5 = getelementptr inbounds %"Pair<u8, u8>", ptr %4, i64 0, i32 0
+    %6 = load i8, ptr %1
+    store i8 %6, ptr %5
+    %7 = getelementptr inbounds %"Pair<u8, u8>", ptr %4, i64 0, i32 1
+    store i8 1, ptr %7
+    %8 = load %"Pair<u8, Pair<u8, u8>>", ptr %_0
+    ret %"Pair<u8, Pair<u8, u8>>" %8
+}
+define i32 @"count<i32>" (i32) {
+entry:
+    %1 = alloca i32
+    store i32 %0, ptr %1
+    %2 = load i32, ptr %1
+    %3 = icmp eq i32 %2, 0
+    br i1 %3, label %4, label %6
+    4:
+        %5 = load i32, ptr %1
+        ret i32 %5
+    %7 = load i32, ptr %1
+    %8 = sub i32 %7, 1
+    %9 = tail call i32 @"count<i32>" (i32 %8)
+    ret i32 %9
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i8
+    %_1 = alloca i8
+    %_2 = alloca i32
+    %_3 = alloca i64
+    %_4 = alloca %"Pair<i32, bool>"
+    %_5 = alloca %"Pair<u8, Pair<u8, u8>>"
+    %_6 = alloca %"geo::Point"
+    %_7 = alloca %"geo::Point"
+    %_8 = alloca %"geo::Point"
+    store i8 3, ptr %_0
+    %0 = load i8, ptr %_0
+    %1 = call i8 @"max<u8>" (i8 %0, i8 9)
+    store i8 %1, ptr %_1
+    %2 = call i32 @"max<i32>" (i32 4, i32 2)
+    store i32 %2, ptr %_2
+    %3 = call i64 @"max<i64>" (i64 1, i64 5)
+    store i64 %3, ptr %_3
+    %4 = getelementptr inbounds %"Pair<i32, bool>", ptr %_4, i64 0, i32 0
+    store i32 6, ptr %4
+    %5 = getelementptr inbounds %"Pair<i32, bool>", ptr %_4, i64 0, i32 1
+    store i1 1, ptr %5
+    %6 = load i8, ptr %_0
+    %7 = call %"Pair<u8, Pair<u8, u8>>" @"wrap<u8>" (i8 %6)
+    store %"Pair<u8, Pair<u8, u8>>" %7, ptr %_5
+    %8 = getelementptr inbounds %"geo::Point", ptr %_7, i64 0, i32 0
+    store i32 1, ptr %8
+    %9 = getelementptr inbounds %"geo::Point", ptr %_7, i64 0, i32 1
+    store i32 2, ptr %9
+    %10 = load %"geo::Point", ptr %_7
+    %11 = getelementptr inbounds %"geo::Point", ptr %_8, i64 0, i32 0
+    store i32 3, ptr %11
+    %12 = getelementptr inbounds %"geo::Point", ptr %_8, i64 0, i32 1
+    store i32 4, ptr %12
+    %13 = load %"geo::Point", ptr %_8
+    %14 = call %"geo::Point" @"geo::pick<geo::Point>" (i1 0, %"geo::Point" %10, %"geo::Point" %13)
+    store %"geo::Point" %14, ptr %_6
+    %15 = load i8, ptr %_1
+    %16 = zext i8 %15 to i32
+    %17 = load i32, ptr %_2
+    %18 = add i32 %16, %17
+    %19 = load i64, ptr %_3
+    %20 = trunc i64 %19 to i32
+    %21 = add i32 %18, %20
+    %22 = load %"Pair<i32, bool>", ptr %_4
+    %23 = call i32 @"first<i32, bool>" (%"Pair<i32, bool>" %22)
+    %24 = add i32 %21, %23
+    %25 = call i32 @"count<i32>" (i32 3)
+    %26 = add i32 %24, %25
+    ret i32 %26
+}
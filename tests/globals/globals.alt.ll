@@ -0,0 +1,99 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+@.const.0 = constant [5 x i8] c"hello"
+@"GREETING" = constant %.slice { ptr @.const.0, i64 5 }
+@"COUNTER" = global i32 10
+@"GRID" = global [8 x i32] [i32 0, i32 0, i32 0, i32 0, i32 0, i32 0, i32 0, i32 0]
+@"geo::ORIGIN" = global i64 -7
+define i32 @"geo::sides" () {
+entry:
+    %0 = add i32 3, 1
+    %1 = sub i32 %0, 1
+    ret i32 %1
+}
+define void @"bump" (i32*) {
+entry:
+    %1 = alloca i32*
+    store i32* %0, ptr %1
+    %2 = load i32*, ptr %1
+    %3 = load i32, ptr %2
+    %4 = add i32 %3, 1
+    %5 = load i32*, ptr %1
+    store i32 %4, ptr %5
+    ret void
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca [4 x i32]
+    %_1 = alloca i32*
+    %0 = getelementptr inbounds i32, ptr %_0, i64 0
+    store i32 1, ptr %0
+    %1 = getelementptr inbounds i32, ptr %_0, i64 1
+    store i32 2, ptr %1
+    %2 = getelementptr inbounds i32, ptr %_0, i64 2
+    store i32 3, ptr %2
+    %3 = getelementptr inbounds i32, ptr %_0, i64 3
+    store i32 4, ptr %3
+    %4 = load i32, ptr @"COUNTER"
+    %5 = add i32 %4, 5
+    store i32 %5, ptr @"COUNTER"
+    call void @"bump" (i32* @"COUNTER")
+    %6 = getelementptr inbounds [8 x i32], ptr @"GRID", i64 0, i64 3
+    store i32 9, ptr %6
+    %7 = load i64, ptr @"geo::ORIGIN"
+    %8 = mul i64 %7, 2
+    store i64 %8, ptr @"geo::ORIGIN"
+    %9 = xor i1 1, -1
+    br i1 %9, label %10, label %11
+    10:
+        ret i32 1
+    %12 = getelementptr inbounds [4 x i32], ptr %_0, i64 0, i64 3
+    %13 = load i32, ptr %12
+    %14 = icmp ne i32 %13, 4
+    br i1 %14, label %15, label %16
+    15:
+        ret i32 2
+    %17 = load %.slice, ptr @"GREETING"
+    %18 = extractvalue %.slice %17, 0
+    %19 = getelementptr inbounds [0 x i8], ptr %18, i64 0, i64 1
+    %20 = load i8, ptr %19
+    %21 = icmp ne i8 %20, 101
+    br i1 %21, label %22, label %23
+    22:
+        ret i32 3
+    %24 = fmul double 0x400C000000000000, 0x4000000000000000
+    %25 = fcmp une double %24, 0x401C000000000000
+    br i1 %25, label %26, label %27
+    26:
+        ret i32 4
+    %28 = icmp ne i8 15, 15
+    br i1 %28, label %29, label %30
+    29:
+        ret i32 5
+    %31 = load i64, ptr @"geo::ORIGIN"
+    %32 = icmp ne i64 %31, -14
+    br i1 %32, label %33, label %34
+    33:
+        ret i32 6
+    %35 = call i32 @"geo::sides" ()
+    %36 = icmp ne i32 %35, 3
+    br i1 %36, label %37, label %38
+    37:
+        ret i32 7
+    %39 = icmp ne i8 255, 255
+    br i1 %39, label %40, label %41
+    40:
+        ret i32 8
+    store i32* @"COUNTER", ptr %_1
+    %42 = load i32, ptr @"COUNTER"
+    %43 = getelementptr inbounds [8 x i32], ptr @"GRID", i64 0, i64 3
+    %44 = load i32, ptr %43
+    %45 = add i32 %42, %44
+    %46 = add i32 %45, 3
+    %47 = add i32 %46, 3
+    %48 = load i32*, ptr %_1
+    %49 = load i32, ptr %48
+    %50 = add i32 %47, %49
+    ret i32 %50
+}
@@ -0,0 +1,27 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+define i32 @"fib" (i32) {
+entry:
+    %1 = alloca i32
+    store i32 %0, ptr %1
+    %2 = load i32, ptr %1
+    %3 = icmp slt i32 %2, 2
+    br i1 %3, label %4, label %6
+    4:
+        %5 = load i32, ptr %1
+        ret i32 %5
+    %7 = load i32, ptr %1
+    %8 = sub i32 %7, 1
+    %9 = call i32 @"fib" (i32 %8)
+    %10 = load i32, ptr %1
+    %11 = sub i32 %10, 2
+    %12 = call i32 @"fib" (i32 %11)
+    %13 = add i32 %9, %12
+    ret i32 %13
+}
+define i32 @"main" () {
+entry:
+    %0 = tail call i32 @"fib" (i32 10)
+    ret i32 %0
+}
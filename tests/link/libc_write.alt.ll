@@ -0,0 +1,11 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i64 @write (i32, ptr, i64)
+@.const.0 = constant [7 x i8] c"linked\0A"
+define i32 @"main" () {
+entry:
+    %0 = extractvalue %.slice { ptr @.const.0, i64 7 }, 0
+    %1 = call i64 @"write" (i32 1, ptr %0, i64 7)
+    ret i32 0
+}
@@ -0,0 +1,38 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i64 @write (i32, ptr, i64)
+declare i32 @putchar (i32)
+@.const.0 = constant [16 x i8] c"tab\09\22q\22 \5C A\00\E2\98\BA\0A"
+@.const.1 = constant [9 x i8] c"one line\0A"
+define i32 @"main" () {
+entry:
+    %_0 = alloca i8
+    %0 = extractvalue %.slice { ptr @.const.0, i64 16 }, 0
+    %1 = call i64 @"write" (i32 1, ptr %0, i64 16)
+    %2 = extractvalue %.slice { ptr @.const.1, i64 9 }, 0
+    %3 = call i64 @"write" (i32 1, ptr %2, i64 9)
+    %4 = call i32 @"putchar" (i32 99)
+    %5 = zext i8 39 to i32
+    %6 = call i32 @"putchar" (i32 %5)
+    %7 = zext i8 126 to i32
+    %8 = call i32 @"putchar" (i32 %7)
+    %9 = call i32 @"putchar" (i32 10)
+    %10 = icmp ne i32 128512, 128512
+    br i1 %10, label %11, label %12
+    11:
+        ret i32 1
+    %13 = icmp ne i32 1099, 1099
+    br i1 %13, label %14, label %15
+    14:
+        ret i32 2
+    store i8 255, ptr %_0
+    %16 = sub i32 97, 65
+    %17 = load i8, ptr %_0
+    %18 = zext i8 %17 to i32
+    %19 = add i32 %16, %18
+    %20 = sub i32 %19, 255
+    %21 = zext i8 10 to i32
+    %22 = add i32 %20, %21
+    ret i32 %22
+}
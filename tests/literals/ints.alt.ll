@@ -0,0 +1,97 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+define i32 @"main" () {
+entry:
+    %_0 = alloca i8
+    %_1 = alloca i8
+    %_2 = alloca i64
+    %_3 = alloca i128
+    %_4 = alloca i128
+    %_5 = alloca i8
+    %_6 = alloca float
+    %_7 = alloca i8
+    %0 = icmp ne i32 255, 255
+    br i1 %0, label %1, label %2
+    1:
+        ret i32 1
+    %3 = icmp ne i32 10, 10
+    br i1 %3, label %4, label %5
+    4:
+        ret i32 2
+    %6 = icmp ne i32 511, 511
+    br i1 %6, label %7, label %8
+    7:
+        ret i32 3
+    %9 = icmp ne i32 1000000, 1000000
+    br i1 %9, label %10, label %11
+    10:
+        ret i32 4
+    store i8 10, ptr %_0
+    store i8 240, ptr %_1
+    %12 = load i8, ptr %_0
+    %13 = load i8, ptr %_1
+    %14 = or i8 %12, %13
+    %15 = icmp ne i8 %14, 250
+    br i1 %15, label %16, label %17
+    16:
+        ret i32 5
+    store i64 -5, ptr %_2
+    %18 = load i64, ptr %_2
+    %19 = mul i64 %18, 3000000000
+    %20 = icmp ne i64 %19, -15000000000
+    br i1 %20, label %21, label %22
+    21:
+        ret i32 6
+    store i128 340282366920938463463374607431768211455, ptr %_3
+    %23 = load i128, ptr %_3
+    %24 = icmp ne i128 %23, 340282366920938463463374607431768211455
+    br i1 %24, label %25, label %26
+    25:
+        ret i32 7
+    %27 = load i128, ptr %_3
+    %28 = lshr i128 %27, 100
+    %29 = trunc i128 %28 to i32
+    %30 = icmp ne i32 %29, 268435455
+    br i1 %30, label %31, label %32
+    31:
+        ret i32 8
+    store i128 -170141183460469231731687303715884105728, ptr %_4
+    %33 = load i128, ptr %_4
+    %34 = add i128 %33, 1
+    %35 = icmp ne i128 %34, -170141183460469231731687303715884105727
+    br i1 %35, label %36, label %37
+    36:
+        ret i32 9
+    store i8 -128, ptr %_5
+    %38 = fadd float 0x3FF8000000000000, 0x4000000000000000
+    store float %38, ptr %_6
+    store i8 16, ptr %_7
+    br label %match7.arm0
+    match7.arm0:
+    %39 = load i8, ptr %_7
+    %40 = sub i8 %39, 0
+    %41 = icmp ule i8 %40, 15
+    br i1 %41, label %match7.ok41, label %match7.arm1
+    match7.ok41:
+    ret i32 10
+    match7.arm1:
+    %42 = load i8, ptr %_7
+    %43 = icmp eq i8 %42, 16
+    br i1 %43, label %match7.ok43, label %match7.arm2
+    match7.ok43:
+    br label %match7.end
+    match7.arm2:
+    ret i32 11
+    match7.none:
+    unreachable
+    match7.end:
+    %44 = load i8, ptr %_5
+    %45 = sext i8 %44 to i32
+    %46 = add i32 %45, 128
+    %47 = load float, ptr %_6
+    %48 = fptosi float %47 to i32
+    %49 = add i32 %46, %48
+    %50 = add i32 %49, 40
+    ret i32 %50
+}
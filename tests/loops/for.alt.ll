@@ -0,0 +1,781 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i32 @putchar (i32)
+define void @"digit" (i8) {
+entry:
+    %1 = alloca i8
+    store i8 %0, ptr %1
+    %2 = load i8, ptr %1
+    %3 = zext i8 %2 to i32
+    %4 = add i32 48, %3
+    %5 = call i32 @"putchar" (i32 %4)
+    ret void
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i8
+    %_1 = alloca i8
+    %_2 = alloca i64
+    %_3 = alloca i64
+    %_4 = alloca i8
+    %_5 = alloca i8
+    %_6 = alloca i8
+    %_7 = alloca i8
+    %_8 = alloca i64
+    %_9 = alloca i1
+    %_10 = alloca i64
+    %_11 = alloca i8
+    %_12 = alloca i8
+    %_13 = alloca i8
+    %_14 = alloca i8
+    %_15 = alloca i64
+    %_16 = alloca i64
+    %_17 = alloca i8
+    %_18 = alloca i8
+    %_19 = alloca i8
+    %_20 = alloca i8
+    %_21 = alloca i64
+    %_22 = alloca i1
+    %_23 = alloca i64
+    %_24 = alloca i8
+    %_25 = alloca i8
+    %_26 = alloca i8
+    %_27 = alloca i64
+    %_28 = alloca i64
+    %_29 = alloca i8
+    %_30 = alloca i32
+    %_31 = alloca i8
+    %_32 = alloca i8
+    %_33 = alloca i64
+    %_34 = alloca i64
+    %_35 = alloca i8
+    %_36 = alloca i8
+    %_37 = alloca i8
+    %_38 = alloca i64
+    %_39 = alloca i64
+    %_40 = alloca i8
+    %_41 = alloca i8
+    %_42 = alloca i8
+    %_43 = alloca i8
+    %_44 = alloca i64
+    %_45 = alloca i1
+    %_46 = alloca i64
+    %_47 = alloca i8
+    %_48 = alloca i8
+    %_49 = alloca i8
+    %_50 = alloca i8
+    %_51 = alloca i64
+    %_52 = alloca i1
+    %_53 = alloca i64
+    %_54 = alloca i8
+    %_55 = alloca i8
+    %_56 = alloca i8
+    %_57 = alloca i8
+    %_58 = alloca i8
+    %_59 = alloca i64
+    %_60 = alloca i1
+    %_61 = alloca i64
+    %_62 = alloca i8
+    %_63 = alloca i8
+    %_64 = alloca i8
+    %_65 = alloca i8
+    %_66 = alloca i8
+    %_67 = alloca i64
+    %_68 = alloca i1
+    %_69 = alloca i64
+    %_70 = alloca i8
+    %_71 = alloca [5 x i32]
+    %_72 = alloca i32
+    %_73 = alloca [5 x i32]*
+    %_74 = alloca i64
+    %_75 = alloca i64
+    %_76 = alloca i32
+    %_77 = alloca [5 x i32]*
+    %_78 = alloca [5 x i32]*
+    %_79 = alloca i64
+    %_80 = alloca i64
+    %_81 = alloca i64
+    %_82 = alloca i32
+    %_83 = alloca i32
+    %_84 = alloca i32
+    %_85 = alloca i64
+    %_86 = alloca i64
+    %_87 = alloca i32
+    %_88 = alloca i32
+    store i8 0, ptr %_0
+    store i8 5, ptr %_1
+    store i64 0, ptr %_2
+    %0 = load i8, ptr %_0
+    %1 = load i8, ptr %_1
+    %2 = icmp ult i8 %0, %1
+    br i1 %2, label %3, label %8
+    3:
+        %4 = load i8, ptr %_1
+        %5 = load i8, ptr %_0
+        %6 = sub i8 %4, %5
+        %7 = zext i8 %6 to i64
+        store i64 %7, ptr %_2
+        br label %8
+    store i64 0, ptr %_3
+    br label %9
+    9:
+        %10 = load i64, ptr %_3
+        %11 = load i64, ptr %_2
+        %12 = icmp ne i64 %10, %11
+        %13 = xor i1 %12, -1
+        br i1 %13, label %14, label %15
+        14:
+            br label %end.9
+        %16 = load i8, ptr %_0
+        %17 = load i64, ptr %_3
+        %18 = trunc i64 %17 to i8
+        %19 = add i8 %16, %18
+        store i8 %19, ptr %_4
+        %20 = load i8, ptr %_4
+        call void @"digit" (i8 %20)
+        %21 = load i64, ptr %_3
+        %22 = add i64 %21, 1
+        store i64 %22, ptr %_3
+        br label %9
+    end.9:
+    %23 = call i32 @"putchar" (i32 32)
+    store i8 0, ptr %_5
+    store i8 9, ptr %_6
+    store i8 3, ptr %_7
+    store i64 0, ptr %_8
+    %24 = load i8, ptr %_5
+    %25 = load i8, ptr %_6
+    %26 = icmp ule i8 %24, %25
+    store i1 %26, ptr %_9
+    br i1 %26, label %27, label %end.27
+    %28 = load i8, ptr %_7
+    %29 = icmp ugt i8 %28, 0
+    store i1 %29, ptr %_9
+    br label %end.27
+    end.27:
+    %30 = load i1, ptr %_9
+    br i1 %30, label %31, label %40
+    31:
+        %32 = load i8, ptr %_6
+        %33 = load i8, ptr %_5
+        %34 = sub i8 %32, %33
+        %35 = zext i8 %34 to i64
+        %36 = load i8, ptr %_7
+        %37 = zext i8 %36 to i64
+        %38 = udiv i64 %35, %37
+        %39 = add i64 %38, 1
+        store i64 %39, ptr %_8
+        br label %40
+    store i64 0, ptr %_10
+    br label %41
+    41:
+        %42 = load i64, ptr %_10
+        %43 = load i64, ptr %_8
+        %44 = icmp ne i64 %42, %43
+        %45 = xor i1 %44, -1
+        br i1 %45, label %46, label %47
+        46:
+            br label %end.41
+        %48 = load i8, ptr %_5
+        %49 = load i64, ptr %_10
+        %50 = trunc i64 %49 to i8
+        %51 = load i8, ptr %_7
+        %52 = mul i8 %50, %51
+        %53 = add i8 %48, %52
+        store i8 %53, ptr %_11
+        %54 = load i8, ptr %_11
+        call void @"digit" (i8 %54)
+        %55 = load i64, ptr %_10
+        %56 = add i64 %55, 1
+        store i64 %56, ptr %_10
+        br label %41
+    end.41:
+    %57 = call i32 @"putchar" (i32 32)
+    store i8 4, ptr %_12
+    store i8 0, ptr %_13
+    %58 = load i8, ptr %_12
+    store i8 %58, ptr %_14
+    store i64 0, ptr %_15
+    %59 = load i8, ptr %_13
+    %60 = load i8, ptr %_14
+    %61 = icmp ult i8 %59, %60
+    br i1 %61, label %62, label %67
+    62:
+        %63 = load i8, ptr %_14
+        %64 = load i8, ptr %_13
+        %65 = sub i8 %63, %64
+        %66 = zext i8 %65 to i64
+        store i64 %66, ptr %_15
+        br label %67
+    store i64 0, ptr %_16
+    br label %68
+    68:
+        %69 = load i64, ptr %_16
+        %70 = load i64, ptr %_15
+        %71 = icmp ne i64 %69, %70
+        %72 = xor i1 %71, -1
+        br i1 %72, label %73, label %74
+        73:
+            br label %end.68
+        %75 = load i8, ptr %_13
+        %76 = load i64, ptr %_15
+        %77 = sub i64 %76, 1
+        %78 = load i64, ptr %_16
+        %79 = sub i64 %77, %78
+        %80 = trunc i64 %79 to i8
+        %81 = add i8 %75, %80
+        store i8 %81, ptr %_17
+        %82 = load i8, ptr %_17
+        call void @"digit" (i8 %82)
+        %83 = load i64, ptr %_16
+        %84 = add i64 %83, 1
+        store i64 %84, ptr %_16
+        br label %68
+    end.68:
+    %85 = call i32 @"putchar" (i32 32)
+    store i8 0, ptr %_18
+    store i8 10, ptr %_19
+    store i8 4, ptr %_20
+    store i64 0, ptr %_21
+    %86 = load i8, ptr %_18
+    %87 = load i8, ptr %_19
+    %88 = icmp ult i8 %86, %87
+    store i1 %88, ptr %_22
+    br i1 %88, label %89, label %end.89
+    %90 = load i8, ptr %_20
+    %91 = icmp ugt i8 %90, 0
+    store i1 %91, ptr %_22
+    br label %end.89
+    end.89:
+    %92 = load i1, ptr %_22
+    br i1 %92, label %93, label %103
+    93:
+        %94 = load i8, ptr %_19
+        %95 = load i8, ptr %_18
+        %96 = sub i8 %94, %95
+        %97 = zext i8 %96 to i64
+        %98 = sub i64 %97, 1
+        %99 = load i8, ptr %_20
+        %100 = zext i8 %99 to i64
+        %101 = udiv i64 %98, %100
+        %102 = add i64 %101, 1
+        store i64 %102, ptr %_21
+        br label %103
+    store i64 0, ptr %_23
+    br label %104
+    104:
+        %105 = load i64, ptr %_23
+        %106 = load i64, ptr %_21
+        %107 = icmp ne i64 %105, %106
+        %108 = xor i1 %107, -1
+        br i1 %108, label %109, label %110
+        109:
+            br label %end.104
+        %111 = load i8, ptr %_18
+        %112 = load i64, ptr %_21
+        %113 = sub i64 %112, 1
+        %114 = load i64, ptr %_23
+        %115 = sub i64 %113, %114
+        %116 = trunc i64 %115 to i8
+        %117 = load i8, ptr %_20
+        %118 = mul i8 %116, %117
+        %119 = add i8 %111, %118
+        store i8 %119, ptr %_24
+        %120 = load i8, ptr %_24
+        call void @"digit" (i8 %120)
+        %121 = load i64, ptr %_23
+        %122 = add i64 %121, 1
+        store i64 %122, ptr %_23
+        br label %104
+    end.104:
+    %123 = call i32 @"putchar" (i32 32)
+    store i8 5, ptr %_25
+    store i8 2, ptr %_26
+    store i64 0, ptr %_27
+    %124 = load i8, ptr %_25
+    %125 = load i8, ptr %_26
+    %126 = icmp ult i8 %124, %125
+    br i1 %126, label %127, label %132
+    127:
+        %128 = load i8, ptr %_26
+        %129 = load i8, ptr %_25
+        %130 = sub i8 %128, %129
+        %131 = zext i8 %130 to i64
+        store i64 %131, ptr %_27
+        br label %132
+    store i64 0, ptr %_28
+    br label %133
+    133:
+        %134 = load i64, ptr %_28
+        %135 = load i64, ptr %_27
+        %136 = icmp ne i64 %134, %135
+        %137 = xor i1 %136, -1
+        br i1 %137, label %138, label %139
+        138:
+            br label %end.133
+        %140 = load i8, ptr %_25
+        %141 = load i64, ptr %_28
+        %142 = trunc i64 %141 to i8
+        %143 = add i8 %140, %142
+        store i8 %143, ptr %_29
+        %144 = load i8, ptr %_29
+        call void @"digit" (i8 %144)
+        %145 = load i64, ptr %_28
+        %146 = add i64 %145, 1
+        store i64 %146, ptr %_28
+        br label %133
+    end.133:
+    store i32 0, ptr %_30
+    store i8 0, ptr %_31
+    store i8 255, ptr %_32
+    store i64 0, ptr %_33
+    %147 = load i8, ptr %_31
+    %148 = load i8, ptr %_32
+    %149 = icmp ule i8 %147, %148
+    br i1 %149, label %150, label %156
+    150:
+        %151 = load i8, ptr %_32
+        %152 = load i8, ptr %_31
+        %153 = sub i8 %151, %152
+        %154 = zext i8 %153 to i64
+        %155 = add i64 %154, 1
+        store i64 %155, ptr %_33
+        br label %156
+    store i64 0, ptr %_34
+    br label %157
+    157:
+        %158 = load i64, ptr %_34
+        %159 = load i64, ptr %_33
+        %160 = icmp ne i64 %158, %159
+        %161 = xor i1 %160, -1
+        br i1 %161, label %162, label %163
+        162:
+            br label %end.157
+        %164 = load i8, ptr %_31
+        %165 = load i64, ptr %_34
+        %166 = trunc i64 %165 to i8
+        %167 = add i8 %164, %166
+        store i8 %167, ptr %_35
+        %168 = load i32, ptr %_30
+        %169 = add i32 %168, 1
+        store i32 %169, ptr %_30
+        %170 = load i64, ptr %_34
+        %171 = add i64 %170, 1
+        store i64 %171, ptr %_34
+        br label %157
+    end.157:
+    store i8 -128, ptr %_36
+    store i8 127, ptr %_37
+    store i64 0, ptr %_38
+    %172 = load i8, ptr %_36
+    %173 = load i8, ptr %_37
+    %174 = icmp sle i8 %172, %173
+    br i1 %174, label %175, label %181
+    175:
+        %176 = load i8, ptr %_37
+        %177 = load i8, ptr %_36
+        %178 = sub i8 %176, %177
+        %179 = zext i8 %178 to i64
+        %180 = add i64 %179, 1
+        store i64 %180, ptr %_38
+        br label %181
+    store i64 0, ptr %_39
+    br label %182
+    182:
+        %183 = load i64, ptr %_39
+        %184 = load i64, ptr %_38
+        %185 = icmp ne i64 %183, %184
+        %186 = xor i1 %185, -1
+        br i1 %186, label %187, label %188
+        187:
+            br label %end.182
+        %189 = load i8, ptr %_36
+        %190 = load i64, ptr %_39
+        %191 = trunc i64 %190 to i8
+        %192 = add i8 %189, %191
+        store i8 %192, ptr %_40
+        %193 = load i32, ptr %_30
+        %194 = add i32 %193, 1
+        store i32 %194, ptr %_30
+        %195 = load i64, ptr %_39
+        %196 = add i64 %195, 1
+        store i64 %196, ptr %_39
+        br label %182
+    end.182:
+    store i8 0, ptr %_41
+    store i8 200, ptr %_42
+    store i8 100, ptr %_43
+    store i64 0, ptr %_44
+    %197 = load i8, ptr %_41
+    %198 = load i8, ptr %_42
+    %199 = icmp ult i8 %197, %198
+    store i1 %199, ptr %_45
+    br i1 %199, label %200, label %end.200
+    %201 = load i8, ptr %_43
+    %202 = icmp ugt i8 %201, 0
+    store i1 %202, ptr %_45
+    br label %end.200
+    end.200:
+    %203 = load i1, ptr %_45
+    br i1 %203, label %204, label %214
+    204:
+        %205 = load i8, ptr %_42
+        %206 = load i8, ptr %_41
+        %207 = sub i8 %205, %206
+        %208 = zext i8 %207 to i64
+        %209 = sub i64 %208, 1
+        %210 = load i8, ptr %_43
+        %211 = zext i8 %210 to i64
+        %212 = udiv i64 %209, %211
+        %213 = add i64 %212, 1
+        store i64 %213, ptr %_44
+        br label %214
+    store i64 0, ptr %_46
+    br label %215
+    215:
+        %216 = load i64, ptr %_46
+        %217 = load i64, ptr %_44
+        %218 = icmp ne i64 %216, %217
+        %219 = xor i1 %218, -1
+        br i1 %219, label %220, label %221
+        220:
+            br label %end.215
+        %222 = load i8, ptr %_41
+        %223 = load i64, ptr %_46
+        %224 = trunc i64 %223 to i8
+        %225 = load i8, ptr %_43
+        %226 = mul i8 %224, %225
+        %227 = add i8 %222, %226
+        store i8 %227, ptr %_47
+        %228 = load i32, ptr %_30
+        %229 = add i32 %228, 1
+        store i32 %229, ptr %_30
+        %230 = load i64, ptr %_46
+        %231 = add i64 %230, 1
+        store i64 %231, ptr %_46
+        br label %215
+    end.215:
+    store i8 -100, ptr %_48
+    store i8 100, ptr %_49
+    store i8 50, ptr %_50
+    store i64 0, ptr %_51
+    %232 = load i8, ptr %_48
+    %233 = load i8, ptr %_49
+    %234 = icmp slt i8 %232, %233
+    store i1 %234, ptr %_52
+    br i1 %234, label %235, label %end.235
+    %236 = load i8, ptr %_50
+    %237 = icmp sgt i8 %236, 0
+    store i1 %237, ptr %_52
+    br label %end.235
+    end.235:
+    %238 = load i1, ptr %_52
+    br i1 %238, label %239, label %249
+    239:
+        %240 = load i8, ptr %_49
+        %241 = load i8, ptr %_48
+        %242 = sub i8 %240, %241
+        %243 = zext i8 %242 to i64
+        %244 = sub i64 %243, 1
+        %245 = load i8, ptr %_50
+        %246 = zext i8 %245 to i64
+        %247 = udiv i64 %244, %246
+        %248 = add i64 %247, 1
+        store i64 %248, ptr %_51
+        br label %249
+    store i64 0, ptr %_53
+    br label %250
+    250:
+        %251 = load i64, ptr %_53
+        %252 = load i64, ptr %_51
+        %253 = icmp ne i64 %251, %252
+        %254 = xor i1 %253, -1
+        br i1 %254, label %255, label %256
+        255:
+            br label %end.250
+        %257 = load i8, ptr %_48
+        %258 = load i64, ptr %_53
+        %259 = trunc i64 %258 to i8
+        %260 = load i8, ptr %_50
+        %261 = mul i8 %259, %260
+        %262 = add i8 %257, %261
+        store i8 %262, ptr %_54
+        %263 = load i32, ptr %_30
+        %264 = add i32 %263, 1
+        store i32 %264, ptr %_30
+        %265 = load i64, ptr %_53
+        %266 = add i64 %265, 1
+        store i64 %266, ptr %_53
+        br label %250
+    end.250:
+    store i8 0, ptr %_55
+    store i8 0, ptr %_56
+    store i8 5, ptr %_57
+    %267 = load i8, ptr %_55
+    store i8 %267, ptr %_58
+    store i64 0, ptr %_59
+    %268 = load i8, ptr %_56
+    %269 = load i8, ptr %_57
+    %270 = icmp ult i8 %268, %269
+    store i1 %270, ptr %_60
+    br i1 %270, label %271, label %end.271
+    %272 = load i8, ptr %_58
+    %273 = icmp ugt i8 %272, 0
+    store i1 %273, ptr %_60
+    br label %end.271
+    end.271:
+    %274 = load i1, ptr %_60
+    br i1 %274, label %275, label %285
+    275:
+        %276 = load i8, ptr %_57
+        %277 = load i8, ptr %_56
+        %278 = sub i8 %276, %277
+        %279 = zext i8 %278 to i64
+        %280 = sub i64 %279, 1
+        %281 = load i8, ptr %_58
+        %282 = zext i8 %281 to i64
+        %283 = udiv i64 %280, %282
+        %284 = add i64 %283, 1
+        store i64 %284, ptr %_59
+        br label %285
+    store i64 0, ptr %_61
+    br label %286
+    286:
+        %287 = load i64, ptr %_61
+        %288 = load i64, ptr %_59
+        %289 = icmp ne i64 %287, %288
+        %290 = xor i1 %289, -1
+        br i1 %290, label %291, label %292
+        291:
+            br label %end.286
+        %293 = load i8, ptr %_56
+        %294 = load i64, ptr %_61
+        %295 = trunc i64 %294 to i8
+        %296 = load i8, ptr %_58
+        %297 = mul i8 %295, %296
+        %298 = add i8 %293, %297
+        store i8 %298, ptr %_62
+        %299 = load i32, ptr %_30
+        %300 = add i32 %299, 1
+        store i32 %300, ptr %_30
+        %301 = load i64, ptr %_61
+        %302 = add i64 %301, 1
+        store i64 %302, ptr %_61
+        br label %286
+    end.286:
+    store i8 -1, ptr %_63
+    store i8 0, ptr %_64
+    store i8 5, ptr %_65
+    %303 = load i8, ptr %_63
+    store i8 %303, ptr %_66
+    store i64 0, ptr %_67
+    %304 = load i8, ptr %_64
+    %305 = load i8, ptr %_65
+    %306 = icmp slt i8 %304, %305
+    store i1 %306, ptr %_68
+    br i1 %306, label %307, label %end.307
+    %308 = load i8, ptr %_66
+    %309 = icmp sgt i8 %308, 0
+    store i1 %309, ptr %_68
+    br label %end.307
+    end.307:
+    %310 = load i1, ptr %_68
+    br i1 %310, label %311, label %321
+    311:
+        %312 = load i8, ptr %_65
+        %313 = load i8, ptr %_64
+        %314 = sub i8 %312, %313
+        %315 = zext i8 %314 to i64
+        %316 = sub i64 %315, 1
+        %317 = load i8, ptr %_66
+        %318 = zext i8 %317 to i64
+        %319 = udiv i64 %316, %318
+        %320 = add i64 %319, 1
+        store i64 %320, ptr %_67
+        br label %321
+    store i64 0, ptr %_69
+    br label %322
+    322:
+        %323 = load i64, ptr %_69
+        %324 = load i64, ptr %_67
+        %325 = icmp ne i64 %323, %324
+        %326 = xor i1 %325, -1
+        br i1 %326, label %327, label %328
+        327:
+            br label %end.322
+        %329 = load i8, ptr %_64
+        %330 = load i64, ptr %_69
+        %331 = trunc i64 %330 to i8
+        %332 = load i8, ptr %_66
+        %333 = mul i8 %331, %332
+        %334 = add i8 %329, %333
+        store i8 %334, ptr %_70
+        %335 = load i32, ptr %_30
+        %336 = add i32 %335, 1
+        store i32 %336, ptr %_30
+        %337 = load i64, ptr %_69
+        %338 = add i64 %337, 1
+        store i64 %338, ptr %_69
+        br label %322
+    end.322:
+    %339 = getelementptr inbounds i32, ptr %_71, i64 0
+    store i32 3, ptr %339
+    %340 = getelementptr inbounds i32, ptr %_71, i64 1
+    store i32 1, ptr %340
+    %341 = getelementptr inbounds i32, ptr %_71, i64 2
+    store i32 4, ptr %341
+    %342 = getelementptr inbounds i32, ptr %_71, i64 3
+    store i32 1, ptr %342
+    %343 = getelementptr inbounds i32, ptr %_71, i64 4
+    store i32 5, ptr %343
+    store i32 0, ptr %_72
+    store [5 x i32]* %_71, ptr %_73
+    store i64 5, ptr %_74
+    store i64 0, ptr %_75
+    br label %344
+    344:
+        %345 = load i64, ptr %_75
+        %346 = load i64, ptr %_74
+        %347 = icmp ne i64 %345, %346
+        %348 = xor i1 %347, -1
+        br i1 %348, label %349, label %350
+        349:
+            br label %end.344
+        %351 = load [5 x i32]*, ptr %_73
+        %352 = load i64, ptr %_75
+        %353 = getelementptr inbounds [5 x i32], ptr %351, i64 0, i64 %352
+        %354 = load i32, ptr %353
+        store i32 %354, ptr %_76
+        %355 = load i32, ptr %_76
+        %356 = icmp eq i32 %355, 1
+        br i1 %356, label %357, label %360
+        357:
+            %358 = load i64, ptr %_75
+            %359 = add i64 %358, 1
+            store i64 %359, ptr %_75
+            br label %344
+        %361 = load i32, ptr %_72
+        %362 = load i32, ptr %_76
+        %363 = add i32 %361, %362
+        store i32 %363, ptr %_72
+        %364 = load i64, ptr %_75
+        %365 = add i64 %364, 1
+        store i64 %365, ptr %_75
+        br label %344
+    end.344:
+    store [5 x i32]* %_71, ptr %_77
+    %366 = load [5 x i32]*, ptr %_77
+    store [5 x i32]* %366, ptr %_78
+    store i64 5, ptr %_79
+    store i64 0, ptr %_80
+    br label %367
+    367:
+        %368 = load i64, ptr %_80
+        %369 = load i64, ptr %_79
+        %370 = icmp ne i64 %368, %369
+        %371 = xor i1 %370, -1
+        br i1 %371, label %372, label %373
+        372:
+            br label %end.367
+        %374 = load i64, ptr %_80
+        store i64 %374, ptr %_81
+        %375 = load [5 x i32]*, ptr %_78
+        %376 = load i64, ptr %_80
+        %377 = getelementptr inbounds [5 x i32], ptr %375, i64 0, i64 %376
+        %378 = load i32, ptr %377
+        store i32 %378, ptr %_82
+        store i32 0, ptr %_83
+        store i32 3, ptr %_84
+        store i64 0, ptr %_85
+        %379 = load i32, ptr %_83
+        %380 = load i32, ptr %_84
+        %381 = icmp slt i32 %379, %380
+        br i1 %381, label %382, label %387
+        382:
+            %383 = load i32, ptr %_84
+            %384 = load i32, ptr %_83
+            %385 = sub i32 %383, %384
+            %386 = zext i32 %385 to i64
+            store i64 %386, ptr %_85
+            br label %387
+        store i64 0, ptr %_86
+        br label %388
+        388:
+            %389 = load i64, ptr %_86
+            %390 = load i64, ptr %_85
+            %391 = icmp ne i64 %389, %390
+            %392 = xor i1 %391, -1
+            br i1 %392, label %393, label %394
+            393:
+                br label %end.388
+            %395 = load i32, ptr %_83
+            %396 = load i64, ptr %_86
+            %397 = trunc i64 %396 to i32
+            %398 = add i32 %395, %397
+            store i32 %398, ptr %_87
+            %399 = load i32, ptr %_87
+            %400 = icmp eq i32 %399, 1
+            br i1 %400, label %401, label %406
+            401:
+                %402 = load i64, ptr %_86
+                %403 = add i64 %402, 1
+                store i64 %403, ptr %_86
+                %404 = load i64, ptr %_80
+                %405 = add i64 %404, 1
+                store i64 %405, ptr %_80
+                br label %367
+            %407 = load i32, ptr %_82
+            %408 = icmp eq i32 %407, 5
+            br i1 %408, label %409, label %414
+            409:
+                %410 = load i64, ptr %_86
+                %411 = add i64 %410, 1
+                store i64 %411, ptr %_86
+                %412 = load i64, ptr %_80
+                %413 = add i64 %412, 1
+                store i64 %413, ptr %_80
+                br label %end.367
+            %415 = load i32, ptr %_72
+            %416 = load i64, ptr %_81
+            %417 = trunc i64 %416 to i32
+            %418 = add i32 %415, %417
+            store i32 %418, ptr %_72
+            %419 = load i64, ptr %_86
+            %420 = add i64 %419, 1
+            store i64 %420, ptr %_86
+            br label %388
+        end.388:
+        %421 = load i64, ptr %_80
+        %422 = add i64 %421, 1
+        store i64 %422, ptr %_80
+        br label %367
+    end.367:
+    store i32 3, ptr %_88
+    br label %423
+    423:
+        %424 = load i32, ptr %_88
+        %425 = icmp sgt i32 %424, 0
+        %426 = xor i1 %425, -1
+        br i1 %426, label %427, label %428
+        427:
+            br label %end.423
+        %429 = load i32, ptr %_88
+        %430 = sub i32 %429, 1
+        store i32 %430, ptr %_88
+        %431 = load i32, ptr %_88
+        %432 = icmp eq i32 %431, 1
+        br i1 %432, label %433, label %434
+        433:
+            br label %423
+        %435 = load i32, ptr %_72
+        %436 = add i32 %435, 100
+        store i32 %436, ptr %_72
+        br label %423
+    end.423:
+    %437 = call i32 @"putchar" (i32 10)
+    %438 = load i32, ptr %_72
+    %439 = load i32, ptr %_30
+    %440 = add i32 %438, %439
+    ret i32 %440
+}
@@ -0,0 +1,304 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"Shape" = type { i8, [2 x i32] }
+%"Point" = type { i32, i32 }
+define i32 @"area" (%"Shape") {
+entry:
+    %1 = alloca %"Shape"
+    %_0 = alloca %"Shape"
+    %_1 = alloca i32
+    %_2 = alloca i32
+    %_3 = alloca i32
+    store %"Shape" %0, ptr %1
+    %2 = load %"Shape", ptr %1
+    store %"Shape" %2, ptr %_0
+    %3 = getelementptr inbounds %"Shape", ptr %_0, i64 0, i32 0
+    %4 = load i8, ptr %3
+    switch i8 %4, label %match0.none [
+        i8 0, label %match0.arm0
+        i8 1, label %match0.arm1
+        i8 2, label %match0.arm2
+    ]
+    match0.arm0:
+    ret i32 0
+    match0.arm1:
+    %5 = getelementptr inbounds %"Shape", ptr %_0, i64 0, i32 1
+    %6 = getelementptr inbounds {i32}, ptr %5, i64 0, i32 0
+    %7 = load i32, ptr %6
+    store i32 %7, ptr %_1
+    %8 = load i32, ptr %_1
+    %9 = mul i32 3, %8
+    %10 = load i32, ptr %_1
+    %11 = mul i32 %9, %10
+    ret i32 %11
+    match0.arm2:
+    %12 = getelementptr inbounds %"Shape", ptr %_0, i64 0, i32 1
+    %13 = getelementptr inbounds {i32, i32}, ptr %12, i64 0, i32 0
+    %14 = load i32, ptr %13
+    store i32 %14, ptr %_2
+    %15 = getelementptr inbounds {i32, i32}, ptr %12, i64 0, i32 1
+    %16 = load i32, ptr %15
+    store i32 %16, ptr %_3
+    %17 = load i32, ptr %_2
+    %18 = load i32, ptr %_3
+    %19 = mul i32 %17, %18
+    ret i32 %19
+    match0.none:
+    unreachable
+    match0.end:
+    unreachable
+}
+define i32 @"classify" (i32) {
+entry:
+    %1 = alloca i32
+    %_0 = alloca i32
+    %_1 = alloca i32
+    store i32 %0, ptr %1
+    %2 = load i32, ptr %1
+    store i32 %2, ptr %_0
+    br label %match0.arm0
+    match0.arm0:
+    %3 = load i32, ptr %_0
+    %4 = icmp eq i32 %3, 0
+    br i1 %4, label %match0.ok4, label %match0.arm1
+    match0.ok4:
+    ret i32 100
+    match0.arm1:
+    %5 = load i32, ptr %_0
+    %6 = sub i32 %5, 1
+    %7 = icmp ule i32 %6, 8
+    br i1 %7, label %match0.ok7, label %match0.arm2
+    match0.ok7:
+    ret i32 1
+    match0.arm2:
+    %8 = load i32, ptr %_0
+    %9 = sub i32 %8, -5
+    %10 = icmp ule i32 %9, 4
+    br i1 %10, label %match0.ok10, label %match0.arm3
+    match0.ok10:
+    ret i32 2
+    match0.arm3:
+    %11 = load i32, ptr %_0
+    store i32 %11, ptr %_1
+    %12 = load i32, ptr %_1
+    %13 = icmp sgt i32 %12, 50
+    br i1 %13, label %match0.body3, label %match0.arm4
+    match0.body3:
+    ret i32 3
+    match0.arm4:
+    ret i32 4
+    match0.none:
+    unreachable
+    match0.end:
+    unreachable
+}
+define i32 @"quadrant" (%"Point") {
+entry:
+    %1 = alloca %"Point"
+    %_0 = alloca %"Point"
+    %_1 = alloca i32
+    %_2 = alloca i32
+    %_3 = alloca i1
+    store %"Point" %0, ptr %1
+    %2 = load %"Point", ptr %1
+    store %"Point" %2, ptr %_0
+    br label %match0.arm0
+    match0.arm0:
+    %3 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = icmp eq i32 %4, 0
+    br i1 %5, label %match0.ok5, label %match0.arm1
+    match0.ok5:
+    %6 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 1
+    %7 = load i32, ptr %6
+    %8 = icmp eq i32 %7, 0
+    br i1 %8, label %match0.ok8, label %match0.arm1
+    match0.ok8:
+    ret i32 0
+    match0.arm1:
+    %9 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 0
+    %10 = load i32, ptr %9
+    %11 = icmp eq i32 %10, 0
+    br i1 %11, label %match0.ok11, label %match0.arm2
+    match0.ok11:
+    ret i32 5
+    match0.arm2:
+    %12 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 1
+    %13 = load i32, ptr %12
+    %14 = icmp eq i32 %13, 0
+    br i1 %14, label %match0.ok14, label %match0.arm3
+    match0.ok14:
+    ret i32 6
+    match0.arm3:
+    %15 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 0
+    %16 = load i32, ptr %15
+    store i32 %16, ptr %_1
+    %17 = getelementptr inbounds %"Point", ptr %_0, i64 0, i32 1
+    %18 = load i32, ptr %17
+    store i32 %18, ptr %_2
+    %19 = load i32, ptr %_1
+    %20 = icmp sgt i32 %19, 0
+    store i1 %20, ptr %_3
+    br i1 %20, label %21, label %end.21
+    %22 = load i32, ptr %_2
+    %23 = icmp sgt i32 %22, 0
+    store i1 %23, ptr %_3
+    br label %end.21
+    end.21:
+    %24 = load i1, ptr %_3
+    br i1 %24, label %match0.body3, label %match0.arm4
+    match0.body3:
+    ret i32 1
+    match0.arm4:
+    ret i32 2
+    match0.none:
+    unreachable
+    match0.end:
+    unreachable
+}
+define i32 @"flags" (i1, i1) {
+entry:
+    %2 = alloca i1
+    %3 = alloca i1
+    %_0 = alloca i32
+    %_1 = alloca {i1, i1}
+    store i1 %0, ptr %2
+    store i1 %1, ptr %3
+    store i32 0, ptr %_0
+    %4 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 0
+    %5 = load i1, ptr %2
+    store i1 %5, ptr %4
+    %6 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 1
+    %7 = load i1, ptr %3
+    store i1 %7, ptr %6
+    br label %match1.arm0
+    match1.arm0:
+    %8 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 0
+    %9 = load i1, ptr %8
+    %10 = icmp eq i1 %9, 1
+    br i1 %10, label %match1.ok10, label %match1.arm1
+    match1.ok10:
+    %11 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 1
+    %12 = load i1, ptr %11
+    %13 = icmp eq i1 %12, 1
+    br i1 %13, label %match1.ok13, label %match1.arm1
+    match1.ok13:
+    store i32 3, ptr %_0
+    br label %match1.end
+    match1.arm1:
+    %14 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 0
+    %15 = load i1, ptr %14
+    %16 = icmp eq i1 %15, 1
+    br i1 %16, label %match1.ok16, label %match1.arm2
+    match1.ok16:
+    %17 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 1
+    %18 = load i1, ptr %17
+    %19 = icmp eq i1 %18, 0
+    br i1 %19, label %match1.ok19, label %match1.arm2
+    match1.ok19:
+    store i32 2, ptr %_0
+    br label %match1.end
+    match1.arm2:
+    %20 = getelementptr inbounds {i1, i1}, ptr %_1, i64 0, i32 0
+    %21 = load i1, ptr %20
+    %22 = icmp eq i1 %21, 0
+    br i1 %22, label %match1.ok22, label %match1.none
+    match1.ok22:
+    store i32 1, ptr %_0
+    br label %match1.end
+    match1.none:
+    unreachable
+    match1.end:
+    %23 = load i32, ptr %_0
+    ret i32 %23
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i32
+    %_1 = alloca %"Shape"
+    %_2 = alloca i32
+    %_3 = alloca %"Shape"
+    %_4 = alloca i32
+    %_5 = alloca %"Shape"
+    %_6 = alloca i32
+    %_7 = alloca i32
+    %_8 = alloca %"Point"
+    %_9 = alloca %"Point"
+    %_10 = alloca i32
+    %_11 = alloca i32
+    %0 = getelementptr inbounds %"Shape", ptr %_1, i64 0, i32 0
+    store i8 2, ptr %0
+    %1 = getelementptr inbounds %"Shape", ptr %_1, i64 0, i32 1
+    %2 = getelementptr inbounds {i32, i32}, ptr %1, i64 0, i32 0
+    store i32 2, ptr %2
+    %3 = getelementptr inbounds {i32, i32}, ptr %1, i64 0, i32 1
+    store i32 3, ptr %3
+    %4 = load %"Shape", ptr %_1
+    %5 = call i32 @"area" (%"Shape" %4)
+    store i32 %5, ptr %_0
+    %6 = getelementptr inbounds %"Shape", ptr %_3, i64 0, i32 0
+    store i8 1, ptr %6
+    %7 = getelementptr inbounds %"Shape", ptr %_3, i64 0, i32 1
+    %8 = getelementptr inbounds {i32}, ptr %7, i64 0, i32 0
+    store i32 1, ptr %8
+    %9 = load %"Shape", ptr %_3
+    %10 = call i32 @"area" (%"Shape" %9)
+    store i32 %10, ptr %_2
+    %11 = getelementptr inbounds %"Shape", ptr %_5, i64 0, i32 0
+    store i8 0, ptr %11
+    %12 = load %"Shape", ptr %_5
+    %13 = call i32 @"area" (%"Shape" %12)
+    store i32 %13, ptr %_4
+    %14 = call i32 @"classify" (i32 5)
+    %15 = call i32 @"classify" (i32 -3)
+    %16 = add i32 %14, %15
+    %17 = call i32 @"classify" (i32 60)
+    %18 = add i32 %16, %17
+    %19 = call i32 @"classify" (i32 20)
+    %20 = add i32 %18, %19
+    %21 = call i32 @"classify" (i32 0)
+    %22 = add i32 %20, %21
+    store i32 %22, ptr %_6
+    %23 = getelementptr inbounds %"Point", ptr %_8, i64 0, i32 0
+    store i32 0, ptr %23
+    %24 = getelementptr inbounds %"Point", ptr %_8, i64 0, i32 1
+    store i32 3, ptr %24
+    %25 = load %"Point", ptr %_8
+    %26 = call i32 @"quadrant" (%"Point" %25)
+    %27 = getelementptr inbounds %"Point", ptr %_9, i64 0, i32 0
+    store i32 2, ptr %27
+    %28 = getelementptr inbounds %"Point", ptr %_9, i64 0, i32 1
+    store i32 2, ptr %28
+    %29 = load %"Point", ptr %_9
+    %30 = call i32 @"quadrant" (%"Point" %29)
+    %31 = add i32 %26, %30
+    store i32 %31, ptr %_7
+    %32 = call i32 @"flags" (i1 1, i1 0)
+    %33 = call i32 @"flags" (i1 0, i1 1)
+    %34 = add i32 %32, %33
+    store i32 %34, ptr %_10
+    %35 = load i32, ptr %_4
+    store i32 %35, ptr %_11
+    %36 = load i32, ptr %_11
+    switch i32 %36, label %match11.arm1 [
+        i32 0, label %match11.arm0
+    ]
+    match11.arm0:
+    br label %match11.end
+    match11.arm1:
+    ret i32 1
+    match11.none:
+    unreachable
+    match11.end:
+    %37 = load i32, ptr %_0
+    %38 = load i32, ptr %_2
+    %39 = add i32 %37, %38
+    %40 = load i32, ptr %_6
+    %41 = add i32 %39, %40
+    %42 = load i32, ptr %_7
+    %43 = add i32 %41, %42
+    %44 = load i32, ptr %_10
+    %45 = add i32 %43, %44
+    ret i32 %45
+}
@@ -0,0 +1,179 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"Shape" = type { i8, [2 x i32] }
+define i32 @"area" (%"Shape") {
+entry:
+    %1 = alloca %"Shape"
+    %_0 = alloca i32
+    %_1 = alloca %"Shape"
+    %_2 = alloca i32
+    %_3 = alloca i32
+    %_4 = alloca i32
+    store %"Shape" %0, ptr %1
+    %2 = load %"Shape", ptr %1
+    store %"Shape" %2, ptr %_1
+    %3 = getelementptr inbounds %"Shape", ptr %_1, i64 0, i32 0
+    %4 = load i8, ptr %3
+    switch i8 %4, label %match1.none [
+        i8 0, label %match1.arm0
+        i8 1, label %match1.arm1
+        i8 2, label %match1.arm2
+    ]
+    match1.arm0:
+    store i32 0, ptr %_0
+    br label %match1.end
+    match1.arm1:
+    %5 = getelementptr inbounds %"Shape", ptr %_1, i64 0, i32 1
+    %6 = getelementptr inbounds {i32}, ptr %5, i64 0, i32 0
+    %7 = load i32, ptr %6
+    store i32 %7, ptr %_2
+    %8 = load i32, ptr %_2
+    %9 = mul i32 3, %8
+    %10 = load i32, ptr %_2
+    %11 = mul i32 %9, %10
+    store i32 %11, ptr %_0
+    br label %match1.end
+    match1.arm2:
+    %12 = getelementptr inbounds %"Shape", ptr %_1, i64 0, i32 1
+    %13 = getelementptr inbounds {i32, i32}, ptr %12, i64 0, i32 0
+    %14 = load i32, ptr %13
+    store i32 %14, ptr %_3
+    %15 = getelementptr inbounds {i32, i32}, ptr %12, i64 0, i32 1
+    %16 = load i32, ptr %15
+    store i32 %16, ptr %_4
+    %17 = load i32, ptr %_3
+    %18 = load i32, ptr %_4
+    %19 = mul i32 %17, %18
+    store i32 %19, ptr %_0
+    br label %match1.end
+    match1.none:
+    unreachable
+    match1.end:
+    %20 = load i32, ptr %_0
+    ret i32 %20
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca i32
+    %_1 = alloca i32
+    %_2 = alloca i32
+    %_3 = alloca i8
+    %_4 = alloca i32
+    %_5 = alloca %"Shape"
+    %_6 = alloca %"Shape"
+    %_7 = alloca %"Shape"
+    %_8 = alloca i32
+    %_9 = alloca {i32, i32}
+    %_10 = alloca i32
+    store i32 1, ptr %_0
+    %0 = load i32, ptr %_0
+    store i32 %0, ptr %_2
+    %1 = load i32, ptr %_2
+    switch i32 %1, label %match2.arm1 [
+        i32 0, label %match2.arm0
+    ]
+    match2.arm0:
+    store i32 5, ptr %_1
+    br label %match2.end
+    match2.arm1:
+    store i32 6, ptr %_1
+    br label %match2.end
+    match2.none:
+    unreachable
+    match2.end:
+    %2 = load i32, ptr %_1
+    %3 = icmp ne i32 %2, 6
+    br i1 %3, label %4, label %5
+    4:
+        ret i32 1
+    %6 = load i32, ptr %_0
+    store i32 %6, ptr %_4
+    br label %match4.arm0
+    match4.arm0:
+    %7 = load i32, ptr %_4
+    %8 = icmp eq i32 %7, 1
+    br i1 %8, label %match4.ok8, label %match4.arm1
+    match4.ok8:
+    %9 = load i32, ptr %_1
+    %10 = icmp eq i32 %9, 5
+    br i1 %10, label %match4.body0, label %match4.arm1
+    match4.body0:
+    store i8 10, ptr %_3
+    br label %match4.end
+    match4.arm1:
+    %11 = load i32, ptr %_4
+    %12 = icmp eq i32 %11, 1
+    br i1 %12, label %match4.ok12, label %match4.arm2
+    match4.ok12:
+    store i8 20, ptr %_3
+    br label %match4.end
+    match4.arm2:
+    ret i32 2
+    match4.none:
+    unreachable
+    match4.end:
+    %13 = load i8, ptr %_3
+    %14 = icmp ne i8 %13, 20
+    br i1 %14, label %15, label %16
+    15:
+        ret i32 3
+    %17 = getelementptr inbounds %"Shape", ptr %_5, i64 0, i32 0
+    store i8 2, ptr %17
+    %18 = getelementptr inbounds %"Shape", ptr %_5, i64 0, i32 1
+    %19 = getelementptr inbounds {i32, i32}, ptr %18, i64 0, i32 0
+    store i32 2, ptr %19
+    %20 = getelementptr inbounds {i32, i32}, ptr %18, i64 0, i32 1
+    store i32 3, ptr %20
+    %21 = load %"Shape", ptr %_5
+    %22 = call i32 @"area" (%"Shape" %21)
+    %23 = getelementptr inbounds %"Shape", ptr %_6, i64 0, i32 0
+    store i8 1, ptr %23
+    %24 = getelementptr inbounds %"Shape", ptr %_6, i64 0, i32 1
+    %25 = getelementptr inbounds {i32}, ptr %24, i64 0, i32 0
+    store i32 1, ptr %25
+    %26 = load %"Shape", ptr %_6
+    %27 = call i32 @"area" (%"Shape" %26)
+    %28 = add i32 %22, %27
+    %29 = getelementptr inbounds %"Shape", ptr %_7, i64 0, i32 0
+    store i8 0, ptr %29
+    %30 = load %"Shape", ptr %_7
+    %31 = call i32 @"area" (%"Shape" %30)
+    %32 = add i32 %28, %31
+    %33 = icmp ne i32 %32, 9
+    br i1 %33, label %34, label %35
+    34:
+        ret i32 4
+    %36 = getelementptr inbounds {i32, i32}, ptr %_9, i64 0, i32 0
+    %37 = load i32, ptr %_0
+    store i32 %37, ptr %36
+    %38 = getelementptr inbounds {i32, i32}, ptr %_9, i64 0, i32 1
+    %39 = load i32, ptr %_1
+    store i32 %39, ptr %38
+    br label %match9.arm0
+    match9.arm0:
+    %40 = getelementptr inbounds {i32, i32}, ptr %_9, i64 0, i32 0
+    %41 = load i32, ptr %40
+    %42 = icmp eq i32 %41, 1
+    br i1 %42, label %match9.ok42, label %match9.arm1
+    match9.ok42:
+    %43 = getelementptr inbounds {i32, i32}, ptr %_9, i64 0, i32 1
+    %44 = load i32, ptr %43
+    %45 = icmp eq i32 %44, 6
+    br i1 %45, label %match9.ok45, label %match9.arm1
+    match9.ok45:
+    %46 = load i32, ptr %_1
+    %47 = mul i32 %46, 7
+    store i32 %47, ptr %_10
+    %48 = load i32, ptr %_10
+    store i32 %48, ptr %_8
+    br label %match9.end
+    match9.arm1:
+    store i32 0, ptr %_8
+    br label %match9.end
+    match9.none:
+    unreachable
+    match9.end:
+    %49 = load i32, ptr %_8
+    ret i32 %49
+}
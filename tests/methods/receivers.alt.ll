@@ -0,0 +1,130 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"geo::Point" = type { i32, i32 }
+define %"geo::Point" @"geo::Point::new" (i32, i32) {
+entry:
+    %2 = alloca i32
+    %3 = alloca i32
+    %_0 = alloca %"geo::Point"
+    store i32 %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = getelementptr inbounds %"geo::Point", ptr %_0, i64 0, i32 0
+    %5 = load i32, ptr %2
+    store i32 %5, ptr %4
+    %6 = getelementptr inbounds %"geo::Point", ptr %_0, i64 0, i32 1
+    %7 = load i32, ptr %3
+    store i32 %7, ptr %6
+    %8 = load %"geo::Point", ptr %_0
+    ret %"geo::Point" %8
+}
+define %"geo::Point" @"geo::Point::origin" () {
+entry:
+    %0 = tail call %"geo::Point" @"geo::Point::new" (i32 0, i32 0)
+    ret %"geo::Point" %0
+}
+define i32 @"geo::Point::sum" (%"geo::Point"*) {
+entry:
+    %1 = alloca %"geo::Point"*
+    store %"geo::Point"* %0, ptr %1
+    %2 = load %"geo::Point"*, ptr %1
+    %3 = getelementptr inbounds %"geo::Point", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"geo::Point"*, ptr %1
+    %6 = getelementptr inbounds %"geo::Point", ptr %5, i64 0, i32 1
+    %7 = load i32, ptr %6
+    %8 = add i32 %4, %7
+    ret i32 %8
+}
+define void @"geo::Point::shift" (%"geo::Point"*, i32) {
+entry:
+    %2 = alloca %"geo::Point"*
+    %3 = alloca i32
+    store %"geo::Point"* %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = load %"geo::Point"*, ptr %2
+    %5 = getelementptr inbounds %"geo::Point", ptr %4, i64 0, i32 0
+    %6 = load i32, ptr %5
+    %7 = load i32, ptr %3
+    %8 = add i32 %6, %7
+    %9 = load %"geo::Point"*, ptr %2
+    %10 = getelementptr inbounds %"geo::Point", ptr %9, i64 0, i32 0
+    store i32 %8, ptr %10
+    ret void
+}
+define %"geo::Point" @"geo::Point::swapped" (%"geo::Point") {
+entry:
+    %1 = alloca %"geo::Point"
+    %_0 = alloca %"geo::Point"
+    store %"geo::Point" %0, ptr %1
+    %2 = getelementptr inbounds %"geo::Point", ptr %_0, i64 0, i32 0
+    %3 = getelementptr inbounds %"geo::Point", ptr %1, i64 0, i32 1
+    %4 = load i32, ptr %3
+    store i32 %4, ptr %2
+    %5 = getelementptr inbounds %"geo::Point", ptr %_0, i64 0, i32 1
+    %6 = getelementptr inbounds %"geo::Point", ptr %1, i64 0, i32 0
+    %7 = load i32, ptr %6
+    store i32 %7, ptr %5
+    %8 = load %"geo::Point", ptr %_0
+    ret %"geo::Point" %8
+}
+define i32 @"geo::Point::hidden" (%"geo::Point"*) {
+entry:
+    %1 = alloca %"geo::Point"*
+    store %"geo::Point"* %0, ptr %1
+    %2 = load %"geo::Point"*, ptr %1
+    %3 = getelementptr inbounds %"geo::Point", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    ret i32 %4
+}
+define i32 @"geo::Point::twice" (%"geo::Point"*) {
+entry:
+    %1 = alloca %"geo::Point"*
+    store %"geo::Point"* %0, ptr %1
+    %2 = load %"geo::Point"*, ptr %1
+    %3 = call i32 @"geo::Point::hidden" (%"geo::Point"* %2)
+    %4 = mul i32 %3, 2
+    ret i32 %4
+}
+define i32 @"<geo::Point as Area>::area" (%"geo::Point"*) {
+entry:
+    %1 = alloca %"geo::Point"*
+    store %"geo::Point"* %0, ptr %1
+    %2 = load %"geo::Point"*, ptr %1
+    %3 = getelementptr inbounds %"geo::Point", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"geo::Point"*, ptr %1
+    %6 = getelementptr inbounds %"geo::Point", ptr %5, i64 0, i32 1
+    %7 = load i32, ptr %6
+    %8 = mul i32 %4, %7
+    ret i32 %8
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca %"geo::Point"
+    %_1 = alloca %"geo::Point"*
+    %_2 = alloca %"geo::Point"
+    %_3 = alloca %"geo::Point"
+    %0 = call %"geo::Point" @"geo::Point::new" (i32 1, i32 2)
+    store %"geo::Point" %0, ptr %_0
+    call void @"geo::Point::shift" (%"geo::Point"* %_0, i32 2)
+    store %"geo::Point"* %_0, ptr %_1
+    %1 = load %"geo::Point"*, ptr %_1
+    %2 = load %"geo::Point", ptr %1
+    %3 = call %"geo::Point" @"geo::Point::swapped" (%"geo::Point" %2)
+    store %"geo::Point" %3, ptr %_2
+    %4 = call %"geo::Point" @"geo::Point::origin" ()
+    store %"geo::Point" %4, ptr %_3
+    %5 = call i32 @"geo::Point::sum" (%"geo::Point"* %_0)
+    %6 = getelementptr inbounds %"geo::Point", ptr %_2, i64 0, i32 0
+    %7 = load i32, ptr %6
+    %8 = add i32 %5, %7
+    %9 = call i32 @"geo::Point::twice" (%"geo::Point"* %_0)
+    %10 = add i32 %8, %9
+    %11 = load %"geo::Point"*, ptr %_1
+    %12 = call i32 @"<geo::Point as Area>::area" (%"geo::Point"* %11)
+    %13 = add i32 %10, %12
+    %14 = call i32 @"geo::Point::sum" (%"geo::Point"* %_3)
+    %15 = add i32 %13, %14
+    ret i32 %15
+}
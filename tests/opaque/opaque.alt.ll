@@ -0,0 +1,61 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare ptr @fdopen (i32, ptr)
+declare i32 @fputs (ptr, ptr)
+declare i32 @fflush (ptr)
+%"Log" = type { ptr, i32 }
+define void @"line" (%"Log"*, %.slice) {
+entry:
+    %2 = alloca %"Log"*
+    %3 = alloca %.slice
+    store %"Log"* %0, ptr %2
+    store %.slice %1, ptr %3
+    %4 = load %.slice, ptr %3
+    %5 = extractvalue %.slice %4, 0
+    %6 = load %"Log"*, ptr %2
+    %7 = getelementptr inbounds %"Log", ptr %6, i64 0, i32 0
+    %8 = load ptr, ptr %7
+    %9 = call i32 @"fputs" (ptr %5, ptr %8)
+    %10 = load %"Log"*, ptr %2
+    %11 = getelementptr inbounds %"Log", ptr %10, i64 0, i32 1
+    %12 = load i32, ptr %11
+    %13 = add i32 %12, 1
+    %14 = load %"Log"*, ptr %2
+    %15 = getelementptr inbounds %"Log", ptr %14, i64 0, i32 1
+    store i32 %13, ptr %15
+    ret void
+}
+@.const.0 = constant [2 x i8] c"w\00"
+@.const.1 = constant [8 x i8] c"opaque\0A\00"
+@.const.2 = constant [7 x i8] c"types\0A\00"
+define i32 @"main" () {
+entry:
+    %_0 = alloca ptr
+    %_1 = alloca %"Log"
+    %_2 = alloca {ptr, i32}
+    %0 = extractvalue %.slice { ptr @.const.0, i64 2 }, 0
+    %1 = call ptr @"fdopen" (i32 1, ptr %0)
+    store ptr %1, ptr %_0
+    %2 = getelementptr inbounds %"Log", ptr %_1, i64 0, i32 0
+    %3 = load ptr, ptr %_0
+    store ptr %3, ptr %2
+    %4 = getelementptr inbounds %"Log", ptr %_1, i64 0, i32 1
+    store i32 0, ptr %4
+    call void @"line" (%"Log"* %_1, %.slice { ptr @.const.1, i64 8 })
+    call void @"line" (%"Log"* %_1, %.slice { ptr @.const.2, i64 7 })
+    %5 = getelementptr inbounds {ptr, i32}, ptr %_2, i64 0, i32 0
+    %6 = load ptr, ptr %_0
+    store ptr %6, ptr %5
+    %7 = getelementptr inbounds {ptr, i32}, ptr %_2, i64 0, i32 1
+    store i32 2, ptr %7
+    %8 = getelementptr inbounds {ptr, i32}, ptr %_2, i64 0, i32 0
+    %9 = load ptr, ptr %8
+    %10 = call i32 @"fflush" (ptr %9)
+    %11 = getelementptr inbounds %"Log", ptr %_1, i64 0, i32 1
+    %12 = load i32, ptr %11
+    %13 = getelementptr inbounds {ptr, i32}, ptr %_2, i64 0, i32 1
+    %14 = load i32, ptr %13
+    %15 = add i32 %12, %14
+    ret i32 %15
+}
@@ -0,0 +1,389 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i32 @putchar (i32)
+define i32 @"sum" (%.slice) {
+entry:
+    %1 = alloca %.slice
+    %_0 = alloca i32
+    %_1 = alloca %.slice
+    %_2 = alloca i64
+    %_3 = alloca i64
+    %_4 = alloca i32
+    store %.slice %0, ptr %1
+    store i32 0, ptr %_0
+    %2 = load %.slice, ptr %1
+    store %.slice %2, ptr %_1
+    %3 = load %.slice, ptr %_1
+    %4 = extractvalue %.slice %3, 1
+    store i64 %4, ptr %_2
+    store i64 0, ptr %_3
+    br label %5
+    5:
+        %6 = load i64, ptr %_3
+        %7 = load i64, ptr %_2
+        %8 = icmp ne i64 %6, %7
+        %9 = xor i1 %8, -1
+        br i1 %9, label %10, label %11
+        10:
+            br label %end.5
+        %12 = load %.slice, ptr %_1
+        %13 = extractvalue %.slice %12, 0
+        %14 = load i64, ptr %_3
+        %15 = getelementptr inbounds [0 x i32], ptr %13, i64 0, i64 %14
+        %16 = load i32, ptr %15
+        store i32 %16, ptr %_4
+        %17 = load i32, ptr %_0
+        %18 = load i32, ptr %_4
+        %19 = add i32 %17, %18
+        store i32 %19, ptr %_0
+        %20 = load i64, ptr %_3
+        %21 = add i64 %20, 1
+        store i64 %21, ptr %_3
+        br label %5
+    end.5:
+    %22 = load i32, ptr %_0
+    ret i32 %22
+}
+define void @"fill" (%.slice, i32) {
+entry:
+    %2 = alloca %.slice
+    %3 = alloca i32
+    %_0 = alloca i64
+    %_1 = alloca i64
+    %_2 = alloca i128
+    %_3 = alloca i128
+    %_4 = alloca i64
+    store %.slice %0, ptr %2
+    store i32 %1, ptr %3
+    store i64 0, ptr %_0
+    %4 = load %.slice, ptr %2
+    %5 = extractvalue %.slice %4, 1
+    store i64 %5, ptr %_1
+    store i128 0, ptr %_2
+    %6 = load i64, ptr %_0
+    %7 = load i64, ptr %_1
+    %8 = icmp ult i64 %6, %7
+    br i1 %8, label %9, label %14
+    9:
+        %10 = load i64, ptr %_1
+        %11 = load i64, ptr %_0
+        %12 = sub i64 %10, %11
+        %13 = zext i64 %12 to i128
+        store i128 %13, ptr %_2
+        br label %14
+    store i128 0, ptr %_3
+    br label %15
+    15:
+        %16 = load i128, ptr %_3
+        %17 = load i128, ptr %_2
+        %18 = icmp ne i128 %16, %17
+        %19 = xor i1 %18, -1
+        br i1 %19, label %20, label %21
+        20:
+            br label %end.15
+        %22 = load i64, ptr %_0
+        %23 = load i128, ptr %_3
+        %24 = trunc i128 %23 to i64
+        %25 = add i64 %22, %24
+        store i64 %25, ptr %_4
+        %26 = load i32, ptr %3
+        %27 = load %.slice, ptr %2
+        %28 = extractvalue %.slice %27, 0
+        %29 = load i64, ptr %_4
+        %30 = getelementptr inbounds [0 x i32], ptr %28, i64 0, i64 %29
+        store i32 %26, ptr %30
+        %31 = load i128, ptr %_3
+        %32 = add i128 %31, 1
+        store i128 %32, ptr %_3
+        br label %15
+    end.15:
+    ret void
+}
+define void @"print" (%.slice) {
+entry:
+    %1 = alloca %.slice
+    %_0 = alloca %.slice
+    %_1 = alloca i64
+    %_2 = alloca i64
+    %_3 = alloca i8
+    store %.slice %0, ptr %1
+    %2 = load %.slice, ptr %1
+    store %.slice %2, ptr %_0
+    %3 = load %.slice, ptr %_0
+    %4 = extractvalue %.slice %3, 1
+    store i64 %4, ptr %_1
+    store i64 0, ptr %_2
+    br label %5
+    5:
+        %6 = load i64, ptr %_2
+        %7 = load i64, ptr %_1
+        %8 = icmp ne i64 %6, %7
+        %9 = xor i1 %8, -1
+        br i1 %9, label %10, label %11
+        10:
+            br label %end.5
+        %12 = load %.slice, ptr %_0
+        %13 = extractvalue %.slice %12, 0
+        %14 = load i64, ptr %_2
+        %15 = getelementptr inbounds [0 x i8], ptr %13, i64 0, i64 %14
+        %16 = load i8, ptr %15
+        store i8 %16, ptr %_3
+        %17 = load i8, ptr %_3
+        %18 = zext i8 %17 to i32
+        %19 = call i32 @"putchar" (i32 %18)
+        %20 = load i64, ptr %_2
+        %21 = add i64 %20, 1
+        store i64 %21, ptr %_2
+        br label %5
+    end.5:
+    ret void
+}
+@.const.0 = constant [7 x i8] c"slices\0A"
+define i32 @"main" () {
+entry:
+    %_0 = alloca [6 x i32]
+    %_1 = alloca %.slice
+    %_2 = alloca %.slice
+    %_3 = alloca i64
+    %_4 = alloca i1
+    %_5 = alloca i64
+    %_6 = alloca %.slice
+    %_7 = alloca i64
+    %_8 = alloca i64
+    %_9 = alloca i64
+    %_10 = alloca %.slice
+    %_11 = alloca i64
+    %_12 = alloca i64
+    %_13 = alloca i64
+    %_14 = alloca i32
+    %_15 = alloca i64
+    %_16 = alloca i64
+    %_17 = alloca i64
+    %0 = getelementptr inbounds i32, ptr %_0, i64 0
+    store i32 1, ptr %0
+    %1 = getelementptr inbounds i32, ptr %_0, i64 1
+    store i32 2, ptr %1
+    %2 = getelementptr inbounds i32, ptr %_0, i64 2
+    store i32 3, ptr %2
+    %3 = getelementptr inbounds i32, ptr %_0, i64 3
+    store i32 4, ptr %3
+    %4 = getelementptr inbounds i32, ptr %_0, i64 4
+    store i32 5, ptr %4
+    %5 = getelementptr inbounds i32, ptr %_0, i64 5
+    store i32 6, ptr %5
+    %6 = insertvalue %.slice undef, ptr %_0, 0
+    %7 = insertvalue %.slice %6, i64 6, 1
+    store %.slice %7, ptr %_1
+    %8 = load %.slice, ptr %_1
+    %9 = extractvalue %.slice %8, 1
+    %10 = icmp ne i64 %9, 6
+    br i1 %10, label %11, label %12
+    11:
+        ret i32 1
+    %13 = insertvalue %.slice undef, ptr %_0, 0
+    %14 = insertvalue %.slice %13, i64 6, 1
+    %15 = call i32 @"sum" (%.slice %14)
+    %16 = icmp ne i32 %15, 21
+    br i1 %16, label %17, label %18
+    17:
+        ret i32 2
+    %19 = getelementptr inbounds [6 x i32], ptr %_0, i64 0, i64 1
+    %20 = icmp sgt i32 4, 1
+    br i1 %20, label %21, label %23
+    21:
+        %22 = sub i64 4, 1
+        store i64 %22, ptr %_3
+        br label %24
+    23:
+        store i64 0, ptr %_3
+        br label %24
+    %25 = load i64, ptr %_3
+    %26 = insertvalue %.slice undef, ptr %19, 0
+    %27 = insertvalue %.slice %26, i64 %25, 1
+    store %.slice %27, ptr %_2
+    %28 = load %.slice, ptr %_2
+    %29 = extractvalue %.slice %28, 1
+    %30 = icmp ne i64 %29, 3
+    br i1 %30, label %31, label %32
+    31:
+        ret i32 3
+    %33 = load %.slice, ptr %_2
+    %34 = extractvalue %.slice %33, 0
+    %35 = getelementptr inbounds [0 x i32], ptr %34, i64 0, i64 0
+    %36 = load i32, ptr %35
+    %37 = icmp ne i32 %36, 2
+    store i1 %37, ptr %_4
+    br i1 %37, label %end.38, label %38
+    %39 = load %.slice, ptr %_2
+    %40 = extractvalue %.slice %39, 0
+    %41 = getelementptr inbounds [0 x i32], ptr %40, i64 0, i64 2
+    %42 = load i32, ptr %41
+    %43 = icmp ne i32 %42, 4
+    store i1 %43, ptr %_4
+    br label %end.38
+    end.38:
+    %44 = load i1, ptr %_4
+    br i1 %44, label %45, label %46
+    45:
+        ret i32 4
+    %47 = getelementptr inbounds [6 x i32], ptr %_0, i64 0, i64 3
+    %48 = icmp ugt i64 6, 3
+    br i1 %48, label %49, label %51
+    49:
+        %50 = sub i64 6, 3
+        store i64 %50, ptr %_5
+        br label %52
+    51:
+        store i64 0, ptr %_5
+        br label %52
+    %53 = load i64, ptr %_5
+    %54 = insertvalue %.slice undef, ptr %47, 0
+    %55 = insertvalue %.slice %54, i64 %53, 1
+    %56 = call i32 @"sum" (%.slice %55)
+    %57 = icmp ne i32 %56, 15
+    br i1 %57, label %58, label %59
+    58:
+        ret i32 5
+    %60 = getelementptr inbounds [6 x i32], ptr %_0, i64 0, i64 0
+    %61 = insertvalue %.slice undef, ptr %60, 0
+    %62 = insertvalue %.slice %61, i64 2, 1
+    %63 = call i32 @"sum" (%.slice %62)
+    %64 = icmp ne i32 %63, 3
+    br i1 %64, label %65, label %66
+    65:
+        ret i32 6
+    %67 = load %.slice, ptr %_1
+    %68 = extractvalue %.slice %67, 0
+    %69 = getelementptr inbounds [0 x i32], ptr %68, i64 0, i64 2
+    %70 = load %.slice, ptr %_1
+    %71 = extractvalue %.slice %70, 1
+    %72 = icmp ugt i64 %71, 2
+    br i1 %72, label %73, label %77
+    73:
+        %74 = load %.slice, ptr %_1
+        %75 = extractvalue %.slice %74, 1
+        %76 = sub i64 %75, 2
+        store i64 %76, ptr %_7
+        br label %78
+    77:
+        store i64 0, ptr %_7
+        br label %78
+    %79 = load i64, ptr %_7
+    %80 = insertvalue %.slice undef, ptr %69, 0
+    %81 = insertvalue %.slice %80, i64 %79, 1
+    store %.slice %81, ptr %_6
+    %82 = load %.slice, ptr %_6
+    %83 = extractvalue %.slice %82, 0
+    %84 = getelementptr inbounds [0 x i32], ptr %83, i64 0, i64 1
+    %85 = icmp sgt i32 3, 1
+    br i1 %85, label %86, label %88
+    86:
+        %87 = sub i64 3, 1
+        store i64 %87, ptr %_8
+        br label %89
+    88:
+        store i64 0, ptr %_8
+        br label %89
+    %90 = load i64, ptr %_8
+    %91 = insertvalue %.slice undef, ptr %84, 0
+    %92 = insertvalue %.slice %91, i64 %90, 1
+    %93 = extractvalue %.slice %92, 0
+    %94 = getelementptr inbounds [0 x i32], ptr %93, i64 0, i64 0
+    %95 = load i32, ptr %94
+    %96 = icmp ne i32 %95, 4
+    br i1 %96, label %97, label %98
+    97:
+        ret i32 7
+    %99 = getelementptr inbounds [6 x i32], ptr %_0, i64 0, i64 4
+    %100 = icmp ugt i64 6, 4
+    br i1 %100, label %101, label %103
+    101:
+        %102 = sub i64 6, 4
+        store i64 %102, ptr %_9
+        br label %104
+    103:
+        store i64 0, ptr %_9
+        br label %104
+    %105 = load i64, ptr %_9
+    %106 = insertvalue %.slice undef, ptr %99, 0
+    %107 = insertvalue %.slice %106, i64 %105, 1
+    call void @"fill" (%.slice %107, i32 10)
+    %108 = getelementptr inbounds [6 x i32], ptr %_0, i64 0, i64 0
+    %109 = insertvalue %.slice undef, ptr %108, 0
+    %110 = insertvalue %.slice %109, i64 1, 1
+    call void @"fill" (%.slice %110, i32 7)
+    call void @"print" (%.slice { ptr @.const.0, i64 7 })
+    %111 = load %.slice, ptr %_2
+    store %.slice %111, ptr %_10
+    %112 = load %.slice, ptr %_10
+    %113 = extractvalue %.slice %112, 1
+    store i64 %113, ptr %_11
+    store i64 0, ptr %_12
+    br label %114
+    114:
+        %115 = load i64, ptr %_12
+        %116 = load i64, ptr %_11
+        %117 = icmp ne i64 %115, %116
+        %118 = xor i1 %117, -1
+        br i1 %118, label %119, label %120
+        119:
+            br label %end.114
+        %121 = load i64, ptr %_12
+        store i64 %121, ptr %_13
+        %122 = load %.slice, ptr %_10
+        %123 = extractvalue %.slice %122, 0
+        %124 = load i64, ptr %_12
+        %125 = getelementptr inbounds [0 x i32], ptr %123, i64 0, i64 %124
+        %126 = load i32, ptr %125
+        store i32 %126, ptr %_14
+        %127 = load i32, ptr %_14
+        %128 = load %.slice, ptr %_2
+        %129 = extractvalue %.slice %128, 0
+        %130 = load i64, ptr %_13
+        %131 = getelementptr inbounds [0 x i32], ptr %129, i64 0, i64 %130
+        %132 = load i32, ptr %131
+        %133 = icmp ne i32 %127, %132
+        br i1 %133, label %134, label %137
+        134:
+            %135 = load i64, ptr %_12
+            %136 = add i64 %135, 1
+            store i64 %136, ptr %_12
+            ret i32 8
+        %138 = load i64, ptr %_12
+        %139 = add i64 %138, 1
+        store i64 %139, ptr %_12
+        br label %114
+    end.114:
+    store i64 3, ptr %_15
+    store i64 1, ptr %_16
+    %140 = load %.slice, ptr %_1
+    %141 = extractvalue %.slice %140, 0
+    %142 = load i64, ptr %_15
+    %143 = getelementptr inbounds [0 x i32], ptr %141, i64 0, i64 %142
+    %144 = load i64, ptr %_16
+    %145 = load i64, ptr %_15
+    %146 = icmp ugt i64 %144, %145
+    br i1 %146, label %147, label %151
+    147:
+        %148 = load i64, ptr %_16
+        %149 = load i64, ptr %_15
+        %150 = sub i64 %148, %149
+        store i64 %150, ptr %_17
+        br label %152
+    151:
+        store i64 0, ptr %_17
+        br label %152
+    %153 = load i64, ptr %_17
+    %154 = insertvalue %.slice undef, ptr %143, 0
+    %155 = insertvalue %.slice %154, i64 %153, 1
+    %156 = extractvalue %.slice %155, 1
+    %157 = icmp ne i64 %156, 0
+    br i1 %157, label %158, label %159
+    158:
+        ret i32 9
+    %160 = insertvalue %.slice undef, ptr %_0, 0
+    %161 = insertvalue %.slice %160, i64 6, 1
+    %162 = call i32 @"sum" (%.slice %161)
+    ret i32 %162
+}
@@ -0,0 +1,161 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+declare i32 @puts (ptr)
+declare i64 @strlen (ptr)
+declare i64 @write (i32, ptr, i64)
+@.const.0 = constant [3 x i8] c"hi\00"
+@"GREETING" = constant %.slice { ptr @.const.0, i64 3 }
+define i32 @"count" (%.slice, i8) {
+entry:
+    %2 = alloca %.slice
+    %3 = alloca i8
+    %_0 = alloca i32
+    %_1 = alloca %.slice
+    %_2 = alloca i64
+    %_3 = alloca i64
+    %_4 = alloca i8
+    store %.slice %0, ptr %2
+    store i8 %1, ptr %3
+    store i32 0, ptr %_0
+    %4 = load %.slice, ptr %2
+    store %.slice %4, ptr %_1
+    %5 = load %.slice, ptr %_1
+    %6 = extractvalue %.slice %5, 1
+    store i64 %6, ptr %_2
+    store i64 0, ptr %_3
+    br label %7
+    7:
+        %8 = load i64, ptr %_3
+        %9 = load i64, ptr %_2
+        %10 = icmp ne i64 %8, %9
+        %11 = xor i1 %10, -1
+        br i1 %11, label %12, label %13
+        12:
+            br label %end.7
+        %14 = load %.slice, ptr %_1
+        %15 = extractvalue %.slice %14, 0
+        %16 = load i64, ptr %_3
+        %17 = getelementptr inbounds [0 x i8], ptr %15, i64 0, i64 %16
+        %18 = load i8, ptr %17
+        store i8 %18, ptr %_4
+        %19 = load i8, ptr %_4
+        %20 = load i8, ptr %3
+        %21 = icmp eq i8 %19, %20
+        br i1 %21, label %22, label %25
+        22:
+            %23 = load i32, ptr %_0
+            %24 = add i32 %23, 1
+            store i32 %24, ptr %_0
+            br label %25
+        %26 = load i64, ptr %_3
+        %27 = add i64 %26, 1
+        store i64 %27, ptr %_3
+        br label %7
+    end.7:
+    %28 = load i32, ptr %_0
+    ret i32 %28
+}
+@.const.1 = constant [8 x i8] c"strings\00"
+@.const.2 = constant [6 x i8] c"banana"
+@.const.3 = constant [4 x i8] c"abc\00"
+@.const.4 = constant [1 x i8] c"\0A"
+@.const.5 = constant [11 x i8] c"mississippi"
+define i32 @"main" () {
+entry:
+    %_0 = alloca %.slice
+    %_1 = alloca %.slice
+    %_2 = alloca i1
+    %_3 = alloca i1
+    %_4 = alloca %.slice
+    %_5 = alloca i64
+    %0 = extractvalue %.slice { ptr @.const.1, i64 8 }, 0
+    %1 = call i32 @"puts" (ptr %0)
+    store %.slice { ptr @.const.2, i64 6 }, ptr %_0
+    %2 = load %.slice, ptr %_0
+    %3 = extractvalue %.slice %2, 1
+    %4 = icmp ne i64 %3, 6
+    br i1 %4, label %5, label %6
+    5:
+        ret i32 1
+    %7 = load %.slice, ptr %_0
+    %8 = call i32 @"count" (%.slice %7, i8 97)
+    %9 = icmp ne i32 %8, 3
+    br i1 %9, label %10, label %11
+    10:
+        ret i32 2
+    %12 = extractvalue %.slice { ptr @.const.2, i64 6 }, 0
+    %13 = getelementptr inbounds [0 x i8], ptr %12, i64 0, i64 5
+    %14 = load i8, ptr %13
+    %15 = icmp ne i8 %14, 97
+    br i1 %15, label %16, label %17
+    16:
+        ret i32 3
+    store %.slice { ptr @.const.3, i64 4 }, ptr %_1
+    %18 = load %.slice, ptr %_1
+    %19 = extractvalue %.slice %18, 1
+    %20 = icmp ne i64 %19, 4
+    store i1 %20, ptr %_2
+    br i1 %20, label %end.21, label %21
+    %22 = load %.slice, ptr %_1
+    %23 = extractvalue %.slice %22, 0
+    %24 = getelementptr inbounds [0 x i8], ptr %23, i64 0, i64 3
+    %25 = load i8, ptr %24
+    %26 = icmp ne i8 %25, 0
+    store i1 %26, ptr %_2
+    br label %end.21
+    end.21:
+    %27 = load i1, ptr %_2
+    br i1 %27, label %28, label %29
+    28:
+        ret i32 4
+    %30 = load %.slice, ptr %_1
+    %31 = extractvalue %.slice %30, 0
+    %32 = call i64 @"strlen" (ptr %31)
+    %33 = icmp ne i64 %32, 3
+    br i1 %33, label %34, label %35
+    34:
+        ret i32 5
+    %36 = load %.slice, ptr @"GREETING"
+    %37 = extractvalue %.slice %36, 1
+    %38 = icmp ne i64 %37, 3
+    store i1 %38, ptr %_3
+    br i1 %38, label %end.39, label %39
+    %40 = load %.slice, ptr @"GREETING"
+    %41 = extractvalue %.slice %40, 0
+    %42 = getelementptr inbounds [0 x i8], ptr %41, i64 0, i64 1
+    %43 = load i8, ptr %42
+    %44 = icmp ne i8 %43, 105
+    store i1 %44, ptr %_3
+    br label %end.39
+    end.39:
+    %45 = load i1, ptr %_3
+    br i1 %45, label %46, label %47
+    46:
+        ret i32 6
+    %48 = load %.slice, ptr %_0
+    %49 = extractvalue %.slice %48, 0
+    %50 = getelementptr inbounds [0 x i8], ptr %49, i64 0, i64 1
+    %51 = icmp sgt i32 4, 1
+    br i1 %51, label %52, label %54
+    52:
+        %53 = sub i64 4, 1
+        store i64 %53, ptr %_5
+        br label %55
+    54:
+        store i64 0, ptr %_5
+        br label %55
+    %56 = load i64, ptr %_5
+    %57 = insertvalue %.slice undef, ptr %50, 0
+    %58 = insertvalue %.slice %57, i64 %56, 1
+    store %.slice %58, ptr %_4
+    %59 = load %.slice, ptr %_4
+    %60 = extractvalue %.slice %59, 0
+    %61 = load %.slice, ptr %_4
+    %62 = extractvalue %.slice %61, 1
+    %63 = call i64 @"write" (i32 1, ptr %60, i64 %62)
+    %64 = extractvalue %.slice { ptr @.const.4, i64 1 }, 0
+    %65 = call i64 @"write" (i32 1, ptr %64, i64 1)
+    %66 = call i32 @"count" (%.slice { ptr @.const.5, i64 11 }, i8 115)
+    ret i32 %66
+}
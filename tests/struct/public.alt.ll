@@ -0,0 +1,24 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"geo::P" = type { i32 }
+%"geo::Q" = type { %"geo::P" }
+define %"geo::Q" @"geo::q" () {
+entry:
+    %_0 = alloca %"geo::Q"
+    %0 = getelementptr inbounds %"geo::Q", ptr %_0, i64 0, i32 0
+    %1 = getelementptr inbounds %"geo::P", ptr %0, i64 0, i32 0
+    store i32 3, ptr %1
+    %2 = load %"geo::Q", ptr %_0
+    ret %"geo::Q" %2
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca %"geo::Q"
+    %0 = call %"geo::Q" @"geo::q" ()
+    store %"geo::Q" %0, ptr %_0
+    %1 = getelementptr inbounds %"geo::Q", ptr %_0, i64 0, i32 0
+    %2 = getelementptr inbounds %"geo::P", ptr %1, i64 0, i32 0
+    %3 = load i32, ptr %2
+    ret i32 %3
+}
@@ -0,0 +1,128 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"Square" = type { i32 }
+%"Rect" = type { i32, i32 }
+define i32 @"<Square as Shape>::area" (%"Square"*) {
+entry:
+    %1 = alloca %"Square"*
+    store %"Square"* %0, ptr %1
+    %2 = load %"Square"*, ptr %1
+    %3 = getelementptr inbounds %"Square", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"Square"*, ptr %1
+    %6 = getelementptr inbounds %"Square", ptr %5, i64 0, i32 0
+    %7 = load i32, ptr %6
+    %8 = mul i32 %4, %7
+    ret i32 %8
+}
+define void @"<Square as Shape>::grow" (%"Square"*, i32) {
+entry:
+    %2 = alloca %"Square"*
+    %3 = alloca i32
+    store %"Square"* %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = load %"Square"*, ptr %2
+    %5 = getelementptr inbounds %"Square", ptr %4, i64 0, i32 0
+    %6 = load i32, ptr %5
+    %7 = load i32, ptr %3
+    %8 = add i32 %6, %7
+    %9 = load %"Square"*, ptr %2
+    %10 = getelementptr inbounds %"Square", ptr %9, i64 0, i32 0
+    store i32 %8, ptr %10
+    ret void
+}
+define i32 @"<Rect as Shape>::area" (%"Rect"*) {
+entry:
+    %1 = alloca %"Rect"*
+    store %"Rect"* %0, ptr %1
+    %2 = load %"Rect"*, ptr %1
+    %3 = getelementptr inbounds %"Rect", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"Rect"*, ptr %1
+    %6 = getelementptr inbounds %"Rect", ptr %5, i64 0, i32 1
+    %7 = load i32, ptr %6
+    %8 = mul i32 %4, %7
+    ret i32 %8
+}
+define void @"<Rect as Shape>::grow" (%"Rect"*, i32) {
+entry:
+    %2 = alloca %"Rect"*
+    %3 = alloca i32
+    store %"Rect"* %0, ptr %2
+    store i32 %1, ptr %3
+    %4 = load %"Rect"*, ptr %2
+    %5 = getelementptr inbounds %"Rect", ptr %4, i64 0, i32 0
+    %6 = load i32, ptr %5
+    %7 = load i32, ptr %3
+    %8 = add i32 %6, %7
+    %9 = load %"Rect"*, ptr %2
+    %10 = getelementptr inbounds %"Rect", ptr %9, i64 0, i32 0
+    store i32 %8, ptr %10
+    ret void
+}
+define i32 @"area_of" ({ ptr, ptr }) {
+entry:
+    %1 = alloca { ptr, ptr }
+    store { ptr, ptr } %0, ptr %1
+    %2 = load { ptr, ptr }, ptr %1
+    %3 = extractvalue { ptr, ptr } %2, 1
+    %4 = getelementptr inbounds ptr, ptr %3, i64 0
+    %5 = load ptr, ptr %4
+    %6 = load { ptr, ptr }, ptr %1
+    %7 = extractvalue { ptr, ptr } %6, 0
+    %8 = call i32 %5 (ptr %7)
+    ret i32 %8
+}
+define void @"enlarge" ({ ptr, ptr }) {
+entry:
+    %1 = alloca { ptr, ptr }
+    store { ptr, ptr } %0, ptr %1
+    %2 = load { ptr, ptr }, ptr %1
+    %3 = extractvalue { ptr, ptr } %2, 1
+    %4 = getelementptr inbounds ptr, ptr %3, i64 1
+    %5 = load ptr, ptr %4
+    %6 = load { ptr, ptr }, ptr %1
+    %7 = extractvalue { ptr, ptr } %6, 0
+    call void %5 (ptr %7, i32 1)
+    ret void
+}
+@.const.0 = constant [2 x ptr] [ptr @"<Square as Shape>::area", ptr @"<Square as Shape>::grow"]
+@.const.1 = constant [2 x ptr] [ptr @"<Rect as Shape>::area", ptr @"<Rect as Shape>::grow"]
+define i32 @"main" () {
+entry:
+    %_0 = alloca %"Square"
+    %_1 = alloca %"Rect"
+    %_2 = alloca { ptr, ptr }
+    %0 = getelementptr inbounds %"Square", ptr %_0, i64 0, i32 0
+    store i32 3, ptr %0
+    %1 = getelementptr inbounds %"Rect", ptr %_1, i64 0, i32 0
+    store i32 2, ptr %1
+    %2 = getelementptr inbounds %"Rect", ptr %_1, i64 0, i32 1
+    store i32 5, ptr %2
+    %3 = insertvalue { ptr, ptr } undef, ptr %_0, 0
+    %4 = insertvalue { ptr, ptr } %3, ptr @.const.0, 1
+    call void @"enlarge" ({ ptr, ptr } %4)
+    %5 = insertvalue { ptr, ptr } undef, ptr %_1, 0
+    %6 = insertvalue { ptr, ptr } %5, ptr @.const.1, 1
+    call void @"enlarge" ({ ptr, ptr } %6)
+    %7 = insertvalue { ptr, ptr } undef, ptr %_1, 0
+    %8 = insertvalue { ptr, ptr } %7, ptr @.const.1, 1
+    store { ptr, ptr } %8, ptr %_2
+    %9 = insertvalue { ptr, ptr } undef, ptr %_0, 0
+    %10 = insertvalue { ptr, ptr } %9, ptr @.const.0, 1
+    %11 = call i32 @"area_of" ({ ptr, ptr } %10)
+    %12 = insertvalue { ptr, ptr } undef, ptr %_1, 0
+    %13 = insertvalue { ptr, ptr } %12, ptr @.const.1, 1
+    %14 = call i32 @"area_of" ({ ptr, ptr } %13)
+    %15 = add i32 %11, %14
+    %16 = load { ptr, ptr }, ptr %_2
+    %17 = extractvalue { ptr, ptr } %16, 1
+    %18 = getelementptr inbounds ptr, ptr %17, i64 0
+    %19 = load ptr, ptr %18
+    %20 = load { ptr, ptr }, ptr %_2
+    %21 = extractvalue { ptr, ptr } %20, 0
+    %22 = call i32 %19 (ptr %21)
+    %23 = add i32 %15, %22
+    ret i32 %23
+}
@@ -0,0 +1,115 @@
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+%.slice = type { ptr, i64 }
+%"geo::Square" = type { i32 }
+define i32 @"geo::<geo::Square as geo::Shape>::area" (%"geo::Square"*) {
+entry:
+    %1 = alloca %"geo::Square"*
+    store %"geo::Square"* %0, ptr %1
+    %2 = load %"geo::Square"*, ptr %1
+    %3 = getelementptr inbounds %"geo::Square", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"geo::Square"*, ptr %1
+    %6 = getelementptr inbounds %"geo::Square", ptr %5, i64 0, i32 0
+    %7 = load i32, ptr %6
+    %8 = mul i32 %4, %7
+    ret i32 %8
+}
+define i32 @"geo::<geo::Square as geo::Shape>::sides" (%"geo::Square"*) {
+entry:
+    %1 = alloca %"geo::Square"*
+    store %"geo::Square"* %0, ptr %1
+    ret i32 4
+}
+%"Triangle" = type { i32, i32 }
+define i32 @"<Triangle as geo::Shape>::area" (%"Triangle"*) {
+entry:
+    %1 = alloca %"Triangle"*
+    store %"Triangle"* %0, ptr %1
+    %2 = load %"Triangle"*, ptr %1
+    %3 = getelementptr inbounds %"Triangle", ptr %2, i64 0, i32 0
+    %4 = load i32, ptr %3
+    %5 = load %"Triangle"*, ptr %1
+    %6 = getelementptr inbounds %"Triangle", ptr %5, i64 0, i32 1
+    %7 = load i32, ptr %6
+    %8 = mul i32 %4, %7
+    %9 = sdiv i32 %8, 2
+    ret i32 %9
+}
+define i32 @"<Triangle as geo::Shape>::sides" (%"Triangle"*) {
+entry:
+    %1 = alloca %"Triangle"*
+    store %"Triangle"* %0, ptr %1
+    ret i32 3
+}
+define i8 @"<Triangle as Named>::id" (%"Triangle") {
+entry:
+    %1 = alloca %"Triangle"
+    store %"Triangle" %0, ptr %1
+    ret i8 1
+}
+define i8 @"<i32 as Named>::id" (i32) {
+entry:
+    %1 = alloca i32
+    store i32 %0, ptr %1
+    %2 = load i32, ptr %1
+    %3 = trunc i32 %2 to i8
+    ret i8 %3
+}
+define i32 @"total<geo::Square>" (%"geo::Square"*) {
+entry:
+    %1 = alloca %"geo::Square"*
+    store %"geo::Square"* %0, ptr %1
+    %2 = load %"geo::Square"*, ptr %1
+    %3 = call i32 @"geo::<geo::Square as geo::Shape>::area" (%"geo::Square"* %2)
+    %4 = load %"geo::Square"*, ptr %1
+    %5 = call i32 @"geo::<geo::Square as geo::Shape>::sides" (%"geo::Square"* %4)
+    %6 = add i32 %3, %5
+    ret i32 %6
+}
+define i32 @"total<Triangle>" (%"Triangle"*) {
+entry:
+    %1 = alloca %"Triangle"*
+    store %"Triangle"* %0, ptr %1
+    %2 = load %"Triangle"*, ptr %1
+    %3 = call i32 @"<Triangle as geo::Shape>::area" (%"Triangle"* %2)
+    %4 = load %"Triangle"*, ptr %1
+    %5 = call i32 @"<Triangle as geo::Shape>::sides" (%"Triangle"* %4)
+    %6 = add i32 %3, %5
+    ret i32 %6
+}
+define i32 @"both<Triangle>" (%"Triangle") {
+entry:
+    %1 = alloca %"Triangle"
+    store %"Triangle" %0, ptr %1
+    %2 = call i32 @"<Triangle as geo::Shape>::area" (%"Triangle"* %1)
+    %3 = load %"Triangle", ptr %1
+    %4 = call i8 @"<Triangle as Named>::id" (%"Triangle" %3)
+    %5 = zext i8 %4 to i32
+    %6 = add i32 %2, %5
+    ret i32 %6
+}
+define i32 @"main" () {
+entry:
+    %_0 = alloca %"geo::Square"
+    %_1 = alloca %"Triangle"
+    %_2 = alloca i32
+    %0 = getelementptr inbounds %"geo::Square", ptr %_0, i64 0, i32 0
+    store i32 3, ptr %0
+    %1 = getelementptr inbounds %"Triangle", ptr %_1, i64 0, i32 0
+    store i32 4, ptr %1
+    %2 = getelementptr inbounds %"Triangle", ptr %_1, i64 0, i32 1
+    store i32 5, ptr %2
+    store i32 7, ptr %_2
+    %3 = call i32 @"total<geo::Square>" (%"geo::Square"* %_0)
+    %4 = call i32 @"total<Triangle>" (%"Triangle"* %_1)
+    %5 = add i32 %3, %4
+    %6 = load %"Triangle", ptr %_1
+    %7 = call i32 @"both<Triangle>" (%"Triangle" %6)
+    %8 = add i32 %5, %7
+    %9 = load i32, ptr %_2
+    %10 = call i8 @"<i32 as Named>::id" (i32 %9)
+    %11 = zext i8 %10 to i32
+    %12 = add i32 %8, %11
+    ret i32 %12
+}